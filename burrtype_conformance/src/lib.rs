@@ -0,0 +1,211 @@
+//! Checks that what serde writes for a type matches what burrtype exports for it
+//! Sample values are serialized with `serde_json` and validated against the type's IR, then read back to make sure they survive the trip
+//!
+//! Types registered with `samples!` are all checked by `Harness::registered`, which also fails for any type registered with `#[derive(Burr)]` that nothing checked
+//!
+//! ```ignore
+//! use burrtype_conformance::{samples, Harness, Sample};
+//!
+//! impl Sample for Shape {
+//!     fn samples() -> Vec<Self> {
+//!         vec![Shape::Circle { radius: 1.0 }, Shape::Empty]
+//!     }
+//! }
+//! samples!(Shape);
+//!
+//! #[test]
+//! fn conformance() {
+//!     Harness::registered().assert();
+//! }
+//! ```
+
+mod validate;
+
+pub use validate::*;
+
+use burrtype::export::Burrxporter;
+use burrtype::ir::{Direction, IrExt, IrItem, TypeKey};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+#[doc(hidden)]
+pub use burrtype::linkme;
+
+/// Provides values covering the shapes a type can take, such as one for each enum variant
+pub trait Sample: Sized {
+    fn samples() -> Vec<Self>;
+}
+
+/// Adds each type registered with `samples!` to a harness
+#[burrtype::linkme::distributed_slice]
+#[linkme(crate = burrtype::linkme)]
+pub static SAMPLED: [fn(Harness) -> Harness] = [..];
+
+/// Registers types implementing [`Sample`] to be checked by `Harness::registered`
+#[macro_export]
+macro_rules! samples {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            const _: () = {
+                #[$crate::linkme::distributed_slice($crate::SAMPLED)]
+                #[linkme(crate = $crate::linkme)]
+                static SAMPLED: fn($crate::Harness) -> $crate::Harness = $crate::Harness::with_type::<$ty>;
+            };
+        )+
+    };
+}
+
+/// A sample whose JSON does not match the IR of its type
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    /// Rust path of the type being checked
+    pub item: &'static str,
+    /// The sample, as serde wrote it
+    pub json: String,
+    pub invalid: Invalid,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}`: {}\n  json: {}", self.item, self.invalid, self.json)
+    }
+}
+
+/// Validators for what serde writes and what it reads
+struct Validators<'r> {
+    write: Validator<'r>,
+    read: Validator<'r>,
+}
+
+type Check = Box<dyn Fn(&Validators) -> Vec<Mismatch>>;
+
+/// Collects types along with their samples, and checks each sample against the IR
+pub struct Harness {
+    registry: HashMap<TypeKey, IrItem>,
+    checks: Vec<Check>,
+    /// Types that have samples, or were left out on purpose
+    covered: HashSet<TypeKey>,
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Harness {
+    /// Sets up a harness knowing every type registered with `#[derive(Burr)]`, so fields can be followed into them
+    pub fn new() -> Self {
+        Harness {
+            registry: Burrxporter::new().type_registry,
+            checks: Vec::new(),
+            covered: HashSet::new(),
+        }
+    }
+
+    /// Sets up a harness checking every type registered with `samples!`
+    pub fn registered() -> Self {
+        SAMPLED.iter().fold(Harness::new(), |harness, add| add(harness))
+    }
+
+    /// Checks every sample of a type
+    pub fn with_type<T: IrExt + Sample + Serialize + DeserializeOwned + 'static>(self) -> Self {
+        self.with_samples(T::samples)
+    }
+
+    /// Checks samples of a type from somewhere other than [`Sample`], such as for types from another crate
+    pub fn with_samples<T, F>(mut self, samples: F) -> Self
+    where
+        T: IrExt + Serialize + DeserializeOwned + 'static,
+        F: Fn() -> Vec<T> + 'static,
+    {
+        let ir = T::get_ir();
+        let item = ir.path();
+        let id = ir.type_id();
+        self.registry.entry(id).or_insert(ir);
+        self.covered.insert(id);
+
+        self.checks.push(Box::new(move |validators| {
+            samples().iter()
+                .filter_map(|sample| check(validators, &id, item, sample).err())
+                .collect()
+        }));
+        self
+    }
+
+    /// Leaves a type out of those reported by `unchecked`, such as one serde only ever writes
+    pub fn skip<T: IrExt>(mut self) -> Self {
+        self.covered.insert(T::get_ir().type_id());
+        self
+    }
+
+    /// Rust paths of the structs and enums registered with `#[derive(Burr)]` that have no samples here
+    pub fn unchecked(&self) -> Vec<&'static str> {
+        let mut unchecked: Vec<&'static str> = self.registry.values()
+            .filter(|item| !matches!(item, IrItem::Const(_) | IrItem::Alias(_)) && !self.covered.contains(&item.type_id()))
+            .map(IrItem::path)
+            .collect();
+        unchecked.sort_unstable();
+        unchecked
+    }
+
+    /// Checks every sample, returning those that do not match
+    pub fn run(&self) -> Vec<Mismatch> {
+        let validators = Validators {
+            write: Validator::new(&self.registry),
+            read: Validator::new(&self.registry).with_direction(Direction::Deserialize),
+        };
+        self.checks.iter().flat_map(|check| check(&validators)).collect()
+    }
+
+    /// Checks every sample, panicking with a list of those that do not match
+    /// Also panics when a registered type was never checked, so new types cannot go unnoticed
+    pub fn assert(&self) {
+        let mismatches = self.run();
+        if !mismatches.is_empty() {
+            let list: Vec<String> = mismatches.iter().map(ToString::to_string).collect();
+            panic!("{} sample(s) do not match their exported types:\n{}", mismatches.len(), list.join("\n"));
+        }
+
+        let unchecked = self.unchecked();
+        if !unchecked.is_empty() {
+            panic!("{} registered type(s) have no samples, register them with `samples!` or leave them out with `skip`:\n{}", unchecked.len(), unchecked.join("\n"));
+        }
+    }
+}
+
+fn check<T: Serialize + DeserializeOwned>(validators: &Validators, id: &TypeKey, item: &'static str, sample: &T) -> Result<(), Mismatch> {
+    let mismatch = |json: &serde_json::Value, invalid: Invalid| Mismatch {
+        item,
+        json: json.to_string(),
+        invalid,
+    };
+
+    let json = serde_json::to_value(sample)
+        .map_err(|e| mismatch(&serde_json::Value::Null, Invalid::new("$", format!("failed to serialize: {e}"))))?;
+    validators.write.validate(id, &json).map_err(|invalid| mismatch(&json, invalid))?;
+
+    // serde decides whether what it wrote can be read back, and the read side of the IR has to agree either way
+    // fields renamed or skipped one way are written in a shape neither reads
+    let read = match (serde_json::from_value::<T>(json.clone()), validators.read.validate(id, &json)) {
+        (Ok(read), Ok(())) => read,
+        (Err(_), Err(_)) => return Ok(()),
+        (Ok(_), Err(invalid)) => {
+            return Err(mismatch(&json, Invalid::new(invalid.at, format!("read by serde, but rejected when reading: {}", invalid.message))));
+        }
+        (Err(e), Ok(())) => {
+            return Err(mismatch(&json, Invalid::new("$", format!("accepted when reading, but serde failed to deserialize: {e}"))));
+        }
+    };
+
+    // serde reads some shapes it never writes, so only what it wrote is expected to come back
+    let rewritten = serde_json::to_value(&read)
+        .map_err(|e| mismatch(&json, Invalid::new("$", format!("failed to serialize again: {e}"))))?;
+    if rewritten != json {
+        return Err(mismatch(&json, Invalid::new("$", format!("changed after a round trip, to {rewritten}"))));
+    }
+
+    Ok(())
+}
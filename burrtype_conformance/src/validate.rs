@@ -0,0 +1,331 @@
+use burrtype::ir::{Direction, EnumRepr, IntStyle, IrEnum, IrEnumVariant, IrFlattened, IrItem, IrNamedField, IrType, IrUnnamedField, TypeKey};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// Where a value stops matching its IR, and why
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Invalid {
+    /// Path into the value, such as `$.items[0].name`
+    pub at: String,
+    pub message: String,
+}
+
+impl Invalid {
+    pub fn new<A: Into<String>, M: Into<String>>(at: A, message: M) -> Self {
+        Invalid {
+            at: at.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for Invalid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.at, self.message)
+    }
+}
+
+/// JSON that builtin types are written as
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Builtin {
+    String,
+    Bool,
+    Integer,
+    Float,
+}
+
+/// Checks JSON values against the shape IR describes for them
+/// Types the registry knows nothing about, such as those given a name with `TypeScript::with_type_name`, accept any value
+pub struct Validator<'r> {
+    registry: &'r HashMap<TypeKey, IrItem>,
+    builtins: HashMap<TypeKey, Builtin>,
+    direction: Direction,
+}
+
+impl<'r> Validator<'r> {
+    pub fn new(registry: &'r HashMap<TypeKey, IrItem>) -> Self {
+        let builtins = HashMap::from([
+            (TypeKey::of::<String>(), Builtin::String),
+            (TypeKey::of::<str>(), Builtin::String),
+            (TypeKey::of::<char>(), Builtin::String),
+            (TypeKey::of::<bool>(), Builtin::Bool),
+            (TypeKey::of::<u8>(), Builtin::Integer),
+            (TypeKey::of::<u16>(), Builtin::Integer),
+            (TypeKey::of::<u32>(), Builtin::Integer),
+            (TypeKey::of::<u64>(), Builtin::Integer),
+            (TypeKey::of::<u128>(), Builtin::Integer),
+            (TypeKey::of::<usize>(), Builtin::Integer),
+            (TypeKey::of::<i8>(), Builtin::Integer),
+            (TypeKey::of::<i16>(), Builtin::Integer),
+            (TypeKey::of::<i32>(), Builtin::Integer),
+            (TypeKey::of::<i64>(), Builtin::Integer),
+            (TypeKey::of::<i128>(), Builtin::Integer),
+            (TypeKey::of::<isize>(), Builtin::Integer),
+            (TypeKey::of::<f32>(), Builtin::Float),
+            (TypeKey::of::<f64>(), Builtin::Float),
+            (TypeKey::of::<serde_json::Number>(), Builtin::Float),
+            (TypeKey::of::<std::path::PathBuf>(), Builtin::String),
+            (TypeKey::of::<std::path::Path>(), Builtin::String),
+            (TypeKey::of::<std::net::IpAddr>(), Builtin::String),
+            (TypeKey::of::<std::net::Ipv4Addr>(), Builtin::String),
+            (TypeKey::of::<std::net::Ipv6Addr>(), Builtin::String),
+            (TypeKey::of::<std::net::SocketAddr>(), Builtin::String),
+            (TypeKey::of::<std::net::SocketAddrV4>(), Builtin::String),
+            (TypeKey::of::<std::net::SocketAddrV6>(), Builtin::String),
+        ]);
+
+        Validator {
+            registry,
+            builtins,
+            direction: Direction::Serialize,
+        }
+    }
+
+    /// Checks values as serde reads them rather than as it writes them
+    pub fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Checks a value against the registered type with the given key
+    pub fn validate(&self, id: &TypeKey, value: &Value) -> Result<(), Invalid> {
+        self.key(id, value, "$")
+    }
+
+    /// Checks a value against an item
+    pub fn validate_item(&self, item: &IrItem, value: &Value) -> Result<(), Invalid> {
+        self.item(item, value, "$")
+    }
+
+    fn item(&self, item: &IrItem, value: &Value, at: &str) -> Result<(), Invalid> {
+        match &item.view(self.direction) {
+            IrItem::NamedStruct(ir) => match &ir.tag {
+                Some(tag) => {
+                    let map = object(value, at)?;
+                    // serde never looks at the tag when reading, whatever it holds
+                    if self.direction == Direction::Serialize {
+                        let name = tag_value(map, tag.field, at)?;
+                        expect(name == tag.value, &map[tag.field], &format!("\"{}\"", tag.value), &format!("{at}.{}", tag.field))?;
+                    }
+                    self.object(&ir.fields, &ir.flattened, value, at, &[tag.field])
+                }
+                None => self.object(&ir.fields, &ir.flattened, value, at, &[]),
+            },
+            // newtypes are written as the type they wrap
+            IrItem::TupleStruct(ir) if ir.fields.len() == 1 => self.field(&ir.fields[0].ty, ir.fields[0].literals.any, ir.fields[0].int_style, value, at),
+            IrItem::TupleStruct(ir) => self.tuple(&ir.fields, value, at),
+            IrItem::UnitStruct(_) => expect(value.is_null(), value, "null", at),
+            IrItem::Enum(ir) => self.enumeration(ir, value, at),
+            IrItem::Const(ir) => {
+                let expected: Value = serde_json::from_str(&ir.value).expect("constants are serialized as JSON");
+                expect(value == &expected, value, &ir.value, at)
+            }
+            IrItem::Alias(ir) => self.ty(&ir.ty, value, at),
+        }
+    }
+
+    fn enumeration(&self, ir: &IrEnum, value: &Value, at: &str) -> Result<(), Invalid> {
+        match ir.repr {
+            EnumRepr::External => match value {
+                Value::String(name) => match variant(ir, name, at)? {
+                    IrEnumVariant::Unit(_) => Ok(()),
+                    _ => Err(Invalid::new(at, format!("variant `{name}` has fields, so it should be written as an object"))),
+                },
+                Value::Object(map) if map.len() == 1 => {
+                    let (name, content) = map.iter().next().unwrap();
+                    match variant(ir, name, at)? {
+                        IrEnumVariant::Unit(_) => Err(Invalid::new(at, format!("unit variant `{name}` should be written as a string"))),
+                        var => self.variant(var, content, &format!("{at}.{name}")),
+                    }
+                }
+                _ => Err(Invalid::new(at, format!("expected a string or an object with one key, found {}", kind(value)))),
+            },
+            EnumRepr::Untagged => {
+                if ir.variants.iter().any(|var| self.variant(var, value, at).is_ok()) {
+                    Ok(())
+                }
+                else {
+                    Err(Invalid::new(at, format!("matches no variant of `{}`", ir.name())))
+                }
+            }
+            EnumRepr::Internal(tag) => {
+                let map = object(value, at)?;
+                let name = tag_value(map, tag, at)?;
+                match variant(ir, name, at)? {
+                    IrEnumVariant::Unit(_) => self.object(&[], &[], value, at, &[tag]),
+                    IrEnumVariant::Struct(var) => self.object(&var.fields, &var.flattened, value, at, &[tag]),
+                    // the wrapped type's fields sit alongside the tag
+                    IrEnumVariant::Tuple(var) if var.fields.len() == 1 => {
+                        let mut rest = map.clone();
+                        rest.remove(tag);
+                        self.field(&var.fields[0].ty, var.fields[0].literals.any, var.fields[0].int_style, &Value::Object(rest), at)
+                    }
+                    IrEnumVariant::Tuple(_) => Err(Invalid::new(at, format!("variant `{name}` is a tuple, which serde cannot tag internally"))),
+                }
+            }
+            EnumRepr::Adjacent { tag, content } => {
+                let map = object(value, at)?;
+                let name = tag_value(map, tag, at)?;
+                if let Some(key) = map.keys().find(|key| *key != tag && *key != content) {
+                    return Err(Invalid::new(at, format!("unexpected field `{key}`")));
+                }
+                match (variant(ir, name, at)?, map.get(content)) {
+                    (IrEnumVariant::Unit(_), None) => Ok(()),
+                    (IrEnumVariant::Unit(_), Some(_)) => Err(Invalid::new(at, format!("unit variant `{name}` should not have `{content}`"))),
+                    (var, Some(value)) => self.variant(var, value, &format!("{at}.{content}")),
+                    (_, None) => Err(Invalid::new(at, format!("missing field `{content}`"))),
+                }
+            }
+            EnumRepr::Int => {
+                let found = value.as_i64().map(i128::from).or(value.as_u64().map(i128::from))
+                    .is_some_and(|n| ir.variants.iter().any(|var| matches!(var, IrEnumVariant::Unit(unit) if unit.discriminant == Some(n))));
+                expect(found, value, &format!("a discriminant of `{}`", ir.name()), at)
+            }
+        }
+    }
+
+    /// Checks the contents of a variant, as written without any tag
+    fn variant(&self, var: &IrEnumVariant, value: &Value, at: &str) -> Result<(), Invalid> {
+        match var {
+            IrEnumVariant::Struct(var) => self.object(&var.fields, &var.flattened, value, at, &[]),
+            IrEnumVariant::Tuple(var) if var.fields.len() == 1 => self.field(&var.fields[0].ty, var.fields[0].literals.any, var.fields[0].int_style, value, at),
+            IrEnumVariant::Tuple(var) => self.tuple(&var.fields, value, at),
+            IrEnumVariant::Unit(_) => expect(value.is_null(), value, "null", at),
+        }
+    }
+
+    /// Checks an object has exactly the given fields, along with any `extra` keys such as tags
+    /// Keys left over are checked against whatever was flattened in
+    fn object(&self, fields: &[IrNamedField], flattened: &[IrFlattened], value: &Value, at: &str, extra: &[&str]) -> Result<(), Invalid> {
+        let map = object(value, at)?;
+
+        for field in fields {
+            let name = unraw(&field.name());
+            match map.get(&name) {
+                Some(value) => self.field(&field.ty, field.literals.any, field.int_style, value, &format!("{at}.{name}"))?,
+                // serde writes `None` as null, so only skipped fields are ever missing from what it writes
+                None if field.skip_serializing_if || field.default => {}
+                None => return Err(Invalid::new(at, format!("missing field `{name}`"))),
+            }
+        }
+
+        let rest: Map<String, Value> = map.iter()
+            .filter(|(key, _)| !extra.contains(&key.as_str()) && !fields.iter().any(|field| &unraw(&field.name()) == *key))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+
+        match flattened {
+            [] => match rest.keys().next() {
+                Some(key) => Err(Invalid::new(at, format!("unexpected field `{key}`"))),
+                None => Ok(()),
+            },
+            [IrFlattened::Enum(ty)] if ty.optional && rest.is_empty() => Ok(()),
+            [IrFlattened::Enum(ty)] => self.key(&ty.id, &Value::Object(rest), at),
+            // there is no telling which keys belong to which, and maps take anything
+            _ => Ok(()),
+        }
+    }
+
+    fn tuple(&self, fields: &[IrUnnamedField], value: &Value, at: &str) -> Result<(), Invalid> {
+        let Value::Array(values) = value else {
+            return Err(Invalid::new(at, format!("expected an array, found {}", kind(value))));
+        };
+        if values.len() != fields.len() {
+            return Err(Invalid::new(at, format!("expected {} items, found {}", fields.len(), values.len())));
+        }
+
+        fields.iter().zip(values).enumerate()
+            .try_for_each(|(n, (field, value))| self.field(&field.ty, field.literals.any, field.int_style, value, &format!("{at}[{n}]")))
+    }
+
+    /// Checks a field's value, which could be anything when every target writes its type as a literal
+    /// Wide integers given the `string` style are written as text by some helper, since serde never does that on its own
+    fn field(&self, ty: &IrType, literal: Option<&str>, int_style: Option<IntStyle>, value: &Value, at: &str) -> Result<(), Invalid> {
+        match (literal, int_style) {
+            (Some(_), _) => Ok(()),
+            (None, Some(IntStyle::String)) if ty.id.is_wide_int() => match value {
+                Value::Null if ty.optional => Ok(()),
+                Value::Array(values) if ty.array => values.iter().enumerate()
+                    .try_for_each(|(n, value)| expect(value.is_string(), value, "a string", &format!("{at}[{n}]"))),
+                _ if ty.array => Err(Invalid::new(at, format!("expected an array, found {}", kind(value)))),
+                _ => expect(value.is_string(), value, "a string", at),
+            },
+            (None, _) => self.ty(ty, value, at),
+        }
+    }
+
+    fn ty(&self, ty: &IrType, value: &Value, at: &str) -> Result<(), Invalid> {
+        if ty.optional && value.is_null() {
+            return Ok(());
+        }
+
+        if ty.array {
+            let Value::Array(values) = value else {
+                return Err(Invalid::new(at, format!("expected an array, found {}", kind(value))));
+            };
+            values.iter().enumerate().try_for_each(|(n, value)| self.key(&ty.id, value, &format!("{at}[{n}]")))
+        }
+        else {
+            self.key(&ty.id, value, at)
+        }
+    }
+
+    fn key(&self, id: &TypeKey, value: &Value, at: &str) -> Result<(), Invalid> {
+        if let Some(item) = self.registry.get(id) {
+            return self.item(item, value, at);
+        }
+
+        match self.builtins.get(id) {
+            Some(Builtin::String) => expect(value.is_string(), value, "a string", at),
+            Some(Builtin::Bool) => expect(value.is_boolean(), value, "a boolean", at),
+            Some(Builtin::Integer) => expect(value.is_i64() || value.is_u64(), value, "an integer", at),
+            Some(Builtin::Float) => expect(value.is_number(), value, "a number", at),
+            None => Ok(()),
+        }
+    }
+}
+
+fn variant<'i>(ir: &'i IrEnum, name: &str, at: &str) -> Result<&'i IrEnumVariant, Invalid> {
+    ir.variants.iter()
+        .find(|var| unraw(&var.name()) == name)
+        // only there when reading, since serde writes the catch-all under its own name
+        .or_else(|| ir.variants.iter().find(|var| var.is_other()))
+        .ok_or_else(|| Invalid::new(at, format!("`{name}` is not a variant of `{}`", ir.name())))
+}
+
+fn object<'v>(value: &'v Value, at: &str) -> Result<&'v Map<String, Value>, Invalid> {
+    value.as_object().ok_or_else(|| Invalid::new(at, format!("expected an object, found {}", kind(value))))
+}
+
+fn tag_value<'v>(map: &'v Map<String, Value>, tag: &str, at: &str) -> Result<&'v str, Invalid> {
+    match map.get(tag) {
+        Some(Value::String(name)) => Ok(name),
+        Some(value) => Err(Invalid::new(format!("{at}.{tag}"), format!("expected a string, found {}", kind(value)))),
+        None => Err(Invalid::new(at, format!("missing tag `{tag}`"))),
+    }
+}
+
+fn expect(ok: bool, value: &Value, expected: &str, at: &str) -> Result<(), Invalid> {
+    if ok {
+        Ok(())
+    }
+    else {
+        Err(Invalid::new(at, format!("expected {expected}, found {}", kind(value))))
+    }
+}
+
+fn kind(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => format!("\"{s}\""),
+        Value::Array(_) => "an array".to_string(),
+        Value::Object(_) => "an object".to_string(),
+    }
+}
+
+fn unraw(name: &str) -> String {
+    name.strip_prefix("r#").unwrap_or(name).to_string()
+}
@@ -0,0 +1,600 @@
+//! Checks serde's output for each representation burrtype understands against the IR exported for it
+
+use burrtype::prelude::*;
+use burrtype::ir::{Direction, IrExt};
+use burrtype_conformance::{samples, Harness, Sample, Validator};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Burr, Serialize, Deserialize)]
+pub struct Inner {
+    x: u32,
+    label: String,
+}
+
+impl Sample for Inner {
+    fn samples() -> Vec<Self> {
+        vec![inner()]
+    }
+}
+samples!(Inner);
+
+fn inner() -> Inner {
+    Inner { x: 1, label: "one".to_string() }
+}
+
+#[derive(Burr, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Renamed {
+    first_field: u32,
+    #[serde(rename = "second")]
+    second_field: Option<String>,
+    r#type: bool,
+    #[serde(skip)]
+    #[allow(dead_code)]
+    skipped: u8,
+    list: Vec<Inner>,
+}
+
+impl Sample for Renamed {
+    fn samples() -> Vec<Self> {
+        vec![
+            Renamed { first_field: 1, second_field: Some("two".to_string()), r#type: true, skipped: 0, list: vec![inner()] },
+            Renamed { first_field: 1, second_field: None, r#type: false, skipped: 0, list: Vec::new() },
+        ]
+    }
+}
+samples!(Renamed);
+
+#[derive(Burr, Serialize, Deserialize)]
+pub struct Flattened {
+    id: u64,
+    #[serde(flatten)]
+    inner: Inner,
+}
+
+impl Sample for Flattened {
+    fn samples() -> Vec<Self> {
+        vec![Flattened { id: 7, inner: inner() }]
+    }
+}
+samples!(Flattened);
+
+#[derive(Burr, Serialize, Deserialize)]
+pub struct FlattenedEnum {
+    id: u64,
+    #[serde(flatten)]
+    kind: Internal,
+    #[serde(flatten)]
+    inner: Option<Inner>,
+}
+
+impl Sample for FlattenedEnum {
+    fn samples() -> Vec<Self> {
+        vec![
+            FlattenedEnum { id: 7, kind: Internal::Unit, inner: None },
+            FlattenedEnum { id: 7, kind: Internal::Struct { a: 1, b: "one".to_string() }, inner: Some(inner()) },
+        ]
+    }
+}
+samples!(FlattenedEnum);
+
+#[derive(Burr, Serialize, Deserialize)]
+pub struct FlattenedMap {
+    name: String,
+    #[serde(flatten)]
+    counts: HashMap<String, u32>,
+}
+
+impl Sample for FlattenedMap {
+    fn samples() -> Vec<Self> {
+        vec![
+            FlattenedMap { name: "one".to_string(), counts: HashMap::new() },
+            FlattenedMap { name: "one".to_string(), counts: HashMap::from([("a".to_string(), 1)]) },
+        ]
+    }
+}
+samples!(FlattenedMap);
+
+#[derive(Burr, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Defaults {
+    count: u32,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+}
+
+impl Sample for Defaults {
+    fn samples() -> Vec<Self> {
+        vec![
+            Defaults::default(),
+            Defaults { count: 1, tags: vec!["tag".to_string()], note: Some("note".to_string()) },
+        ]
+    }
+}
+samples!(Defaults);
+
+#[derive(Burr, Serialize, Deserialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct OneSided {
+    user_name: String,
+    #[serde(skip_serializing)]
+    #[allow(dead_code)]
+    password: String,
+    #[serde(skip_deserializing)]
+    token: Option<String>,
+}
+
+impl Sample for OneSided {
+    fn samples() -> Vec<Self> {
+        vec![OneSided { user_name: "me".to_string(), password: "secret".to_string(), token: Some("token".to_string()) }]
+    }
+}
+samples!(OneSided);
+
+#[derive(Burr, Serialize, Deserialize)]
+pub struct Pair(u32, String);
+
+impl Sample for Pair {
+    fn samples() -> Vec<Self> {
+        vec![Pair(1, "one".to_string())]
+    }
+}
+samples!(Pair);
+
+#[derive(Burr, Serialize, Deserialize)]
+pub struct Wrapper(Inner);
+
+impl Sample for Wrapper {
+    fn samples() -> Vec<Self> {
+        vec![Wrapper(inner())]
+    }
+}
+samples!(Wrapper);
+
+#[derive(Burr, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Id(u64);
+
+impl Sample for Id {
+    fn samples() -> Vec<Self> {
+        vec![Id(7)]
+    }
+}
+samples!(Id);
+
+#[derive(Burr, Serialize, Deserialize, Clone)]
+#[serde(into = "String", try_from = "String")]
+pub struct Semver {
+    major: u32,
+    minor: u32,
+}
+
+impl From<Semver> for String {
+    fn from(value: Semver) -> Self {
+        format!("{}.{}", value.major, value.minor)
+    }
+}
+
+impl TryFrom<String> for Semver {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let parse = |part: &str| part.parse().map_err(|_| format!("`{value}` is not a version"));
+        let (major, minor) = value.split_once('.').ok_or_else(|| format!("`{value}` is not a version"))?;
+        Ok(Semver { major: parse(major)?, minor: parse(minor)? })
+    }
+}
+
+impl Sample for Semver {
+    fn samples() -> Vec<Self> {
+        vec![Semver { major: 1, minor: 2 }]
+    }
+}
+samples!(Semver);
+
+#[derive(Burr, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub struct Tagged {
+    r#type: String,
+}
+
+impl Sample for Tagged {
+    fn samples() -> Vec<Self> {
+        vec![Tagged { r#type: "circle".to_string() }]
+    }
+}
+samples!(Tagged);
+
+#[derive(Burr, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum External {
+    UnitVariant,
+    Newtype(Inner),
+    Tuple(u32, String),
+    #[serde(rename = "strukt")]
+    Struct { a: u32, b_field: String },
+}
+
+impl Sample for External {
+    fn samples() -> Vec<Self> {
+        vec![
+            External::UnitVariant,
+            External::Newtype(inner()),
+            External::Tuple(1, "one".to_string()),
+            External::Struct { a: 1, b_field: "one".to_string() },
+        ]
+    }
+}
+samples!(External);
+
+#[derive(Burr, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all_fields = "camelCase")]
+pub enum Status {
+    Active {
+        since_day: u32,
+        #[serde(alias = "by")]
+        set_by: String,
+    },
+    #[serde(alias = "off")]
+    Inactive,
+    #[serde(other)]
+    Unknown,
+}
+
+impl Sample for Status {
+    fn samples() -> Vec<Self> {
+        vec![
+            Status::Active { since_day: 1, set_by: "me".to_string() },
+            Status::Inactive,
+            Status::Unknown,
+        ]
+    }
+}
+samples!(Status);
+
+#[derive(Burr, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Untagged {
+    // structs can also be read from arrays, so this has to be tried before `Newtype`
+    Tuple(u32, String),
+    Newtype(Inner),
+    Struct { a: u32, b: String },
+    Unit,
+}
+
+impl Sample for Untagged {
+    fn samples() -> Vec<Self> {
+        vec![
+            Untagged::Newtype(inner()),
+            Untagged::Tuple(1, "one".to_string()),
+            Untagged::Struct { a: 1, b: "one".to_string() },
+            Untagged::Unit,
+        ]
+    }
+}
+samples!(Untagged);
+
+#[derive(Burr, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Internal {
+    Unit,
+    Newtype(Inner),
+    Struct { a: u32, b: String },
+}
+
+impl Sample for Internal {
+    fn samples() -> Vec<Self> {
+        vec![
+            Internal::Unit,
+            Internal::Newtype(inner()),
+            Internal::Struct { a: 1, b: "one".to_string() },
+        ]
+    }
+}
+samples!(Internal);
+
+#[derive(Burr, Serialize, Deserialize)]
+pub enum Nested {
+    Item {
+        id: u64,
+        #[serde(flatten)]
+        kind: Internal,
+    },
+}
+
+impl Sample for Nested {
+    fn samples() -> Vec<Self> {
+        vec![Nested::Item { id: 7, kind: Internal::Newtype(inner()) }]
+    }
+}
+samples!(Nested);
+
+/// Writes times as whole seconds, the same as `chrono::serde::ts_seconds`
+mod ts_seconds {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    pub fn serialize<S: Serializer>(time: &SystemTime, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(time.duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs() as i64))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<SystemTime, D::Error> {
+        Ok(UNIX_EPOCH + Duration::from_secs(i64::deserialize(deserializer)? as u64))
+    }
+}
+
+/// Reads values from their text, the same as `serde_with::rust::display_fromstr`
+mod display_fromstr {
+    use serde::{de, Deserialize, Deserializer};
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: FromStr<Err: Display>>(deserializer: D) -> Result<T, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(de::Error::custom)
+    }
+}
+
+/// Writes integers as text through a helper burrtype does not know, leaving `int_style` to say so
+mod as_text {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(de::Error::custom)
+    }
+}
+
+/// A module of our own sharing its name with `time::serde::timestamp`, which writes text rather than a number
+mod text {
+    pub mod timestamp {
+        use serde::{de, Deserialize, Deserializer, Serializer};
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+        pub fn serialize<S: Serializer>(time: &SystemTime, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(&time.duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs()))
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<SystemTime, D::Error> {
+            let secs = String::deserialize(deserializer)?.parse().map_err(de::Error::custom)?;
+            Ok(UNIX_EPOCH + Duration::from_secs(secs))
+        }
+    }
+}
+
+#[derive(Burr, Serialize, Deserialize)]
+pub struct Helpers {
+    #[serde(with = "ts_seconds")]
+    at: SystemTime,
+    #[serde(deserialize_with = "display_fromstr::deserialize")]
+    count: u32,
+    #[burr(type = "Record<string, number>")]
+    scores: HashMap<String, u32>,
+    #[burr(ts = "Lowercase<string>")]
+    label: String,
+    #[burr(int_style = "string")]
+    #[serde(with = "as_text")]
+    total: u64,
+    #[serde(with = "text::timestamp")]
+    #[burr(with_type = String)]
+    seen: SystemTime,
+}
+
+impl Sample for Helpers {
+    fn samples() -> Vec<Self> {
+        vec![Helpers { at: UNIX_EPOCH + Duration::from_secs(60), count: 3, scores: HashMap::from([("one".to_string(), 1)]), label: "one".to_string(), total: u64::MAX, seen: UNIX_EPOCH }]
+    }
+}
+samples!(Helpers);
+
+#[derive(Burr, Serialize, Deserialize)]
+pub struct Stamp(#[serde(with = "ts_seconds")] SystemTime);
+
+impl Sample for Stamp {
+    fn samples() -> Vec<Self> {
+        vec![Stamp(SystemTime::UNIX_EPOCH)]
+    }
+}
+samples!(Stamp);
+
+#[derive(Burr, Serialize, Deserialize)]
+pub struct Peer {
+    addr: SocketAddr,
+    home: PathBuf,
+}
+
+impl Sample for Peer {
+    fn samples() -> Vec<Self> {
+        vec![Peer { addr: SocketAddr::from(([127, 0, 0, 1], 8080)), home: PathBuf::from("/home/peer") }]
+    }
+}
+samples!(Peer);
+
+#[derive(Burr, Serialize, Deserialize)]
+#[serde(tag = "t", content = "c")]
+pub enum Adjacent {
+    Unit,
+    Newtype(Inner),
+    Tuple(u32, String),
+    Struct { a: u32, b: String },
+}
+
+impl Sample for Adjacent {
+    fn samples() -> Vec<Self> {
+        vec![
+            Adjacent::Unit,
+            Adjacent::Newtype(inner()),
+            Adjacent::Tuple(1, "one".to_string()),
+            Adjacent::Struct { a: 1, b: "one".to_string() },
+        ]
+    }
+}
+samples!(Adjacent);
+
+#[derive(Burr, serde_repr::Serialize_repr, serde_repr::Deserialize_repr)]
+#[repr(u16)]
+pub enum Code {
+    Ok = 200,
+    NotFound = 404,
+    Teapot = 418,
+}
+
+impl Sample for Code {
+    fn samples() -> Vec<Self> {
+        vec![Code::Ok, Code::NotFound, Code::Teapot]
+    }
+}
+samples!(Code);
+
+/// Discriminants past the end of an `i64`
+#[derive(Burr, serde_repr::Serialize_repr, serde_repr::Deserialize_repr)]
+#[repr(u64)]
+pub enum Mask {
+    None = 0,
+    All = u64::MAX,
+}
+
+impl Sample for Mask {
+    fn samples() -> Vec<Self> {
+        vec![Mask::None, Mask::All]
+    }
+}
+samples!(Mask);
+
+#[test]
+fn registered_types() {
+    // checked on their own by `reading_follows_serde`, since serde does not read back what they write
+    Harness::registered()
+        .skip::<ReadsWritten>()
+        .skip::<NeverRead>()
+        .assert();
+}
+
+#[test]
+fn reports_unchecked_types() {
+    let unchecked = Harness::new().with_type::<Inner>().skip::<Renamed>().unchecked();
+    assert!(unchecked.contains(&"serde::Flattened"), "{unchecked:?}");
+    assert!(!unchecked.iter().any(|path| matches!(*path, "serde::Inner" | "serde::Renamed")), "{unchecked:?}");
+}
+
+fn registry() -> HashMap<burrtype::ir::TypeKey, burrtype::ir::IrItem> {
+    burrtype::export::Burrxporter::new().type_registry
+}
+
+#[test]
+fn rejects_wrong_shapes() {
+    let registry = registry();
+    let validator = Validator::new(&registry);
+    let reject = |item: burrtype::ir::IrItem, value: serde_json::Value| {
+        assert!(validator.validate_item(&item, &value).is_err(), "`{value}` should not match `{}`", item.name());
+    };
+
+    reject(Renamed::get_ir(), json!({ "first_field": 1, "type": true, "list": [] }));
+    reject(Renamed::get_ir(), json!({ "firstField": 1, "type": true, "list": [], "skipped": 0 }));
+    reject(Renamed::get_ir(), json!({ "firstField": "1", "type": true, "list": [] }));
+    reject(Renamed::get_ir(), json!({ "firstField": 1, "type": true, "list": [] }));
+    reject(Flattened::get_ir(), json!({ "id": 7, "inner": { "x": 1, "label": "one" } }));
+    reject(FlattenedEnum::get_ir(), json!({ "id": 7 }));
+    reject(FlattenedEnum::get_ir(), json!({ "id": 7, "type": "unit", "a": 1 }));
+    reject(FlattenedMap::get_ir(), json!({ "counts": {} }));
+    reject(Nested::get_ir(), json!({ "Item": { "id": 7, "type": "struct", "a": 1 } }));
+    // defaults only help when reading, serde always writes the field
+    reject(Defaults::get_ir(), json!({ "tags": [] }));
+    reject(External::get_ir(), json!({ "UNIT_VARIANT": null }));
+    reject(External::get_ir(), json!({ "Struct": { "a": 1, "b_field": "one" } }));
+    reject(Untagged::get_ir(), json!("Unit"));
+    reject(Internal::get_ir(), json!({ "type": "Unit", "x": 1 }));
+    reject(Adjacent::get_ir(), json!({ "t": "Unit", "c": null }));
+    reject(Code::get_ir(), json!(201));
+    // `u64::MAX` used to wrap around to this
+    reject(Mask::get_ir(), json!(-1));
+    reject(Id::get_ir(), json!("7"));
+    reject(Semver::get_ir(), json!({ "major": 1, "minor": 2 }));
+    reject(OneSided::get_ir(), json!({ "user_name": "me", "password": "secret" }));
+    reject(Tagged::get_ir(), json!({ "type": "circle" }));
+    reject(Tagged::get_ir(), json!({ "kind": "Shape", "type": "circle" }));
+    reject(Helpers::get_ir(), json!({ "at": { "secs_since_epoch": 60, "nanos_since_epoch": 0 }, "count": 3, "scores": {}, "label": "one", "total": "1", "seen": "0" }));
+    reject(Helpers::get_ir(), json!({ "at": 60, "count": "3", "scores": {}, "label": "one", "total": "1", "seen": "0" }));
+    // a literal for one target says nothing about the JSON, so the field's own type is still checked
+    reject(Helpers::get_ir(), json!({ "at": 60, "count": 3, "scores": {}, "label": 1, "total": "1", "seen": "0" }));
+    // `int_style = "string"` says the helper writes text
+    reject(Helpers::get_ir(), json!({ "at": 60, "count": 3, "scores": {}, "label": "one", "total": 1, "seen": "0" }));
+    // only a full path finds `time::serde::timestamp`, so the type given for our own module is used
+    reject(Helpers::get_ir(), json!({ "at": 60, "count": 3, "scores": {}, "label": "one", "total": "1", "seen": 0 }));
+    reject(Stamp::get_ir(), json!("1970-01-01"));
+    reject(Peer::get_ir(), json!({ "addr": [127, 0, 0, 1], "home": "/home/peer" }));
+
+    // the other way around, serde reads what it would never write
+    let reader = Validator::new(&registry).with_direction(Direction::Deserialize);
+    assert!(reader.validate_item(&OneSided::get_ir(), &json!({ "user_name": "me", "password": "secret" })).is_ok());
+    assert!(reader.validate_item(&OneSided::get_ir(), &json!({ "userName": "me", "token": null })).is_err());
+    assert!(reader.validate_item(&Defaults::get_ir(), &json!({})).is_ok());
+    assert!(reader.validate_item(&Status::get_ir(), &json!({ "type": "Paused" })).is_ok());
+    assert!(reader.validate_item(&Status::get_ir(), &json!({ "type": "off" })).is_ok());
+    assert!(reader.validate_item(&Status::get_ir(), &json!({ "type": "Active", "sinceDay": 1, "by": "me" })).is_ok());
+    assert!(reader.validate_item(&Tagged::get_ir(), &json!({ "type": "circle" })).is_ok());
+    assert!(reader.validate_item(&Helpers::get_ir(), &json!({ "at": 60, "count": "3", "scores": {}, "label": "one", "total": "1", "seen": "0" })).is_ok());
+    assert!(reader.validate_item(&Helpers::get_ir(), &json!({ "at": 60, "count": 3, "scores": {}, "label": "one", "total": "1", "seen": "0" })).is_err());
+    reject(Status::get_ir(), json!({ "type": "Paused" }));
+    reject(Status::get_ir(), json!({ "type": "Active", "since_day": 1, "setBy": "me" }));
+}
+
+/// Read by hand from the name serde writes, rather than the one its IR says it reads
+#[derive(Burr, Serialize)]
+pub struct ReadsWritten {
+    #[serde(rename(deserialize = "total"))]
+    count: u32,
+}
+
+impl<'de> Deserialize<'de> for ReadsWritten {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Written {
+            count: u32,
+        }
+        Written::deserialize(deserializer).map(|written| ReadsWritten { count: written.count })
+    }
+}
+
+/// Never read back, even though its IR reads what it writes
+#[derive(Burr, Serialize)]
+pub struct NeverRead {
+    count: u32,
+}
+
+impl<'de> Deserialize<'de> for NeverRead {
+    fn deserialize<D: serde::Deserializer<'de>>(_: D) -> Result<Self, D::Error> {
+        Err(serde::de::Error::custom("never read"))
+    }
+}
+
+#[test]
+fn reading_follows_serde() {
+    let mismatches = Harness::new()
+        .with_samples(|| vec![ReadsWritten { count: 1 }])
+        .with_samples(|| vec![NeverRead { count: 1 }])
+        .run();
+
+    assert_eq!(mismatches.len(), 2, "{mismatches:?}");
+    assert!(mismatches[0].invalid.message.starts_with("read by serde, but rejected when reading"), "{}", mismatches[0]);
+    assert!(mismatches[1].invalid.message.starts_with("accepted when reading, but serde failed to deserialize"), "{}", mismatches[1]);
+}
+
+#[test]
+fn reports_where_values_go_wrong() {
+    let registry = registry();
+    let validator = Validator::new(&registry);
+
+    let invalid = validator.validate_item(&Renamed::get_ir(), &json!({ "firstField": 1, "second": null, "type": true, "list": [{ "x": 1, "label": 2 }] }))
+        .unwrap_err();
+    assert_eq!(invalid.at, "$.list[0].label");
+}
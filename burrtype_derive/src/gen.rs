@@ -1,12 +1,11 @@
-mod parse;
 mod attrs;
-mod meta;
-
-pub use meta::BurrMeta;
 
+use burrtype_internal::ir::{Direction, EnumRepr};
+use burrtype_internal::parse;
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
-use syn::{Attribute, DataEnum, Fields, FieldsNamed, FieldsUnnamed, Variant};
+use syn::{Attribute, DataEnum, Fields, FieldsNamed, FieldsUnnamed, Item, ItemConst, ItemMod, ItemType, Token, Variant};
+use syn::punctuated::Punctuated;
 
 #[cfg(feature = "auto_register")]
 pub fn auto_registration_fn(name: Ident) -> TokenStream {
@@ -28,15 +27,290 @@ pub fn auto_registration_fn(_name: Ident) -> TokenStream {
     quote!()
 }
 
+/// Describes a constant, with its value serialized when the IR is read
+/// `attrs` are searched for `#[burr(...)]`
+pub fn const_ir(attrs: &[Attribute], item: &ItemConst) -> syn::Result<TokenStream> {
+    let name = &item.ident;
+    let ir_docs = attrs::docs(&item.attrs);
+    let module = attrs::burr_mod(attrs);
+    let (ty, array) = parse::const_type(&item.ty)?;
+
+    Ok(quote! {
+        burrtype::ir::IrConst {
+            ident: burrtype::syn::parse_quote!(#name),
+            id: burrtype::ir::TypeKey::Path(concat!(module_path!(), "::", stringify!(#name))),
+            path: concat!(module_path!(), "::", stringify!(#name)),
+            ty: burrtype::ir::IrType {
+                id: std::any::TypeId::of::<#ty>().into(),
+                path: burrtype::syn::parse_quote!(#ty),
+                optional: false,
+                array: #array,
+            },
+            value: burrtype::serde_json::to_string(&#name).expect(concat!("failed to serialize `", stringify!(#name), "`")),
+            r#mod: #module,
+            #ir_docs
+        }.into()
+    })
+}
+
+/// Describes a type alias under its own name
+/// Fields written with the alias get the aliased type's `TypeId`, so exporters match them up by the path they were written with
+pub fn alias_ir(attrs: &[Attribute], item: &ItemType) -> syn::Result<TokenStream> {
+    let name = &item.ident;
+    let ir_docs = attrs::docs(&item.attrs);
+    let module = attrs::burr_mod(attrs);
+    let (ty, optional, array) = parse::alias_type(item)?;
+
+    Ok(quote! {
+        burrtype::ir::IrAlias {
+            ident: burrtype::syn::parse_quote!(#name),
+            id: burrtype::ir::TypeKey::Path(concat!(module_path!(), "::", stringify!(#name))),
+            path: concat!(module_path!(), "::", stringify!(#name)),
+            ty: burrtype::ir::IrType {
+                id: std::any::TypeId::of::<#ty>().into(),
+                path: burrtype::syn::parse_quote!(#ty),
+                optional: #optional,
+                array: #array,
+            },
+            de_ty: None,
+            target: std::any::TypeId::of::<#name>().into(),
+            r#mod: #module,
+            #ir_docs
+        }.into()
+    })
+}
+
+/// Describes a type serde writes as another, such as one with `#[serde(transparent)]` or `#[serde(into = "T")]`
+/// These are exported as an alias of that type, since that is all that shows up in the JSON
+pub fn proxy_ir(attrs: Vec<Attribute>, name: Ident, proxy: parse::Proxy) -> syn::Result<TokenStream> {
+    let ir_docs = attrs::docs(&attrs);
+    let module = attrs::burr_mod(&attrs);
+    let ident = attrs::serde_rename(&attrs, &name);
+    let ty = proxy_type(&proxy.serialize)?;
+    let de_ty = match &proxy.deserialize {
+        Some(ty) => {
+            let ty = proxy_type(ty)?;
+            quote!(Some(#ty))
+        }
+        None => quote!(None),
+    };
+
+    Ok(quote! {
+        impl burrtype::ir::IrExt for #name {
+            fn get_ir() -> burrtype::ir::IrItem {
+                burrtype::ir::IrAlias {
+                    ident: burrtype::syn::parse_quote!(#ident),
+                    id: std::any::TypeId::of::<#name>().into(),
+                    path: concat!(module_path!(), "::", stringify!(#name)),
+                    ty: #ty,
+                    de_ty: #de_ty,
+                    target: std::any::TypeId::of::<#name>().into(),
+                    r#mod: #module,
+                    #ir_docs
+                }.into()
+            }
+        }
+    })
+}
+
+fn proxy_type(ty: &syn::Type) -> syn::Result<TokenStream> {
+    let (ty, optional, array) = parse::proxy_type(ty)?;
+    Ok(quote! {
+        burrtype::ir::IrType {
+            id: std::any::TypeId::of::<#ty>().into(),
+            path: burrtype::syn::parse_quote!(#ty),
+            optional: #optional,
+            array: #array,
+        }
+    })
+}
+
+/// Adds an item to the `TYPES` slice
+/// Constants and aliases have no type of their own to hang an `IrExt` impl on, so this is how they are found
+#[cfg(feature = "auto_register")]
+pub fn registration_fn(name: &Ident, kind: &str, ir: TokenStream) -> TokenStream {
+    use inflector::Inflector;
+
+    let fn_name = quote::format_ident!("burr_add_{}_{}_registration", name.to_string().to_snake_case(), kind);
+    quote! {
+        #[burrtype::linkme::distributed_slice(burrtype::TYPES)]
+        #[linkme(crate = burrtype::linkme)]
+        #[doc(hidden)]
+        fn #fn_name() -> burrtype::ir::IrItem {
+            #ir
+        }
+    }
+}
+
+#[cfg(not(feature = "auto_register"))]
+pub fn registration_fn(_name: &Ident, _kind: &str, _ir: TokenStream) -> TokenStream {
+    quote!()
+}
+
+/// Adds a type implementing `ModExt` to an inline module, describing every exported item within
+/// Nested inline modules are described the same way and become child modules, unless they contain nothing to export
+/// Returns the name of the added type, if one was added
+pub fn mod_ir(args: parse::BurrModArgs, item: &mut ItemMod) -> syn::Result<Option<Ident>> {
+    use inflector::Inflector;
+
+    let Some((_, content)) = &mut item.content else {
+        return Err(syn::Error::new_spanned(&item.ident, "#[burrmod] only supports inline modules"));
+    };
+
+    let mut items = Vec::new();
+    let mut mods = Vec::new();
+    for inner in content.iter_mut() {
+        match inner {
+            Item::Struct(inner) if derives_burr(&inner.attrs) => {
+                let name = &inner.ident;
+                items.push(quote!(<#name as burrtype::ir::IrExt>::get_ir()));
+            }
+            Item::Enum(inner) if derives_burr(&inner.attrs) => {
+                let name = &inner.ident;
+                items.push(quote!(<#name as burrtype::ir::IrExt>::get_ir()));
+            }
+            Item::Const(inner) if has_attr(&inner.attrs, "burr") => items.push(const_ir(&inner.attrs, inner)?),
+            Item::Type(inner) if has_attr(&inner.attrs, "burr") => items.push(alias_ir(&inner.attrs, inner)?),
+            Item::Mod(inner) if inner.content.is_some() => {
+                // nested modules are handled here rather than by their own attribute, so they are only described once
+                let args = match inner.attrs.iter().position(|attr| attr_is(attr, "burrmod")) {
+                    Some(pos) => parse::burrmod_args(&inner.attrs.remove(pos))?,
+                    None => parse::BurrModArgs::default(),
+                };
+                if let Some(ir_name) = mod_ir(args, inner)? {
+                    let name = &inner.ident;
+                    mods.push(quote!(<#name::#ir_name as burrtype::ir::ModExt>::get_ir()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if items.is_empty() && mods.is_empty() {
+        return Ok(None);
+    }
+
+    let rust_name = syn::ext::IdentExt::unraw(&item.ident).to_string();
+    let ir_name = quote::format_ident!("{}Mod", rust_name.to_pascal_case());
+    let name = args.name.unwrap_or(rust_name);
+    let flatten = args.flatten;
+
+    content.push(syn::parse_quote! {
+        /// Describes this module's exported items, see `BurrMod::from_mod`
+        #[allow(dead_code)]
+        pub struct #ir_name;
+    });
+    content.push(syn::parse_quote! {
+        impl burrtype::ir::ModExt for #ir_name {
+            fn get_ir() -> burrtype::ir::IrMod {
+                burrtype::ir::IrMod {
+                    name: #name,
+                    ir_name: burrtype::syn::parse_quote!(#ir_name),
+                    flatten: #flatten,
+                    inline: true,
+                    items: vec![#(#items),*],
+                    mods: vec![#(#mods),*],
+                }
+            }
+        }
+    });
+
+    Ok(Some(ir_name))
+}
+
+/// Substitutes in what a flattened struct, enum or map adds to the fields around it
+/// A default for the flattened field covers everything inside it
+fn flatten_fields(ty: &syn::Type, default: bool) -> TokenStream {
+    let (ty, optional) = parse::option(ty.clone());
+    let flatten = if parse::map(&ty) {
+        quote!(burrtype::ir::IrFlatten { fields: Vec::new(), flattened: vec![burrtype::ir::IrFlattened::Map] })
+    }
+    else {
+        quote!(<#ty as burrtype::ir::FlattenExt>::flatten())
+    };
+
+    quote! {{
+        let flatten = #flatten.with_default(#default).with_optional(#optional);
+        fields.extend(flatten.fields);
+        flattened.extend(flatten.flattened);
+    }}
+}
+
+/// Declares the list flattened enums and maps are collected into, only mutable when something is flattened
+fn flattened_list(fields: &FieldsNamed) -> TokenStream {
+    let any = fields.named.iter().any(|field| parse::named_field_attrs(field).is_ok_and(|ir| ir.flatten && !ir.ignore));
+    if any {
+        quote!(let mut flattened = Vec::<burrtype::ir::IrFlattened>::new();)
+    }
+    else {
+        quote!(let flattened = Vec::<burrtype::ir::IrFlattened>::new();)
+    }
+}
+
+/// Describes a field type written with its `Option` and `Vec`
+fn ir_type(ty: &syn::Type) -> TokenStream {
+    let (ty, optional) = parse::option(ty.clone());
+    let (ty, vec) = parse::vec(ty);
+    quote! {
+        burrtype::ir::IrType {
+            id: std::any::TypeId::of::<#ty>().into(),
+            path: burrtype::syn::parse_quote!(#ty),
+            optional: #optional,
+            array: #vec,
+        }
+    }
+}
+
+/// The type a field is deserialized as, when it differs from the one it is serialized as
+fn de_type(de_ty: Option<&syn::Type>) -> TokenStream {
+    match de_ty {
+        Some(ty) => {
+            let ty = ir_type(ty);
+            quote!(Some(#ty))
+        }
+        None => quote!(None),
+    }
+}
+
+/// Checks for `Burr` in a `#[derive(...)]`
+fn derives_burr(attrs: &[Attribute]) -> bool {
+    attrs.iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| attr.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated).ok())
+        .flatten()
+        .any(|path| path.segments.last().is_some_and(|seg| seg.ident == "Burr"))
+}
+
+/// Checks for an attribute by name, however its path was written
+fn has_attr(attrs: &[Attribute], name: &str) -> bool {
+    attrs.iter().any(|attr| attr_is(attr, name))
+}
+
+/// Checks for a `#[repr(...)]` hint by name
+fn has_repr(attrs: &[Attribute], name: &str) -> bool {
+    attrs.iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+        .filter_map(|attr| attr.parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated).ok())
+        .flatten()
+        .any(|hint| hint == name)
+}
+
+fn attr_is(attr: &Attribute, name: &str) -> bool {
+    attr.path().segments.last().is_some_and(|seg| seg.ident == name)
+}
+
 pub fn named_struct_ir(
     attrs: Vec<Attribute>,
     name: Ident,
     fields: FieldsNamed,
 ) -> TokenStream {
     let ir_docs = attrs::docs(&attrs);
-    let module = attrs::container::burr_mod(&attrs);
+    let module = attrs::burr_mod(&attrs);
     let ident = attrs::serde_rename(&attrs, &name);
-    let case = attrs::serde_rename_all(&attrs);
+    let rename_all = parse::serde_rename_all(&attrs);
+    let container_default = parse::serde_default(&attrs);
+    let tag = attrs::struct_tag(&attrs, &name);
+    let flattened_list = flattened_list(&fields);
 
     // Collect fragments for each field that inserts the field's IR
     let field_impls = fields.named.iter()
@@ -44,32 +318,46 @@ pub fn named_struct_ir(
             let name = field.ident.clone().unwrap();
             match parse::named_field_attrs(field) {
                 Ok(ir) => {
-                    let ty = ir.ty.as_ref().map_or_else(|| &field.ty, |d| d);
-                    let st = if ir.ignore {
+                    let ty = ir.ty.as_ref().unwrap_or(&ir.field.ty);
+                    if ir.ignore {
                         quote!()
                     } else if ir.flatten {
-                        quote! {fields.extend(<#ty as burrtype::ir::NamedStructExt>::fields());}
+                        flatten_fields(ty, container_default || ir.default)
                     } else {
                         let (ty, optional) = parse::option(ty.clone());
                         let (ty, vec) = parse::vec(ty);
                         let field_docs = attrs::docs(&field.attrs);
-                        let name = Ident::new_raw(&case.transform(&name.to_string()), name.span());
-                        let ident = attrs::serde_rename(&field.attrs, &name);
+                        let (ident, de_ident) = rename_all.names(&field.attrs, &name);
+                        let de_ident = attrs::de_ident(de_ident);
+                        let aliases = attrs::aliases(&field.attrs);
+                        let skip = attrs::skip(ir.skip);
+                        let default = container_default || ir.default;
+                        let skip_serializing_if = ir.skip_serializing_if;
+                        let de_ty = de_type(ir.de_ty.as_ref());
+                        let literals = attrs::literals(ir.literals);
+                        let int_style = attrs::int_style(ir.int_style);
 
                         quote! {
                             fields.push(burrtype::ir::IrNamedField {
                                 ident: burrtype::syn::parse_quote!(#ident),
+                                de_ident: #de_ident,
+                                aliases: #aliases,
                                 ty: burrtype::ir::IrType {
-                                    id: std::any::TypeId::of::<#ty>(),
+                                    id: std::any::TypeId::of::<#ty>().into(),
                                     path: burrtype::syn::parse_quote!(#ty),
                                     optional: #optional,
                                     array: #vec,
                                 },
+                                de_ty: #de_ty,
+                                literals: #literals,
+                                int_style: #int_style,
+                                skip: #skip,
+                                default: #default,
+                                skip_serializing_if: #skip_serializing_if,
                                 #field_docs
                             });
                         }
-                    };
-                    st
+                    }
                 }
                 Err(err) => panic!("{}", err),
             }
@@ -78,14 +366,15 @@ pub fn named_struct_ir(
 
     let field_map_frag = quote! {
         let mut fields = Vec::<burrtype::ir::IrNamedField>::new();
+        #flattened_list
         #( #field_impls )*
-        fields
     };
 
     let impl_ext_frag = quote! {
-            impl burrtype::ir::NamedStructExt for #name {
-                fn fields() -> Vec<burrtype::ir::IrNamedField> {
+            impl burrtype::ir::FlattenExt for #name {
+                fn flatten() -> burrtype::ir::IrFlatten {
                     #field_map_frag
+                    burrtype::ir::IrFlatten { fields, flattened }
                 }
             }
         };
@@ -95,13 +384,15 @@ pub fn named_struct_ir(
 
         impl burrtype::ir::IrExt for #name {
             fn get_ir() -> burrtype::ir::IrItem {
-                let mut fields = Vec::<burrtype::ir::IrNamedField>::new();
-                #( #field_impls )*
+                #field_map_frag
 
                 burrtype::ir::IrNamedStruct {
                     ident: burrtype::syn::parse_quote!(#ident),
-                    id: std::any::TypeId::of::<#name>(),
+                    id: std::any::TypeId::of::<#name>().into(),
+                    path: concat!(module_path!(), "::", stringify!(#name)),
                     fields,
+                    flattened,
+                    tag: #tag,
                     r#mod: #module,
                     #ir_docs
 
@@ -117,7 +408,7 @@ pub fn tuple_struct_ir(
     fields: FieldsUnnamed
 ) -> TokenStream {
     let ir_docs = attrs::docs(&attrs);
-    let module = attrs::container::burr_mod(&attrs);
+    let module = attrs::burr_mod(&attrs);
     let ident = attrs::serde_rename(&attrs, &name);
 
     // Collect fragments for each field that describes the field's IR
@@ -125,7 +416,7 @@ pub fn tuple_struct_ir(
         .map(|field| {
             match parse::unnamed_field_attrs(field) {
                 Ok(ir) => {
-                    let ty = ir.ty.as_ref().map_or_else(|| &field.ty, |d| d);
+                    let ty = ir.ty.as_ref().unwrap_or(&ir.field.ty);
                     let st = if ir.ignore {
                         quote!()
                     } else {
@@ -137,14 +428,19 @@ pub fn tuple_struct_ir(
                             panic!("Option types unsupported for tuple structs");
                         }
 
+                        let literals = attrs::literals(ir.literals);
+                        let int_style = attrs::int_style(ir.int_style);
+
                         quote! {
                             burrtype::ir::IrUnnamedField {
                                 ty: burrtype::ir::IrType {
-                                    id: std::any::TypeId::of::<#ty>(),
+                                    id: std::any::TypeId::of::<#ty>().into(),
                                     path: burrtype::syn::parse_quote!(#ty),
                                     optional: #optional,
                                     array: #vec,
                                 },
+                                literals: #literals,
+                                int_style: #int_style,
                                 #field_docs
                             },
                         }
@@ -161,7 +457,8 @@ pub fn tuple_struct_ir(
             fn get_ir() -> burrtype::ir::IrItem {
                 burrtype::ir::IrTupleStruct {
                     ident: burrtype::syn::parse_quote!(#ident),
-                    id: std::any::TypeId::of::<#name>(),
+                    id: std::any::TypeId::of::<#name>().into(),
+                    path: concat!(module_path!(), "::", stringify!(#name)),
                     fields: vec![#(#field_ir)*],
                     r#mod: #module,
                     #ir_docs
@@ -176,7 +473,7 @@ pub fn unit_struct_ir(
     name: Ident
 ) -> TokenStream {
     let ir_docs = attrs::docs(&attrs);
-    let module = attrs::container::burr_mod(&attrs);
+    let module = attrs::burr_mod(&attrs);
     let ident = attrs::serde_rename(&attrs, &name);
 
     quote! {
@@ -184,7 +481,8 @@ pub fn unit_struct_ir(
             fn get_ir() -> burrtype::ir::IrItem {
                 burrtype::ir::IrUnitStruct {
                     ident: burrtype::syn::parse_quote!(#ident),
-                    id: std::any::TypeId::of::<#name>(),
+                    id: std::any::TypeId::of::<#name>().into(),
+                    path: concat!(module_path!(), "::", stringify!(#name)),
                     r#mod: #module,
                     #ir_docs
                 }.into()
@@ -199,34 +497,94 @@ pub fn enum_ir(
     data: DataEnum
 ) -> TokenStream {
     let ir_docs = attrs::docs(&attrs);
-    let module = attrs::container::burr_mod(&attrs);
+    let module = attrs::burr_mod(&attrs);
     let ident = attrs::serde_rename(&attrs, &name);
-    let case = attrs::serde_rename_all(&attrs);
-    let repr = attrs::container::serde_enum_repr(&attrs);
+    let rename_all = parse::serde_rename_all(&attrs);
+    let rename_all_fields = parse::serde_rename_all_fields(&attrs);
+    let repr = match parse::enum_repr(&attrs) {
+        Ok(repr) => repr,
+        Err(e) => return e.into_compile_error(),
+    };
+    let style = match parse::enum_style(&attrs) {
+        Ok(Some(style)) => quote!(Some(burrtype::ir::#style)),
+        Ok(None) => quote!(None),
+        Err(e) => return e.into_compile_error(),
+    };
+
+    // Discriminants only decide how an enum is serialized when none of its variants have fields
+    let with_fields = data.variants.iter().find(|var| !matches!(var.fields, Fields::Unit));
+    if let (EnumRepr::Int, Some(var)) = (repr, with_fields) {
+        return syn::Error::new_spanned(var, "only enums without fields can be represented as integers").into_compile_error();
+    }
+    let c_like = with_fields.is_none();
+    // Every other integer fits in an `i128`, but `u128` values past its end would wrap
+    let wide = has_repr(&attrs, "u128");
+
+    // Other derives such as `Serialize_repr` are hidden from us, so enums without fields ask serde how they serialize instead
+    let first = data.variants.iter()
+        .find(|var| !parse::serde_skip(&var.attrs) && parse::serde_skip_in(&var.attrs) != Some(Direction::Serialize))
+        .map(|var| &var.ident);
+    let repr = match first {
+        Some(first) if c_like && !matches!(repr, EnumRepr::Int) => quote! {{
+            use burrtype::probe::{FallbackProbe as _, SerializeProbe as _};
+            if (&&burrtype::probe::Probe(&#name::#first)).serializes_as_int() {
+                burrtype::ir::EnumRepr::Int
+            }
+            else {
+                burrtype::ir::#repr
+            }
+        }},
+        _ => quote!(burrtype::ir::#repr),
+    };
 
     // Collect fragments for each variant that describes the variant's IR
     let variant_frags = data.variants.into_iter().map(|var| {
         let Variant { attrs, ident, fields, .. } = var;
 
-        if attrs::variant::serde_skip(&attrs) {
+        if parse::serde_skip(&attrs) {
             return quote!()
         }
 
-        if var.discriminant.is_some() {
-            panic!("Enums with discriminants are unsupported");
-        }
-
-        let ident = Ident::new_raw(&case.transform(&ident.to_string()), ident.span());
+        let discriminant = match (c_like, wide) {
+            (true, true) => quote!(Some({
+                const _: () = assert!(#name::#ident as u128 <= i128::MAX as u128, "discriminants above `i128::MAX` cannot be exported");
+                #name::#ident as i128
+            })),
+            (true, false) => quote!(Some(#name::#ident as i128)),
+            (false, _) => quote!(None),
+        };
+        let (ident, de_ident) = rename_all.names(&attrs, &ident);
+        let names = VariantNames {
+            ident,
+            de_ident: attrs::de_ident(de_ident),
+            aliases: attrs::aliases(&attrs),
+            skip: attrs::skip(parse::serde_skip_in(&attrs)),
+        };
 
         match fields {
-            Fields::Named(inner) => enum_struct_variant_ir(attrs, ident, inner),
-            Fields::Unnamed(inner) => enum_tuple_variant_ir(attrs, ident, inner),
-            Fields::Unit => enum_unit_variant_ir(attrs, ident),
+            Fields::Named(inner) => enum_struct_variant_ir(attrs, names, rename_all_fields, inner),
+            Fields::Unnamed(inner) => enum_tuple_variant_ir(attrs, names, inner),
+            Fields::Unit => enum_unit_variant_ir(attrs, names, discriminant),
         }
     })
     .collect::<Vec<_>>();
 
     quote! {
+        // enums keep their shape when flattened, and are written alongside the fields around them
+        impl burrtype::ir::FlattenExt for #name {
+            fn flatten() -> burrtype::ir::IrFlatten {
+                burrtype::ir::IrFlatten {
+                    fields: Vec::new(),
+                    flattened: vec![burrtype::ir::IrFlattened::Enum(burrtype::ir::IrType {
+                        id: std::any::TypeId::of::<#name>().into(),
+                        path: burrtype::syn::parse_quote!(#name),
+                        optional: false,
+                        array: false,
+                    })],
+                }
+            }
+        }
+
         impl burrtype::ir::IrExt for #name {
             fn get_ir() -> burrtype::ir::IrItem {
                 let mut variants = Vec::<burrtype::ir::IrEnumVariant>::new();
@@ -234,9 +592,11 @@ pub fn enum_ir(
 
                 burrtype::ir::IrEnum {
                     ident: burrtype::syn::parse_quote!(#ident),
-                    id: std::any::TypeId::of::<#name>(),
+                    id: std::any::TypeId::of::<#name>().into(),
+                    path: concat!(module_path!(), "::", stringify!(#name)),
                     variants,
-                    repr: burrtype::ir::#repr,
+                    repr: #repr,
+                    style: #style,
                     r#mod: #module,
                     #ir_docs
                 }.into()
@@ -245,47 +605,73 @@ pub fn enum_ir(
     }
 }
 
+/// What a variant is called each way, and which way it is skipped
+struct VariantNames {
+    ident: Ident,
+    de_ident: TokenStream,
+    aliases: TokenStream,
+    skip: TokenStream,
+}
+
 fn enum_struct_variant_ir(
     attrs: Vec<Attribute>,
-    name: Ident,
+    names: VariantNames,
+    rename_all_fields: parse::RenameAll,
     fields: FieldsNamed,
 ) -> TokenStream {
     let ir_docs = attrs::docs(&attrs);
-    let ident = attrs::serde_rename(&attrs, &name);
-    let case = attrs::serde_rename_all(&attrs);
+    let VariantNames { ident, de_ident, aliases, skip } = names;
+    let rename_all = parse::serde_rename_all(&attrs).or(rename_all_fields);
+    // serde does not allow `#[serde(default)]` on enums
+    let container_default = false;
+    let flattened_list = flattened_list(&fields);
 
     // Collect fragments for each field that inserts the field's IR
     let field_impls = fields.named.iter()
         .map(|field| {
             let name = field.ident.clone().unwrap();
-            let ty = &field.ty;
             match parse::named_field_attrs(field) {
                 Ok(ir) => {
-                    let st = if ir.ignore {
+                    let ty = ir.ty.as_ref().unwrap_or(&ir.field.ty);
+                    if ir.ignore {
                         quote!()
                     } else if ir.flatten {
-                        quote! {fields.extend(<#ty as burrtype::ir::NamedStructExt>::fields());}
+                        flatten_fields(ty, container_default || ir.default)
                     } else {
                         let (ty, optional) = parse::option(ty.clone());
                         let (ty, vec) = parse::vec(ty);
                         let field_docs = attrs::docs(&field.attrs);
-                        let name = Ident::new_raw(&case.transform(&name.to_string()), name.span());
-                        let ident = attrs::serde_rename(&field.attrs, &name);
+                        let (ident, de_ident) = rename_all.names(&field.attrs, &name);
+                        let de_ident = attrs::de_ident(de_ident);
+                        let aliases = attrs::aliases(&field.attrs);
+                        let skip = attrs::skip(ir.skip);
+                        let default = container_default || ir.default;
+                        let skip_serializing_if = ir.skip_serializing_if;
+                        let de_ty = de_type(ir.de_ty.as_ref());
+                        let literals = attrs::literals(ir.literals);
+                        let int_style = attrs::int_style(ir.int_style);
 
                         quote! {
                             fields.push(burrtype::ir::IrNamedField {
                                 ident: burrtype::syn::parse_quote!(#ident),
+                                de_ident: #de_ident,
+                                aliases: #aliases,
                                 ty: burrtype::ir::IrType {
-                                    id: std::any::TypeId::of::<#ty>(),
+                                    id: std::any::TypeId::of::<#ty>().into(),
                                     path: burrtype::syn::parse_quote!(#ty),
                                     optional: #optional,
                                     array: #vec,
                                 },
+                                de_ty: #de_ty,
+                                literals: #literals,
+                                int_style: #int_style,
+                                skip: #skip,
+                                default: #default,
+                                skip_serializing_if: #skip_serializing_if,
                                 #field_docs
                             });
                         }
-                    };
-                    st
+                    }
                 }
                 Err(err) => panic!("{}", err),
             }
@@ -294,11 +680,16 @@ fn enum_struct_variant_ir(
 
     quote! {
         let mut fields = Vec::<burrtype::ir::IrNamedField>::new();
+        #flattened_list
         #( #field_impls )*
 
         variants.push(burrtype::ir::IrEnumStructVariant {
             ident: burrtype::syn::parse_quote!(#ident),
+            de_ident: #de_ident,
+            aliases: #aliases,
+            skip: #skip,
             fields,
+            flattened,
             #ir_docs
         }.into());
     }
@@ -306,16 +697,15 @@ fn enum_struct_variant_ir(
 
 fn enum_tuple_variant_ir(
     attrs: Vec<Attribute>,
-    name: Ident,
+    names: VariantNames,
     fields: FieldsUnnamed,
 ) -> TokenStream {
     // Collect fragments for each field that describes the field's IR
     let field_ir = fields.unnamed.iter()
         .map(|field| {
-            let ty = &field.ty;
-
             match parse::unnamed_field_attrs(field) {
                 Ok(ir) => {
+                    let ty = ir.ty.as_ref().unwrap_or(&ir.field.ty);
                     let st = if ir.ignore {
                         quote!()
                     } else {
@@ -327,14 +717,19 @@ fn enum_tuple_variant_ir(
                             panic!("Option types unsupported for tuple variants");
                         }
 
+                        let literals = attrs::literals(ir.literals);
+                        let int_style = attrs::int_style(ir.int_style);
+
                         quote! {
                             burrtype::ir::IrUnnamedField {
                                 ty: burrtype::ir::IrType {
-                                    id: std::any::TypeId::of::<#ty>(),
+                                    id: std::any::TypeId::of::<#ty>().into(),
                                     path: burrtype::syn::parse_quote!(#ty),
                                     optional: #optional,
                                     array: #vec,
                                 },
+                                literals: #literals,
+                                int_style: #int_style,
                                 #field_docs
                             },
                         }
@@ -347,11 +742,14 @@ fn enum_tuple_variant_ir(
         .collect::<Vec<_>>();
 
     let ir_docs = attrs::docs(&attrs);
-    let ident = attrs::serde_rename(&attrs, &name);
+    let VariantNames { ident, de_ident, aliases, skip } = names;
 
     quote! {
         variants.push(burrtype::ir::IrEnumTupleVariant {
             ident: burrtype::syn::parse_quote!(#ident),
+            de_ident: #de_ident,
+            aliases: #aliases,
+            skip: #skip,
             fields: vec![#(#field_ir)*],
             #ir_docs
         }.into());
@@ -360,14 +758,21 @@ fn enum_tuple_variant_ir(
 
 fn enum_unit_variant_ir(
     attrs: Vec<Attribute>,
-    name: Ident
+    names: VariantNames,
+    discriminant: TokenStream,
 ) -> TokenStream {
     let ir_docs = attrs::docs(&attrs);
-    let ident = attrs::serde_rename(&attrs, &name);
+    let VariantNames { ident, de_ident, aliases, skip } = names;
+    let other = parse::serde_other(&attrs);
 
     quote! {
         variants.push(burrtype::ir::IrEnumUnitVariant {
             ident: burrtype::syn::parse_quote!(#ident),
+            de_ident: #de_ident,
+            aliases: #aliases,
+            skip: #skip,
+            discriminant: #discriminant,
+            other: #other,
             #ir_docs
         }.into());
     }
@@ -1,167 +1,104 @@
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
-use syn::{Attribute, Expr, Lit, LitStr, Meta, parse_quote, Token};
-use syn::punctuated::Punctuated;
-use syn::spanned::Spanned;
-use burrtype_internal::ir::CaseConvention;
+use burrtype_internal::ir::{Direction, IntStyle, IrLiterals, IrStructTag};
+use burrtype_internal::parse;
 
-pub mod container;
-pub mod variant;
-pub mod field;
-
-pub fn docs(attrs: &[Attribute]) -> TokenStream {
-    let mut docs = Vec::new();
-    for attr in attrs {
-        if let Meta::NameValue(attr) = &attr.meta {
-            if attr.path.is_ident("doc") {
-                if let Expr::Lit(lit) = &attr.value {
-                    if let Lit::Str(ls) = &lit.lit {
-                        docs.push(ls.value().trim().to_string());
-                    }
-                }
-            }
-        }
-    }
+/// Collects doc comments into the `docs` field of an IR item
+pub fn docs(attrs: &[syn::Attribute]) -> TokenStream {
     #[cfg(feature = "docs")]
-    if docs.is_empty() {
-        quote! {
+    match parse::docs(attrs) {
+        None => quote! {
             docs: None,
-        }
-    }
-    else {
-        let docs = docs.join("\n");
-
-        quote! {
+        },
+        Some(docs) => quote! {
             docs: Some( #docs ),
-        }
+        },
     }
 
     #[cfg(not(feature = "docs"))]
-    quote!()
+    {
+        let _ = attrs;
+        quote!()
+    }
 }
 
+/// #[burr(mod = T)]
+/// Controls the export module of the output type
+pub fn burr_mod(attrs: &[syn::Attribute]) -> TokenStream {
+    match parse::burr_mod(attrs) {
+        Some(module) => quote!(Some(#module)),
+        None => quote!(None),
+    }
+}
 
 /// `serde_compat` attribute:
 /// #[serde(rename = "T")]
-/// #[serde(rename(serialize = "T", deserialize = T")] only for identical Ts
-/// Controls the name of the output type
-pub fn serde_rename(attrs: &[Attribute], default: &Ident) -> TokenStream {
-    for attr in attrs {
-        if attr.path().is_ident("serde") {
-            match attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) {
-                Ok(items) => {
-                    for meta in items {
-                        if meta.path().is_ident("rename") {
-                            match meta {
-                                Meta::NameValue(meta) => {
-                                    // pull the T from "T"
-                                    let value = &meta.value;
-                                    let name: LitStr = parse_quote!(#value);
-                                    let name: Ident = Ident::new(&name.value(), meta.span());
-                                    return quote!(#name);
-                                }
-                                Meta::List(meta) => {
-                                    let mut ser = None::<LitStr>;
-                                    let mut des = None::<LitStr>;
-                                    match meta.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) {
-                                        Ok(inner) => {
-                                            for im in inner {
-                                                match im {
-                                                    Meta::NameValue(meta) if meta.path.is_ident("serialize") => {
-                                                        let value = &meta.value;
-                                                        ser = Some(parse_quote!(#value));
-                                                    }
-                                                    Meta::NameValue(meta) if meta.path.is_ident("deserialize") => {
-                                                        let value = &meta.value;
-                                                        des = Some(parse_quote!(#value));
-                                                    }
-                                                    _ => {}
-                                                }
-                                            }
-                                        }
-                                        Err(e) => panic!("{}", e.into_compile_error())
-                                    }
-
-                                    match (ser, des) {
-                                        (Some(ser), Some(des)) if ser == des => {
-                                            let name: Ident = Ident::new(&ser.value(), meta.span());
-                                            return quote!(#name);
-                                        }
-                                        (None, None) => {}
-                                        _ => panic!("`#[serde(rename(serialize = \"T\", deserialize=\"T\")]` is currently unsupported\nconsider `#[serde(rename = \"T\")]` instead")
-                                    }
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-                }
-                Err(e) => panic!("{}", e.into_compile_error())
-            }
+/// Controls the name of the output type, falling back to `default`
+pub fn serde_rename(attrs: &[syn::Attribute], default: &Ident) -> TokenStream {
+    match parse::serde_rename(attrs) {
+        Some(name) => {
+            let name = Ident::new(&name, Span::call_site());
+            quote!(#name)
         }
+        None => quote!(#default),
+    }
+}
+
+/// The name a field or variant is deserialized with, when it differs from the serialized one
+pub fn de_ident(de_ident: Option<Ident>) -> TokenStream {
+    match de_ident {
+        Some(ident) => quote!(Some(burrtype::syn::parse_quote!(#ident))),
+        None => quote!(None),
     }
+}
 
-    quote!(#default)
+/// #[burr(type = "literal")]
+/// #[burr(ts = "literal")]
+/// #[burr(target(ts = "literal", ...))]
+/// Types written as is in place of a field's own
+pub fn literals(literals: IrLiterals) -> TokenStream {
+    let any = match literals.any {
+        Some(ty) => quote!(Some(#ty)),
+        None => quote!(None),
+    };
+    let targets = literals.targets.iter().map(|(target, ty)| quote!((#target, #ty)));
+    quote!(burrtype::ir::IrLiterals { any: #any, targets: vec![#(#targets),*] })
 }
 
-/// `serde_compat` attribute:
-/// #[serde(rename_all = "case")]
-/// #[serde(rename_all(serialize = "case", deserialize = "case"))] only if case is identical
-/// Controls the case convention for fields or variants
-pub fn serde_rename_all(attrs: &[Attribute]) -> CaseConvention {
-    let mut ser = None::<CaseConvention>;
-    let mut des = None::<CaseConvention>;
+/// #[burr(int_style = "style")]
+/// How wide integers are written, over the target's choice
+pub fn int_style(style: Option<IntStyle>) -> TokenStream {
+    match style {
+        Some(style) => quote!(Some(burrtype::ir::#style)),
+        None => quote!(None),
+    }
+}
 
-    for attr in attrs {
-        if attr.path().is_ident("serde") {
-            match attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) {
-                Ok(items) => {
-                    for meta in items {
-                        if meta.path().is_ident("rename_all") {
-                            match meta {
-                                Meta::NameValue(meta) => {
-                                    // pull the T from "T"
-                                    let value = &meta.value;
-                                    let name: LitStr = parse_quote!(#value);
-                                    return CaseConvention::parse(&name.value());
-                                }
-                                Meta::List(meta) => {
-                                    match meta.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) {
-                                        Ok(inner) => {
-                                            for im in inner {
-                                                match im {
-                                                    Meta::NameValue(meta) if meta.path.is_ident("serialize") => {
-                                                        let value = &meta.value;
-                                                        let name: LitStr = parse_quote!(#value);
-                                                        ser = Some(CaseConvention::parse(&name.value()));
-                                                    }
-                                                    Meta::NameValue(meta) if meta.path.is_ident("deserialize") => {
-                                                        let value = &meta.value;
-                                                        let name: LitStr = parse_quote!(#value);
-                                                        des = Some(CaseConvention::parse(&name.value()));
-                                                    }
-                                                    _ => {}
-                                                }
-                                            }
-                                        }
-                                        Err(e) => panic!("{}", e.into_compile_error())
-                                    }
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-                }
-                Err(e) => panic!("{}", e.into_compile_error())
-            }
-        }
+/// `serde_compat` attributes:
+/// #[serde(skip_serializing)]
+/// #[serde(skip_deserializing)]
+/// The way a field or variant is left out of, when it is only skipped one way
+pub fn skip(skip: Option<Direction>) -> TokenStream {
+    match skip {
+        Some(direction) => quote!(Some(burrtype::ir::#direction)),
+        None => quote!(None),
     }
+}
 
-    match (ser, des) {
-        (Some(ser), Some(des)) if ser == des => {
-            ser
-        }
-        (None, None) => CaseConvention::Default,
-        _ => panic!("`#[serde(rename_all(serialize = \"T\", deserialize=\"T\")]` is currently unsupported\nconsider `#[serde(rename_all = \"T\")]` instead")
+/// `serde_compat` attribute:
+/// #[serde(tag = "type")]
+/// The field holding a struct's name
+pub fn struct_tag(attrs: &[syn::Attribute], name: &Ident) -> TokenStream {
+    match parse::serde_struct_tag(attrs, name) {
+        Some(IrStructTag { field, value }) => quote!(Some(burrtype::ir::IrStructTag { field: #field, value: #value })),
+        None => quote!(None),
     }
-}
\ No newline at end of file
+}
+
+/// `serde_compat` attribute:
+/// #[serde(alias = "name")]
+/// Other names a field or variant is read with
+pub fn aliases(attrs: &[syn::Attribute]) -> TokenStream {
+    let aliases = parse::serde_aliases(attrs);
+    quote!(vec![#( burrtype::syn::parse_quote!(#aliases) ),*])
+}
@@ -1,8 +1,9 @@
 mod gen;
 
 use proc_macro::{TokenStream as ProcTokenStream};
+use burrtype_internal::parse;
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput, Data, Fields};
+use syn::{parse_macro_input, parse_quote, Attribute, DeriveInput, Data, Fields, Item, ItemMod};
 
 #[proc_macro_derive(Burr, attributes(burr))]
 pub fn burr_macro(input: ProcTokenStream) -> ProcTokenStream {
@@ -12,7 +13,13 @@ pub fn burr_macro(input: ProcTokenStream) -> ProcTokenStream {
 
     let ir_ar = gen::auto_registration_fn(ident.clone());
 
-    let ir_impl = match data {
+    let ir_impl = match parse::serde_proxy(&attrs, &data) {
+        Ok(None) => None,
+        Ok(Some(proxy)) => Some(gen::proxy_ir(attrs.clone(), ident.clone(), proxy).unwrap_or_else(syn::Error::into_compile_error)),
+        Err(err) => Some(err.into_compile_error()),
+    };
+
+    let ir_impl = ir_impl.unwrap_or_else(|| match data {
         Data::Struct(inner) => {
             match inner.fields {
                 Fields::Named(inner) => gen::named_struct_ir(attrs, ident, inner),
@@ -22,7 +29,7 @@ pub fn burr_macro(input: ProcTokenStream) -> ProcTokenStream {
         }
         Data::Enum(inner) => gen::enum_ir(attrs, ident, inner),
         Data::Union(_) => panic!("unions are unsupported"),
-    };
+    });
 
     quote!(#ir_impl #ir_ar).into()
 }
@@ -32,4 +39,64 @@ pub fn burr_macro(input: ProcTokenStream) -> ProcTokenStream {
 #[proc_macro_attribute]
 pub fn linkme(_args: ProcTokenStream, input: ProcTokenStream) -> ProcTokenStream {
     input
-}
\ No newline at end of file
+}
+/// Exports a constant or type alias alongside types
+/// Accepts the same `mod = "..."` argument as `#[burr(mod = "...")]` on types
+/// ```ignore
+/// #[burr(mod = "api")]
+/// pub const MAX_PAGE_SIZE: u32 = 100;
+///
+/// #[burr(mod = "api")]
+/// pub type UserId = u64;
+/// ```
+/// Constant values are serialized with `serde_json`, so their type must implement `Serialize`
+#[proc_macro_attribute]
+pub fn burr(args: ProcTokenStream, input: ProcTokenStream) -> ProcTokenStream {
+    let args = proc_macro2::TokenStream::from(args);
+    let item = parse_macro_input!(input as Item);
+
+    // Reuse the attribute parsing we already have for types
+    let attrs: Vec<Attribute> = if args.is_empty() { Vec::new() } else { vec![parse_quote!(#[burr(#args)])] };
+
+    let ir = match &item {
+        Item::Const(inner) => gen::const_ir(&attrs, inner).map(|ir| gen::registration_fn(&inner.ident, "const", ir)),
+        Item::Type(inner) => gen::alias_ir(&attrs, inner).map(|ir| gen::registration_fn(&inner.ident, "alias", ir)),
+        _ => Err(syn::Error::new_spanned(&item, "#[burr] can only be used on constants and type aliases, use #[derive(Burr)] for types")),
+    };
+
+    let ir = ir.unwrap_or_else(syn::Error::into_compile_error);
+    quote!(#item #ir).into()
+}
+
+/// Describes an inline module and everything exported within it
+/// This adds a type named after the module, such as `InnerMod` for `mod inner`, to build a `BurrMod` from
+/// ```ignore
+/// #[burrmod]
+/// pub mod inner {
+///     #[derive(Burr)]
+///     pub struct Foo(u64);
+///
+///     // `flatten` merges a module's contents into its parent, and `name` exports it under another name
+///     #[burrmod(flatten)]
+///     pub mod more {}
+/// }
+///
+/// exporter.with_mod(BurrMod::from_mod::<inner::InnerMod>());
+/// ```
+/// Note this will not work for file-based modules, only inline ones
+#[proc_macro_attribute]
+pub fn burrmod(args: ProcTokenStream, input: ProcTokenStream) -> ProcTokenStream {
+    let args = proc_macro2::TokenStream::from(args);
+    let mut item = parse_macro_input!(input as ItemMod);
+
+    let attr: Attribute = if args.is_empty() { parse_quote!(#[burrmod]) } else { parse_quote!(#[burrmod(#args)]) };
+    let result = burrtype_internal::parse::burrmod_args(&attr).and_then(|args| gen::mod_ir(args, &mut item));
+
+    match result {
+        Ok(_) => quote!(#item).into(),
+        Err(err) => {
+            let err = err.into_compile_error();
+            quote!(#item #err).into()
+        }
+    }
+}
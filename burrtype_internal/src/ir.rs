@@ -1,19 +1,31 @@
+#[path = "ir/alias.rs"]
+mod alias;
 #[path = "ir/attr.rs"]
 mod attr;
+#[path = "ir/const.rs"]
+mod r#const;
 #[path = "ir/enum.rs"]
 mod r#enum;
 #[path = "ir/field.rs"]
 mod field;
 #[path = "ir/item.rs"]
 mod item;
+#[path = "ir/key.rs"]
+mod key;
 #[path = "ir/mod.rs"]
 mod r#mod;
 #[path = "ir/struct.rs"]
 mod r#struct;
+#[path = "ir/view.rs"]
+mod view;
 
+pub use alias::*;
 pub use attr::*;
+pub use r#const::*;
 pub use r#enum::*;
 pub use field::*;
 pub use item::*;
+pub use key::*;
 pub use r#mod::*;
 pub use r#struct::*;
+pub use view::*;
@@ -0,0 +1,41 @@
+use super::{paths_match, IrType, TypeKey};
+use proc_macro2::Ident;
+
+/// A type alias with the format:
+/// ```text
+/// type T = type;
+/// ```
+/// Also describes types serde writes as another, such as those with `#[serde(transparent)]` or `#[serde(into = "T")]`
+#[derive(Clone, Debug)]
+pub struct IrAlias {
+    pub ident: Ident,
+    /// Aliases share a `TypeId` with the type they stand for, so they are keyed by their path
+    /// Types written as another have a `TypeId` of their own, which they are keyed by as usual
+    pub id: TypeKey,
+    /// Full Rust path to the alias, such as `my_crate::api::UserId`
+    pub path: &'static str,
+    /// The type being aliased
+    pub ty: IrType,
+    /// The type read instead of `ty`, such as with `#[serde(from = "T")]`, when it differs
+    pub de_ty: Option<IrType>,
+    /// Key that fields written with this alias are given, which is the key of the aliased type as a whole
+    pub target: TypeKey,
+    #[cfg(feature = "docs")]
+    pub docs: Option<&'static str>,
+    pub r#mod: Option<&'static str>,
+}
+
+impl IrAlias {
+    pub fn name(&self) -> String {
+        self.ident.to_string()
+    }
+
+    /// Checks whether a type was written with this alias
+    /// Both the written path and the key need to match, since another type could share the alias's name
+    pub fn matches(&self, ty: &IrType) -> bool {
+        // `crate::`, `self::`, and `super::` say nothing about the alias's name, so leave them out of the comparison
+        let written = ty.path_string();
+        let written: Vec<&str> = written.split("::").skip_while(|s| matches!(*s, "crate" | "self" | "super")).collect();
+        ty.id == self.target && paths_match(self.path, &written.join("::"))
+    }
+}
@@ -0,0 +1,27 @@
+use super::{IrType, TypeKey};
+use proc_macro2::Ident;
+
+/// A constant with the format:
+/// ```text
+/// const NAME: type = value;
+/// ```
+#[derive(Clone, Debug)]
+pub struct IrConst {
+    pub ident: Ident,
+    /// Constants have no `TypeId` of their own, so they are always keyed by their path
+    pub id: TypeKey,
+    /// Full Rust path to the constant, such as `my_crate::api::MAX_PAGE_SIZE`
+    pub path: &'static str,
+    pub ty: IrType,
+    /// The value, serialized as JSON
+    pub value: String,
+    #[cfg(feature = "docs")]
+    pub docs: Option<&'static str>,
+    pub r#mod: Option<&'static str>,
+}
+
+impl IrConst {
+    pub fn name(&self) -> String {
+        self.ident.to_string()
+    }
+}
@@ -1,7 +1,7 @@
-use super::{ IrNamedField, IrUnnamedField};
-use std::any::TypeId;
+use super::{Direction, IrFlattened, IrNamedField, IrUnnamedField, TypeKey};
 use std::collections::HashSet;
-use proc_macro2::Ident;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{quote, ToTokens};
 
 #[derive(Clone, Copy, Debug)]
 pub enum EnumRepr {
@@ -12,14 +12,79 @@ pub enum EnumRepr {
         tag: &'static str,
         content: &'static str,
     },
+    /// Serialized as the discriminant of each variant, such as with `serde_repr`
+    /// Only valid for enums where every variant is a unit variant
+    Int,
+}
+
+impl ToTokens for EnumRepr {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name: Ident = Ident::new("EnumRepr", Span::call_site());
+        tokens.extend(match *self {
+            EnumRepr::External => quote!(#name :: External),
+            EnumRepr::Untagged => quote!(#name :: Untagged),
+            EnumRepr::Internal(tag) => quote!(#name :: Internal ( #tag )),
+            EnumRepr::Adjacent { tag, content } => quote!(#name :: Adjacent {
+                tag: #tag,
+                content: #content,
+            }),
+            EnumRepr::Int => quote!(#name :: Int),
+        })
+    }
+}
+
+/// How an enum without fields is written, for targets with more than one way to do so
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "snake_case"))]
+pub enum EnumStyle {
+    /// A union of literals, such as `"One" | "Two"`
+    #[default]
+    Union,
+    /// An `enum` with each variant set to the value it is serialized as
+    Enum,
+    /// An `enum` that is inlined wherever it is used
+    ConstEnum,
+    /// A union along with an array of every value, such as `export const THINGS = ["One", "Two"] as const`
+    UnionWithValues,
+}
+
+impl EnumStyle {
+    pub fn parse(style: &str) -> Option<Self> {
+        match style {
+            "union" => Some(EnumStyle::Union),
+            "enum" => Some(EnumStyle::Enum),
+            "const_enum" => Some(EnumStyle::ConstEnum),
+            "union_with_values" => Some(EnumStyle::UnionWithValues),
+            _ => None,
+        }
+    }
+}
+
+impl ToTokens for EnumStyle {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name: Ident = Ident::new("EnumStyle", Span::call_site());
+        let style: Ident = Ident::new(match *self {
+            EnumStyle::Union => "Union",
+            EnumStyle::Enum => "Enum",
+            EnumStyle::ConstEnum => "ConstEnum",
+            EnumStyle::UnionWithValues => "UnionWithValues",
+        }, Span::call_site());
+        tokens.extend(quote! {
+            #name :: #style
+        })
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct IrEnum {
     pub ident: Ident,
-    pub id: TypeId,
+    pub id: TypeKey,
+    /// Full Rust path to the type, such as `my_crate::api::User`
+    pub path: &'static str,
     pub variants: Vec<IrEnumVariant>,
     pub repr: EnumRepr,
+    /// Set by `#[burr(enum_style = "...")]`, overriding the target's choice
+    pub style: Option<EnumStyle>,
     #[cfg(feature = "docs")]
     pub docs: Option<&'static str>,
     pub r#mod: Option<&'static str>,
@@ -40,13 +105,13 @@ pub enum IrEnumVariant {
 
 impl IrEnumVariant {
     /// Gets a flat set of all types being used by a variant
-    pub fn all_field_types(&self) -> HashSet<TypeId> {
+    pub fn all_field_types(&self) -> HashSet<TypeKey> {
         match self {
             IrEnumVariant::Struct(inner) => {
-                HashSet::from_iter(inner.fields.iter().map(|field| field.ty.id))
+                HashSet::from_iter(inner.fields.iter().flat_map(IrNamedField::types).chain(inner.flattened.iter().filter_map(IrFlattened::ty)).map(|ty| ty.id))
             },
             IrEnumVariant::Tuple(inner) => {
-                HashSet::from_iter(inner.fields.iter().map(|field| field.ty.id))
+                HashSet::from_iter(inner.fields.iter().flat_map(IrUnnamedField::types).map(|ty| ty.id))
             },
             _ => HashSet::default(),
         }
@@ -63,10 +128,42 @@ impl IrEnumVariant {
     pub fn name(&self) -> String {
         self.ident().to_string()
     }
+
+    /// The name this variant is deserialized with, when it differs
+    pub fn de_ident(&self) -> Option<&Ident> {
+        match self {
+            IrEnumVariant::Struct(inner) => inner.de_ident.as_ref(),
+            IrEnumVariant::Tuple(inner) => inner.de_ident.as_ref(),
+            IrEnumVariant::Unit(inner) => inner.de_ident.as_ref(),
+        }
+    }
+
+    /// Other names this variant is deserialized with
+    pub fn aliases(&self) -> &[Ident] {
+        match self {
+            IrEnumVariant::Struct(inner) => &inner.aliases,
+            IrEnumVariant::Tuple(inner) => &inner.aliases,
+            IrEnumVariant::Unit(inner) => &inner.aliases,
+        }
+    }
+
+    /// Whether unknown variants are read as this one
+    pub fn is_other(&self) -> bool {
+        matches!(self, IrEnumVariant::Unit(inner) if inner.other)
+    }
+
+    /// The way this variant is left out of, if any
+    pub fn skip(&self) -> Option<Direction> {
+        match self {
+            IrEnumVariant::Struct(inner) => inner.skip,
+            IrEnumVariant::Tuple(inner) => inner.skip,
+            IrEnumVariant::Unit(inner) => inner.skip,
+        }
+    }
 }
 
 /// A variant with the format:
-/// ```
+/// ```text
 /// T {
 ///     name: type,
 ///     ...
@@ -74,8 +171,17 @@ impl IrEnumVariant {
 /// ```
 #[derive(Clone, Debug)]
 pub struct IrEnumStructVariant {
+    /// The name this variant is serialized with
     pub ident: Ident,
+    /// The name this variant is deserialized with, when it differs
+    pub de_ident: Option<Ident>,
+    /// Other names this variant is deserialized with, from `#[serde(alias = "...")]`
+    pub aliases: Vec<Ident>,
+    /// Left out entirely when going this way, from `#[serde(skip_serializing)]` or `#[serde(skip_deserializing)]`
+    pub skip: Option<Direction>,
     pub fields: Vec<IrNamedField>,
+    /// Enums and maps flattened into the variant
+    pub flattened: Vec<IrFlattened>,
     #[cfg(feature = "docs")]
     pub docs: Option<&'static str>,
 }
@@ -93,12 +199,19 @@ impl From<IrEnumStructVariant> for IrEnumVariant {
 }
 
 /// A variant with the format:
-/// ```
+/// ```text
 /// T (U, ...),
 /// ```
 #[derive(Clone, Debug)]
 pub struct IrEnumTupleVariant {
+    /// The name this variant is serialized with
     pub ident: Ident,
+    /// The name this variant is deserialized with, when it differs
+    pub de_ident: Option<Ident>,
+    /// Other names this variant is deserialized with, from `#[serde(alias = "...")]`
+    pub aliases: Vec<Ident>,
+    /// Left out entirely when going this way, from `#[serde(skip_serializing)]` or `#[serde(skip_deserializing)]`
+    pub skip: Option<Direction>,
     pub fields: Vec<IrUnnamedField>,
     #[cfg(feature = "docs")]
     pub docs: Option<&'static str>,
@@ -117,12 +230,23 @@ impl From<IrEnumTupleVariant> for IrEnumVariant {
 }
 
 /// A variant with the format:
-/// ```
+/// ```text
 /// T,
 /// ```
 #[derive(Clone, Debug)]
 pub struct IrEnumUnitVariant {
+    /// The name this variant is serialized with
     pub ident: Ident,
+    /// The name this variant is deserialized with, when it differs
+    pub de_ident: Option<Ident>,
+    /// Other names this variant is deserialized with, from `#[serde(alias = "...")]`
+    pub aliases: Vec<Ident>,
+    /// Left out entirely when going this way, from `#[serde(skip_serializing)]` or `#[serde(skip_deserializing)]`
+    pub skip: Option<Direction>,
+    /// Only known for enums where every variant is a unit variant
+    pub discriminant: Option<i128>,
+    /// Read in place of any variant the enum does not know, from `#[serde(other)]`
+    pub other: bool,
     #[cfg(feature = "docs")]
     pub docs: Option<&'static str>,
 }
@@ -137,4 +261,4 @@ impl From<IrEnumUnitVariant> for IrEnumVariant {
     fn from(value: IrEnumUnitVariant) -> Self {
         IrEnumVariant::Unit(value)
     }
-}
\ No newline at end of file
+}
@@ -1,20 +1,109 @@
-use std::any::TypeId;
-use proc_macro2::Ident;
+use super::{Direction, TypeKey};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{quote, ToTokens};
 use syn::TypePath;
 
 #[derive(Clone, Debug)]
 pub struct IrType {
     pub path: TypePath,
-    pub id: TypeId,
+    pub id: TypeKey,
     pub optional: bool,
     pub array: bool,
 }
 
+impl IrType {
+    /// Gets the path this type was written with, without any generics
+    pub fn path_string(&self) -> String {
+        self.path.path.segments.iter().map(|segment| segment.ident.to_string()).collect::<Vec<_>>().join("::")
+    }
+}
+
+/// How integers wider than 32 bits are written, for targets whose numbers cannot hold all of them
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "snake_case"))]
+pub enum IntStyle {
+    /// The target's usual number, marked as losing precision past 2^53
+    #[default]
+    Number,
+    /// An integer of any size, such as TypeScript's `bigint`, for clients reading JSON with a parser that makes them
+    BigInt,
+    /// Text, for integers written with something like `serde_with::DisplayFromStr`
+    String,
+}
+
+impl IntStyle {
+    pub fn parse(style: &str) -> Option<Self> {
+        match style {
+            "number" => Some(IntStyle::Number),
+            "bigint" => Some(IntStyle::BigInt),
+            "string" => Some(IntStyle::String),
+            _ => None,
+        }
+    }
+}
+
+impl ToTokens for IntStyle {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name: Ident = Ident::new("IntStyle", Span::call_site());
+        let style: Ident = Ident::new(match *self {
+            IntStyle::Number => "Number",
+            IntStyle::BigInt => "BigInt",
+            IntStyle::String => "String",
+        }, Span::call_site());
+        tokens.extend(quote! {
+            #name :: #style
+        })
+    }
+}
+
+/// Types written as is in place of a field's own, which no other target needs to know about
+#[derive(Clone, Debug, Default)]
+pub struct IrLiterals {
+    /// Written by every target without one of its own, from `#[burr(type = "...")]`
+    pub any: Option<&'static str>,
+    /// Written by a single target, keyed by its name, from `#[burr(ts = "...")]` or `#[burr(target(ts = "..."))]`
+    pub targets: Vec<(&'static str, &'static str)>,
+}
+
+impl IrLiterals {
+    /// Gets the type written as is by a target, if any
+    pub fn get(&self, target: &str) -> Option<&'static str> {
+        self.targets.iter().find(|(name, _)| *name == target).map(|(_, ty)| *ty).or(self.any)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.any.is_none() && self.targets.is_empty()
+    }
+
+    /// Keeps only what one target writes, so it stands for every target from then on
+    pub fn pick(&mut self, target: &str) {
+        self.any = self.get(target);
+        self.targets.clear();
+    }
+}
+
 /// A `name: type,` field
 #[derive(Clone, Debug)]
 pub struct IrNamedField {
+    /// The name this field is serialized with
     pub ident: Ident,
+    /// The name this field is deserialized with, when it differs
+    pub de_ident: Option<Ident>,
+    /// Other names this field is deserialized with, from `#[serde(alias = "...")]`
+    pub aliases: Vec<Ident>,
     pub ty: IrType,
+    /// The type this field is deserialized as, when it differs, from `#[serde(deserialize_with = "...")]` and friends
+    pub de_ty: Option<IrType>,
+    /// Types written as is in place of `ty`
+    pub literals: IrLiterals,
+    /// Set by `#[burr(int_style = "...")]`, overriding the target's choice for wide integers
+    pub int_style: Option<IntStyle>,
+    /// Left out entirely when going this way, from `#[serde(skip_serializing)]` or `#[serde(skip_deserializing)]`
+    pub skip: Option<Direction>,
+    /// May be left out when deserializing, from `#[serde(default)]` on the field or its container
+    pub default: bool,
+    /// May be left out when serializing, from `#[serde(skip_serializing_if = "...")]`
+    pub skip_serializing_if: bool,
     #[cfg(feature = "docs")]
     pub docs: Option<&'static str>,
 }
@@ -23,6 +112,10 @@ pub struct IrNamedField {
 #[derive(Clone, Debug)]
 pub struct IrUnnamedField {
     pub ty: IrType,
+    /// Types written as is in place of `ty`
+    pub literals: IrLiterals,
+    /// Set by `#[burr(int_style = "...")]`, overriding the target's choice for wide integers
+    pub int_style: Option<IntStyle>,
     #[cfg(feature = "docs")]
     pub docs: Option<&'static str>,
 }
@@ -31,4 +124,20 @@ impl IrNamedField {
     pub fn name(&self) -> String {
         self.ident.to_string()
     }
-}
\ No newline at end of file
+
+    /// Gets the types this field is exported as, which are none when it is given as a literal
+    pub fn types(&self) -> impl Iterator<Item = &IrType> {
+        [Some(&self.ty), self.de_ty.as_ref()].into_iter().flatten().filter(|_| self.literals.any.is_none())
+    }
+
+    /// Whether this field can be missing from the JSON, whichever way it is going
+    pub fn is_optional(&self) -> bool {
+        self.ty.optional || self.default || self.skip_serializing_if || self.skip.is_some()
+    }
+}
+impl IrUnnamedField {
+    /// Gets the type this field is exported as, which is none when it is given as a literal
+    pub fn types(&self) -> impl Iterator<Item = &IrType> {
+        self.literals.any.is_none().then_some(&self.ty).into_iter()
+    }
+}
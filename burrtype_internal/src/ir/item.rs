@@ -1,6 +1,5 @@
-use std::any::TypeId;
 use std::collections::HashSet;
-use super::{IrNamedStruct, IrTupleStruct, IrUnitStruct};
+use super::{IrAlias, IrConst, IrEnumVariant, IrFlattened, IrLiterals, IrNamedField, IrNamedStruct, IrType, IrTupleStruct, IrUnitStruct, IrUnnamedField, TypeKey};
 use syn::Ident;
 use crate::ir::IrEnum;
 
@@ -10,6 +9,8 @@ pub enum IrItem {
     TupleStruct(IrTupleStruct),
     UnitStruct(IrUnitStruct),
     Enum(IrEnum),
+    Const(IrConst),
+    Alias(IrAlias),
 }
 
 impl IrItem {
@@ -19,6 +20,19 @@ impl IrItem {
             IrItem::TupleStruct(inner) => &inner.ident,
             IrItem::UnitStruct(inner) => &inner.ident,
             IrItem::Enum(inner) => &inner.ident,
+            IrItem::Const(inner) => &inner.ident,
+            IrItem::Alias(inner) => &inner.ident,
+        }
+    }
+
+    pub fn ident_mut(&mut self) -> &mut Ident {
+        match self {
+            IrItem::NamedStruct(inner) => &mut inner.ident,
+            IrItem::TupleStruct(inner) => &mut inner.ident,
+            IrItem::UnitStruct(inner) => &mut inner.ident,
+            IrItem::Enum(inner) => &mut inner.ident,
+            IrItem::Const(inner) => &mut inner.ident,
+            IrItem::Alias(inner) => &mut inner.ident,
         }
     }
 
@@ -26,32 +40,48 @@ impl IrItem {
         self.ident().to_string()
     }
 
+    /// Gets the full Rust path to the item, such as `my_crate::api::User`
+    pub fn path(&self) -> &'static str {
+        match self {
+            IrItem::NamedStruct(inner) => inner.path,
+            IrItem::TupleStruct(inner) => inner.path,
+            IrItem::UnitStruct(inner) => inner.path,
+            IrItem::Enum(inner) => inner.path,
+            IrItem::Const(inner) => inner.path,
+            IrItem::Alias(inner) => inner.path,
+        }
+    }
+
     pub fn mod_override(&self) -> Option<&'static str> {
         match self {
             IrItem::NamedStruct(inner) => inner.r#mod,
             IrItem::TupleStruct(inner) => inner.r#mod,
             IrItem::UnitStruct(inner) => inner.r#mod,
             IrItem::Enum(inner) => inner.r#mod,
+            IrItem::Const(inner) => inner.r#mod,
+            IrItem::Alias(inner) => inner.r#mod,
         }
     }
 
-    pub fn type_id(&self) -> TypeId {
+    pub fn type_id(&self) -> TypeKey {
         match self {
             IrItem::NamedStruct(inner) => inner.id,
             IrItem::TupleStruct(inner) => inner.id,
             IrItem::UnitStruct(inner) => inner.id,
             IrItem::Enum(inner) => inner.id,
+            IrItem::Const(inner) => inner.id,
+            IrItem::Alias(inner) => inner.id,
         }
     }
 
     /// Gets a flat set of all types being used by an item
-    pub fn all_field_types(&self) -> HashSet<TypeId> {
+    pub fn all_field_types(&self) -> HashSet<TypeKey> {
         match self {
             IrItem::NamedStruct(inner) => {
-                HashSet::from_iter(inner.fields.iter().map(|field| field.ty.id))
+                HashSet::from_iter(inner.fields.iter().flat_map(IrNamedField::types).chain(inner.flattened.iter().filter_map(IrFlattened::ty)).map(|ty| ty.id))
             },
             IrItem::TupleStruct(inner) => {
-                HashSet::from_iter(inner.fields.iter().map(|field| field.ty.id))
+                HashSet::from_iter(inner.fields.iter().flat_map(IrUnnamedField::types).map(|ty| ty.id))
             },
             IrItem::Enum(inner) => {
                 let mut types = HashSet::new();
@@ -60,9 +90,47 @@ impl IrItem {
                 }
                 types
             },
+            IrItem::Alias(inner) => inner.de_ty.iter().chain([&inner.ty]).map(|ty| ty.id).collect(),
             _ => HashSet::default(),
         }
     }
+
+    /// Gets every type used by an item, so they can be pointed elsewhere
+    pub fn types_mut(&mut self) -> Vec<&mut IrType> {
+        match self {
+            IrItem::NamedStruct(inner) => inner.fields.iter_mut().flat_map(|field| field.de_ty.iter_mut().chain([&mut field.ty]))
+                .chain(inner.flattened.iter_mut().filter_map(IrFlattened::ty_mut))
+                .collect(),
+            IrItem::TupleStruct(inner) => inner.fields.iter_mut().map(|field| &mut field.ty).collect(),
+            IrItem::Enum(inner) => inner.variants.iter_mut().flat_map(|var| match var {
+                IrEnumVariant::Struct(var) => var.fields.iter_mut().flat_map(|field| field.de_ty.iter_mut().chain([&mut field.ty]))
+                    .chain(var.flattened.iter_mut().filter_map(IrFlattened::ty_mut))
+                    .collect(),
+                IrEnumVariant::Tuple(var) => var.fields.iter_mut().map(|field| &mut field.ty).collect(),
+                IrEnumVariant::Unit(_) => Vec::new(),
+            }).collect(),
+            IrItem::Alias(inner) => inner.de_ty.iter_mut().chain([&mut inner.ty]).collect(),
+            IrItem::Const(inner) => vec![&mut inner.ty],
+            IrItem::UnitStruct(_) => Vec::new(),
+        }
+    }
+
+    /// Keeps only the types a target writes as is in place of its fields' own, see [`IrLiterals::pick`]
+    pub fn pick_literals(&mut self, target: &str) {
+        let literals: Vec<&mut IrLiterals> = match self {
+            IrItem::NamedStruct(inner) => inner.fields.iter_mut().map(|field| &mut field.literals).collect(),
+            IrItem::TupleStruct(inner) => inner.fields.iter_mut().map(|field| &mut field.literals).collect(),
+            IrItem::Enum(inner) => inner.variants.iter_mut().flat_map(|var| match var {
+                IrEnumVariant::Struct(var) => var.fields.iter_mut().map(|field| &mut field.literals).collect(),
+                IrEnumVariant::Tuple(var) => var.fields.iter_mut().map(|field| &mut field.literals).collect(),
+                IrEnumVariant::Unit(_) => Vec::new(),
+            }).collect(),
+            IrItem::Alias(_) | IrItem::Const(_) | IrItem::UnitStruct(_) => Vec::new(),
+        };
+        for literals in literals {
+            literals.pick(target);
+        }
+    }
 }
 
 impl From<IrNamedStruct> for IrItem {
@@ -83,6 +151,18 @@ impl From<IrUnitStruct> for IrItem {
     }
 }
 
+impl From<IrAlias> for IrItem {
+    fn from(value: IrAlias) -> Self {
+        IrItem::Alias(value)
+    }
+}
+
+impl From<IrConst> for IrItem {
+    fn from(value: IrConst) -> Self {
+        IrItem::Const(value)
+    }
+}
+
 impl From<IrEnum> for IrItem {
     fn from(value: IrEnum) -> Self {
         IrItem::Enum(value)
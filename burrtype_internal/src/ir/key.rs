@@ -0,0 +1,105 @@
+use std::any::TypeId;
+use std::fmt::{Display, Formatter};
+
+/// Identifies a type within the IR
+/// Types known to the running program are keyed by their `TypeId`
+/// Types we only know by name, such as those parsed from source in a build script, are keyed by their path
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TypeKey {
+    Id(TypeId),
+    Path(&'static str),
+}
+
+impl TypeKey {
+    pub fn of<T: ?Sized + 'static>() -> Self {
+        TypeKey::Id(TypeId::of::<T>())
+    }
+
+    /// Whether this is an integer wider than 32 bits, which some targets cannot hold exactly
+    pub fn is_wide_int(&self) -> bool {
+        [
+            TypeKey::of::<u64>(),
+            TypeKey::of::<i64>(),
+            TypeKey::of::<u128>(),
+            TypeKey::of::<i128>(),
+            TypeKey::of::<usize>(),
+            TypeKey::of::<isize>(),
+        ].contains(self)
+    }
+
+    /// Gets the key for builtin types that every target should understand, such as `u32` or `String`
+    /// Only the last segment is considered for paths through `std`, `core`, or `alloc`
+    pub fn from_std_path(path: &str) -> Option<Self> {
+        let segments: Vec<&str> = path.split("::").map(str::trim).filter(|s| !s.is_empty()).collect();
+        let (name, parents) = segments.split_last()?;
+        if let Some(root) = parents.first() {
+            if !matches!(*root, "std" | "core" | "alloc") {
+                return None;
+            }
+        }
+
+        Some(match *name {
+            "str" => TypeKey::of::<str>(),
+            "char" => TypeKey::of::<char>(),
+            "String" => TypeKey::of::<String>(),
+            "bool" => TypeKey::of::<bool>(),
+            "usize" => TypeKey::of::<usize>(),
+            "u8" => TypeKey::of::<u8>(),
+            "u16" => TypeKey::of::<u16>(),
+            "u32" => TypeKey::of::<u32>(),
+            "u64" => TypeKey::of::<u64>(),
+            "u128" => TypeKey::of::<u128>(),
+            "isize" => TypeKey::of::<isize>(),
+            "i8" => TypeKey::of::<i8>(),
+            "i16" => TypeKey::of::<i16>(),
+            "i32" => TypeKey::of::<i32>(),
+            "i64" => TypeKey::of::<i64>(),
+            "i128" => TypeKey::of::<i128>(),
+            "f32" => TypeKey::of::<f32>(),
+            "f64" => TypeKey::of::<f64>(),
+            _ => return None,
+        })
+    }
+
+    /// Gets the path, if this type is keyed by one
+    pub fn path(&self) -> Option<&'static str> {
+        match self {
+            TypeKey::Id(_) => None,
+            TypeKey::Path(path) => Some(path),
+        }
+    }
+}
+
+impl From<TypeId> for TypeKey {
+    fn from(value: TypeId) -> Self {
+        TypeKey::Id(value)
+    }
+}
+
+impl Display for TypeKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeKey::Id(id) => write!(f, "{id:?}"),
+            TypeKey::Path(path) => write!(f, "{path}"),
+        }
+    }
+}
+
+/// Checks whether two Rust paths could name the same type
+/// The shorter path must match the tail of the longer one, so `Decimal` matches `rust_decimal::Decimal`
+/// Whitespace and leading `::` are ignored
+pub fn paths_match(a: &str, b: &str) -> bool {
+    let a: Vec<&str> = a.split("::").map(str::trim).filter(|s| !s.is_empty()).collect();
+    let b: Vec<&str> = b.split("::").map(str::trim).filter(|s| !s.is_empty()).collect();
+    if a.is_empty() || b.is_empty() {
+        return false;
+    }
+
+    a.iter().rev().zip(b.iter().rev()).all(|(a, b)| a == b)
+}
+
+/// Moves a string to the heap for the rest of the program
+/// The IR favors `&'static str` since the derive produces literals, so anything built at runtime needs to live as long
+pub fn leak_str<S: Into<String>>(s: S) -> &'static str {
+    Box::leak(s.into().into_boxed_str())
+}
@@ -11,17 +11,30 @@ impl<IR: IrExt> From<IR> for IrItem {
     }
 }
 
+/// Helper trait to get the contents of a module marked `#[burrmod]`
+/// Rust modules are not types, so the attribute generates one to implement this on
 pub trait ModExt {
-    fn name() -> Ident;
-    fn flatten() -> bool;
-    fn items() -> Vec<IrItem>;
+    fn get_ir() -> IrMod;
 }
 
+/// A module with the format:
+/// ```text
+/// mod name {
+///     item,
+///     ...
+/// }
+/// ```
 #[derive(Clone, Debug)]
 pub struct IrMod {
-    pub name: Ident,
+    /// Name the module is exported with
+    pub name: &'static str,
+    /// Name of the type generated to describe the module
     pub ir_name: Ident,
+    /// Merges this module's contents into its parent
     pub flatten: bool,
+    /// Whether the module's contents were written inline rather than in another file
     pub inline: bool,
     pub items: Vec<IrItem>,
+    /// Modules nested within this one
+    pub mods: Vec<IrMod>,
 }
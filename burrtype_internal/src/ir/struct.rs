@@ -1,14 +1,72 @@
-use super::{IrNamedField, IrUnnamedField};
-use std::any::TypeId;
+use super::{IrNamedField, IrType, IrUnnamedField, TypeKey};
 use proc_macro2::Ident;
 
 /// Helper trait to get flatten-able items at compile time
-pub trait NamedStructExt {
-    fn fields() -> Vec<IrNamedField>;
+pub trait FlattenExt {
+    fn flatten() -> IrFlatten;
+}
+
+/// What a type adds to the struct it is flattened into with `#[serde(flatten)]`
+#[derive(Clone, Debug, Default)]
+pub struct IrFlatten {
+    /// Substituted in alongside the struct's own fields
+    pub fields: Vec<IrNamedField>,
+    /// Written alongside the struct's own fields, without any fields to substitute in
+    pub flattened: Vec<IrFlattened>,
+}
+
+impl IrFlatten {
+    /// Applies `#[serde(default)]` on the flattened field, which covers every field inside it
+    pub fn with_default(mut self, default: bool) -> Self {
+        for field in &mut self.fields {
+            field.default |= default;
+        }
+        self
+    }
+
+    /// Flattening an `Option` writes nothing for `None`, and reads anything missing as `None`
+    pub fn with_optional(mut self, optional: bool) -> Self {
+        for field in &mut self.fields {
+            field.default |= optional;
+            field.skip_serializing_if |= optional;
+        }
+        for flattened in &mut self.flattened {
+            if let IrFlattened::Enum(ty) = flattened {
+                ty.optional |= optional;
+            }
+        }
+        self
+    }
+}
+
+/// A type flattened into a struct whose contents cannot be substituted in as fields
+#[derive(Clone, Debug)]
+pub enum IrFlattened {
+    /// An enum, whose tag and contents sit alongside the struct's own fields
+    /// Optional when flattened through an `Option`, which writes nothing for `None`
+    Enum(IrType),
+    /// A map, holding every key the struct's fields do not
+    Map,
+}
+
+impl IrFlattened {
+    pub fn ty(&self) -> Option<&IrType> {
+        match self {
+            IrFlattened::Enum(ty) => Some(ty),
+            IrFlattened::Map => None,
+        }
+    }
+
+    pub fn ty_mut(&mut self) -> Option<&mut IrType> {
+        match self {
+            IrFlattened::Enum(ty) => Some(ty),
+            IrFlattened::Map => None,
+        }
+    }
 }
 
 /// A struct with the format:
-/// ```
+/// ```text
 /// struct T {
 ///     name: type,
 ///     ...
@@ -17,8 +75,14 @@ pub trait NamedStructExt {
 #[derive(Clone, Debug)]
 pub struct IrNamedStruct {
     pub ident: Ident,
-    pub id: TypeId,
+    pub id: TypeKey,
+    /// Full Rust path to the type, such as `my_crate::api::User`
+    pub path: &'static str,
     pub fields: Vec<IrNamedField>,
+    /// Enums and maps flattened into the struct
+    pub flattened: Vec<IrFlattened>,
+    /// Set by `#[serde(tag = "...")]`
+    pub tag: Option<IrStructTag>,
     #[cfg(feature = "docs")]
     pub docs: Option<&'static str>,
     pub r#mod: Option<&'static str>,
@@ -30,14 +94,25 @@ impl IrNamedStruct {
     }
 }
 
+/// A field serde writes ahead of a struct's own fields, holding the struct's name
+#[derive(Clone, Debug)]
+pub struct IrStructTag {
+    /// Name of the field
+    pub field: &'static str,
+    /// The struct's name, as written in the field
+    pub value: &'static str,
+}
+
 /// A struct with the format:
-/// ```
+/// ```text
 /// struct T (type, ...);
 /// ```
 #[derive(Clone, Debug)]
 pub struct IrTupleStruct {
     pub ident: Ident,
-    pub id: TypeId,
+    pub id: TypeKey,
+    /// Full Rust path to the type, such as `my_crate::api::User`
+    pub path: &'static str,
     pub fields: Vec<IrUnnamedField>,
     #[cfg(feature = "docs")]
     pub docs: Option<&'static str>,
@@ -51,13 +126,15 @@ impl IrTupleStruct {
 }
 
 /// A struct with the format:
-/// ```
+/// ```text
 /// struct T;
 /// ```
 #[derive(Clone, Debug)]
 pub struct IrUnitStruct {
     pub ident: Ident,
-    pub id: TypeId,
+    pub id: TypeKey,
+    /// Full Rust path to the type, such as `my_crate::api::User`
+    pub path: &'static str,
     #[cfg(feature = "docs")]
     pub docs: Option<&'static str>,
     pub r#mod: Option<&'static str>,
@@ -0,0 +1,143 @@
+use super::{IrEnumVariant, IrItem, IrNamedField};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{quote, ToTokens};
+
+/// Which way a value is going through serde
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "snake_case"))]
+pub enum Direction {
+    /// Written by us, such as a response
+    Serialize,
+    /// Read by us, such as a request
+    Deserialize,
+}
+
+impl ToTokens for Direction {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name: Ident = Ident::new("Direction", Span::call_site());
+        let direction: Ident = Ident::new(match *self {
+            Direction::Serialize => "Serialize",
+            Direction::Deserialize => "Deserialize",
+        }, Span::call_site());
+        tokens.extend(quote! {
+            #name :: #direction
+        })
+    }
+}
+
+impl IrItem {
+    /// Whether serde reads this item in exactly the shape it writes it
+    /// Fields and variants renamed, aliased or skipped on one side, along with fields that may be missing or read as another type on only one side, make the two differ
+    pub fn is_symmetric(&self) -> bool {
+        // `Option`s may be missing either way
+        let field = |field: &IrNamedField| field.de_ident.is_none() && field.aliases.is_empty() && field.skip.is_none()
+            && field.de_ty.is_none() && (field.ty.optional || field.default == field.skip_serializing_if);
+        match self {
+            IrItem::NamedStruct(inner) => inner.fields.iter().all(field),
+            IrItem::Enum(inner) => inner.variants.iter().all(|var| {
+                var.de_ident().is_none() && var.aliases().is_empty() && var.skip().is_none() && !var.is_other() && match var {
+                    IrEnumVariant::Struct(var) => var.fields.iter().all(field),
+                    _ => true,
+                }
+            }),
+            IrItem::Alias(inner) => inner.de_ty.is_none(),
+            _ => true,
+        }
+    }
+
+    /// Gets this item as serde sees it going one way
+    /// Fields and variants take the name and type used that way, anything skipped is left out, and fields are only optional where that side allows it
+    /// Aliases become fields and variants of their own when reading, where aliased fields are all optional since any one of their names may be given
+    pub fn view(&self, direction: Direction) -> IrItem {
+        let mut item = self.clone();
+        match &mut item {
+            IrItem::NamedStruct(inner) => view_fields(&mut inner.fields, direction),
+            IrItem::Enum(inner) => {
+                inner.variants.retain(|var| var.skip() != Some(direction));
+                for var in &mut inner.variants {
+                    let (ident, de_ident, skip) = match var {
+                        IrEnumVariant::Struct(var) => {
+                            view_fields(&mut var.fields, direction);
+                            (&mut var.ident, &mut var.de_ident, &mut var.skip)
+                        }
+                        IrEnumVariant::Tuple(var) => (&mut var.ident, &mut var.de_ident, &mut var.skip),
+                        IrEnumVariant::Unit(var) => {
+                            // the catch-all is written under its own name like any other variant
+                            var.other &= direction == Direction::Deserialize;
+                            (&mut var.ident, &mut var.de_ident, &mut var.skip)
+                        }
+                    };
+                    view_name(ident, de_ident, direction);
+                    *skip = None;
+                }
+                inner.variants = inner.variants.drain(..)
+                    .flat_map(|mut var| {
+                        let aliases = match &mut var {
+                            IrEnumVariant::Struct(var) => std::mem::take(&mut var.aliases),
+                            IrEnumVariant::Tuple(var) => std::mem::take(&mut var.aliases),
+                            IrEnumVariant::Unit(var) => std::mem::take(&mut var.aliases),
+                        };
+                        let aliased: Vec<IrEnumVariant> = match direction {
+                            Direction::Serialize => Vec::new(),
+                            Direction::Deserialize => aliases.into_iter().map(|alias| {
+                                let mut var = var.clone();
+                                match &mut var {
+                                    IrEnumVariant::Struct(var) => var.ident = alias,
+                                    IrEnumVariant::Tuple(var) => var.ident = alias,
+                                    IrEnumVariant::Unit(var) => var.ident = alias,
+                                }
+                                var
+                            }).collect(),
+                        };
+                        std::iter::once(var).chain(aliased)
+                    })
+                    .collect();
+            }
+            IrItem::Alias(inner) => {
+                if let (Direction::Deserialize, Some(ty)) = (direction, inner.de_ty.take()) {
+                    inner.ty = ty;
+                }
+                inner.de_ty = None;
+            }
+            _ => {}
+        }
+        item
+    }
+}
+
+fn view_fields(fields: &mut Vec<IrNamedField>, direction: Direction) {
+    fields.retain(|field| field.skip != Some(direction));
+    for field in fields.iter_mut() {
+        view_name(&mut field.ident, &mut field.de_ident, direction);
+        if let (Direction::Deserialize, Some(ty)) = (direction, field.de_ty.take()) {
+            field.ty = ty;
+        }
+        field.de_ty = None;
+        field.skip = None;
+        match direction {
+            Direction::Serialize => field.default = false,
+            Direction::Deserialize => field.skip_serializing_if = false,
+        }
+    }
+
+    *fields = fields.drain(..)
+        .flat_map(|mut field| {
+            let aliases = std::mem::take(&mut field.aliases);
+            let aliased: Vec<IrNamedField> = match direction {
+                Direction::Serialize => Vec::new(),
+                Direction::Deserialize => {
+                    field.default |= !aliases.is_empty();
+                    aliases.into_iter().map(|alias| IrNamedField { ident: alias, ..field.clone() }).collect()
+                }
+            };
+            std::iter::once(field).chain(aliased)
+        })
+        .collect();
+}
+
+fn view_name(ident: &mut Ident, de_ident: &mut Option<Ident>, direction: Direction) {
+    if let (Direction::Deserialize, Some(de)) = (direction, de_ident.take()) {
+        *ident = de;
+    }
+    *de_ident = None;
+}
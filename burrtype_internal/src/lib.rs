@@ -1,3 +1,4 @@
 #[path = "ir.rs"]
 pub mod ir;
-pub mod prelude;
\ No newline at end of file
+pub mod parse;
+pub mod prelude;
@@ -0,0 +1,12 @@
+//! Attribute parsing shared by the derive macro and the source scanner
+//! Everything here returns plain data so callers can either generate code from it or build IR directly
+
+mod attrs;
+mod field;
+mod item;
+mod meta;
+
+pub use attrs::*;
+pub use field::*;
+pub use item::*;
+pub use meta::*;
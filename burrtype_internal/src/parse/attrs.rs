@@ -0,0 +1,473 @@
+use super::BurrMeta;
+use crate::ir::{CaseConvention, Direction, EnumRepr, EnumStyle, IrStructTag, leak_str};
+use proc_macro2::{Ident, Span};
+use quote::ToTokens;
+use syn::ext::IdentExt;
+use syn::{Attribute, Data, Expr, Lit, LitStr, Meta, parse_quote, Token, Type};
+use syn::punctuated::Punctuated;
+
+/// Collects `///` doc comments, one line per attribute
+pub fn docs(attrs: &[Attribute]) -> Option<String> {
+    let mut docs = Vec::new();
+    for attr in attrs {
+        if let Meta::NameValue(attr) = &attr.meta {
+            if attr.path.is_ident("doc") {
+                if let Expr::Lit(lit) = &attr.value {
+                    if let Lit::Str(ls) = &lit.lit {
+                        docs.push(ls.value().trim().to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    if docs.is_empty() {
+        None
+    }
+    else {
+        Some(docs.join("\n"))
+    }
+}
+
+/// #[burr(mod = T)]
+/// Controls the export module of the output type
+#[allow(clippy::single_match)]
+pub fn burr_mod(attrs: &[Attribute]) -> Option<String> {
+    for attr in attrs {
+        // `#[burr]` on constants and aliases may also be written as `#[burrtype::burr]`
+        if attr.path().segments.last().is_some_and(|seg| seg.ident == "burr") {
+            match attr.parse_args_with(Punctuated::<BurrMeta, Token![,]>::parse_terminated) {
+                Ok(items) => {
+                    for meta in items {
+                        match meta {
+                            BurrMeta::KeywordValue(meta) if meta.path == "mod" => {
+                                let value = &meta.value;
+                                let ls: LitStr = parse_quote!(#value);
+                                return Some(ls.value());
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+                // Err(e) => panic!("{}", e.into_compile_error())
+            }
+        }
+    }
+
+    None
+}
+
+/// Arguments given to `#[burrmod(...)]`
+#[derive(Default)]
+pub struct BurrModArgs {
+    /// Merges the module's contents into its parent
+    pub flatten: bool,
+    /// Exports the module under another name
+    pub name: Option<String>,
+}
+
+/// #[burrmod(flatten, name = "T")]
+/// Controls how an inline module is exported
+pub fn burrmod_args(attr: &Attribute) -> syn::Result<BurrModArgs> {
+    let mut args = BurrModArgs::default();
+    // a bare `#[burrmod]` has no arguments to parse
+    if !matches!(attr.meta, Meta::List(_)) {
+        return Ok(args);
+    }
+
+    for meta in attr.parse_args_with(Punctuated::<BurrMeta, Token![,]>::parse_terminated)? {
+        match meta {
+            BurrMeta::Path(path) if path.is_ident("flatten") => args.flatten = true,
+            BurrMeta::KeywordValue(meta) if meta.path == "name" => {
+                let value = &meta.value;
+                let name: LitStr = syn::parse2(quote::quote!(#value))?;
+                args.name = Some(name.value());
+            }
+            meta => return Err(syn::Error::new_spanned(meta, "expected `flatten` or `name = \"...\"`")),
+        }
+    }
+
+    Ok(args)
+}
+
+/// `serde_compat` attribute:
+/// #[serde(rename = "T")]
+/// #[serde(rename(serialize = "T", deserialize = "U"))]
+/// Controls the name of the output type, as it is serialized
+pub fn serde_rename(attrs: &[Attribute]) -> Option<String> {
+    serde_rename_in(attrs, Direction::Serialize)
+}
+
+/// The name given by `#[serde(rename)]` when going one way
+pub fn serde_rename_in(attrs: &[Attribute], direction: Direction) -> Option<String> {
+    serde_sided(attrs, "rename", direction).map(|name| name.value())
+}
+
+/// Finds `#[serde(key = "T")]` or `#[serde(key(serialize = "T", deserialize = "U"))]`, taking the value for one side
+fn serde_sided(attrs: &[Attribute], key: &str, direction: Direction) -> Option<LitStr> {
+    let side = match direction {
+        Direction::Serialize => "serialize",
+        Direction::Deserialize => "deserialize",
+    };
+    let mut found = None;
+
+    for attr in attrs {
+        if attr.path().is_ident("serde") {
+            match attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) {
+                Ok(items) => {
+                    for meta in items {
+                        if !meta.path().is_ident(key) {
+                            continue;
+                        }
+                        match meta {
+                            Meta::NameValue(meta) => {
+                                // pull the T from "T"
+                                let value = &meta.value;
+                                found = Some(parse_quote!(#value));
+                            }
+                            Meta::List(meta) => {
+                                match meta.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) {
+                                    Ok(inner) => {
+                                        for im in inner {
+                                            match im {
+                                                Meta::NameValue(meta) if meta.path.is_ident(side) => {
+                                                    let value = &meta.value;
+                                                    found = Some(parse_quote!(#value));
+                                                }
+                                                _ => {}
+                                            }
+                                        }
+                                    }
+                                    Err(e) => panic!("{}", e.into_compile_error())
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                Err(e) => panic!("{}", e.into_compile_error())
+            }
+        }
+    }
+
+    found
+}
+
+/// `serde_compat` attribute:
+/// #[serde(default)]
+/// #[serde(default = "path")]
+/// Lets every field of a struct be left out when deserializing
+pub fn serde_default(attrs: &[Attribute]) -> bool {
+    attrs.iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .filter_map(|attr| attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated).ok())
+        .flatten()
+        .any(|meta| matches!(meta, Meta::Path(_) | Meta::NameValue(_) if meta.path().is_ident("default")))
+}
+
+/// The types serde writes and reads a container as, in place of its own fields
+#[derive(Clone, Debug)]
+pub struct Proxy {
+    pub serialize: Type,
+    /// The type read instead, when it differs
+    pub deserialize: Option<Type>,
+}
+
+/// `serde_compat` attributes:
+/// #[serde(transparent)]
+/// #[serde(into = "T")]
+/// #[serde(from = "T")]
+/// #[serde(try_from = "T")]
+/// Finds the types a container goes through serde as, so it can be described as those rather than its own fields
+/// A proxy given only one way is rejected, since the container's own shape cannot be described for just the other
+pub fn serde_proxy(attrs: &[Attribute], data: &Data) -> syn::Result<Option<Proxy>> {
+    let transparent = attrs.iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .filter_map(|attr| attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated).ok())
+        .flatten()
+        .any(|meta| matches!(&meta, Meta::Path(path) if path.is_ident("transparent")));
+
+    if transparent {
+        let Data::Struct(inner) = data else {
+            return Ok(None);
+        };
+        let mut fields = inner.fields.iter().filter(|field| !serde_skip(&field.attrs));
+        return match (fields.next(), fields.next()) {
+            (Some(field), None) => Ok(Some(Proxy {
+                serialize: field.ty.clone(),
+                deserialize: None,
+            })),
+            _ => Err(syn::Error::new_spanned(&inner.fields, "transparent structs need exactly one field that is not skipped")),
+        };
+    }
+
+    let into = serde_sided(attrs, "into", Direction::Serialize);
+    let from = serde_sided(attrs, "from", Direction::Deserialize).or_else(|| serde_sided(attrs, "try_from", Direction::Deserialize));
+
+    match (into, from) {
+        (Some(into), Some(from)) => {
+            let (serialize, deserialize) = (into.parse::<Type>()?, from.parse::<Type>()?);
+            let same = serialize.to_token_stream().to_string() == deserialize.to_token_stream().to_string();
+            Ok(Some(Proxy { serialize, deserialize: (!same).then_some(deserialize) }))
+        }
+        // the other side goes through the container's own fields, which an alias of the proxy cannot describe
+        (Some(one_way), None) | (None, Some(one_way)) => Err(syn::Error::new_spanned(
+            one_way,
+            "types serde only writes or only reads as another type cannot be exported, give both `into` and `from` or `try_from`",
+        )),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Case conventions from `#[serde(rename_all)]`, which may differ between serializing and deserializing
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RenameAll {
+    pub serialize: CaseConvention,
+    pub deserialize: CaseConvention,
+}
+
+impl RenameAll {
+    /// Gets the names a field or variant is serialized and deserialized with, the latter only when they differ
+    pub fn names(&self, attrs: &[Attribute], ident: &Ident) -> (Ident, Option<Ident>) {
+        let name = |direction, case: CaseConvention| match serde_rename_in(attrs, direction) {
+            Some(name) => Ident::new(&name, Span::call_site()),
+            None => Ident::new_raw(&case.transform(&ident.unraw().to_string()), ident.span()),
+        };
+        let ser = name(Direction::Serialize, self.serialize);
+        let de = name(Direction::Deserialize, self.deserialize);
+        let differs = ser.unraw() != de.unraw();
+        (ser, differs.then_some(de))
+    }
+
+    /// Falls back to another convention on whichever side this one leaves names untouched
+    pub fn or(self, other: RenameAll) -> RenameAll {
+        let pick = |case, other| if case == CaseConvention::Default { other } else { case };
+        RenameAll {
+            serialize: pick(self.serialize, other.serialize),
+            deserialize: pick(self.deserialize, other.deserialize),
+        }
+    }
+}
+
+/// `serde_compat` attribute:
+/// #[serde(rename_all = "case")]
+/// #[serde(rename_all(serialize = "case", deserialize = "case"))]
+/// Controls the case convention for fields or variants
+pub fn serde_rename_all(attrs: &[Attribute]) -> RenameAll {
+    serde_case(attrs, "rename_all")
+}
+
+/// `serde_compat` attribute:
+/// #[serde(rename_all_fields = "case")]
+/// #[serde(rename_all_fields(serialize = "case", deserialize = "case"))]
+/// Controls the case convention for the fields of every struct variant, unless a variant has its own `rename_all`
+pub fn serde_rename_all_fields(attrs: &[Attribute]) -> RenameAll {
+    serde_case(attrs, "rename_all_fields")
+}
+
+fn serde_case(attrs: &[Attribute], key: &str) -> RenameAll {
+    let case = |direction| serde_sided(attrs, key, direction)
+        .map_or(CaseConvention::Default, |case| CaseConvention::parse(&case.value()));
+
+    RenameAll {
+        serialize: case(Direction::Serialize),
+        deserialize: case(Direction::Deserialize),
+    }
+}
+
+/// `serde_compat` attribute:
+/// #[serde(alias = "name")]
+/// Gets the other names a field or variant is read with
+pub fn serde_aliases(attrs: &[Attribute]) -> Vec<Ident> {
+    attrs.iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .filter_map(|attr| attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated).ok())
+        .flatten()
+        .filter_map(|meta| match meta {
+            Meta::NameValue(meta) if meta.path.is_ident("alias") => match meta.value {
+                Expr::Lit(syn::ExprLit { lit: Lit::Str(name), .. }) => Some(Ident::new(&name.value(), name.span())),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// `serde_compat` attribute:
+/// #[serde(other)]
+/// Marks the unit variant that any unknown variant is read as
+pub fn serde_other(attrs: &[Attribute]) -> bool {
+    attrs.iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .filter_map(|attr| attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated).ok())
+        .flatten()
+        .any(|meta| matches!(&meta, Meta::Path(path) if path.is_ident("other")))
+}
+
+/// `serde_compat` attributes:
+/// #[serde(tag = "type")]
+/// #[serde(tag = "t", content = "c")]
+/// #[serde(untagged)]
+/// Controls the representation of an enum
+pub fn serde_enum_repr(attrs: &[Attribute]) -> EnumRepr {
+    let mut tag = None::<LitStr>;
+    let mut content = None::<LitStr>;
+
+    for attr in attrs {
+        if attr.path().is_ident("serde") {
+            match attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) {
+                Ok(items) => {
+                    for meta in items {
+                        match meta {
+                            Meta::Path(meta) if meta.is_ident("untagged") => {
+                                return EnumRepr::Untagged;
+                            }
+                            Meta::NameValue(meta) if meta.path.is_ident("tag") => {
+                                // pull the T from "T"
+                                let value = &meta.value;
+                                tag = Some(parse_quote!(#value));
+                            }
+                            Meta::NameValue(meta) if meta.path.is_ident("content") => {
+                                // pull the T from "T"
+                                let value = &meta.value;
+                                content = Some(parse_quote!(#value));
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                Err(e) => panic!("{}", e.into_compile_error())
+            }
+        }
+    }
+
+    match (&tag, &content) {
+        (Some(tag), Some(content)) => {
+            EnumRepr::Adjacent {
+                tag: leak_str(tag.value()),
+                content: leak_str(content.value()),
+            }
+        }
+        (Some(tag), None) => {
+            EnumRepr::Internal(leak_str(tag.value()))
+        }
+        (None, None) => {
+            EnumRepr::External
+        }
+        _ => panic!("invalid #[serde(tag = \"{:?}\", content = \"{:?}\")]", &tag, &content),
+    }
+}
+
+/// `serde_compat` attribute:
+/// #[serde(tag = "type")]
+/// Adds a field holding the struct's name, which serde writes but never looks at when reading
+pub fn serde_struct_tag(attrs: &[Attribute], name: &Ident) -> Option<IrStructTag> {
+    match serde_enum_repr(attrs) {
+        EnumRepr::Internal(field) => Some(IrStructTag {
+            field,
+            value: leak_str(serde_rename(attrs).unwrap_or_else(|| name.unraw().to_string())),
+        }),
+        _ => None,
+    }
+}
+
+/// #[burr(repr = "int")]
+/// #[derive(Serialize_repr)]
+/// Controls the representation of an enum, where being serialized as an integer wins over any `#[serde(tag = "...")]`
+pub fn enum_repr(attrs: &[Attribute]) -> syn::Result<EnumRepr> {
+    for attr in attrs {
+        if attr.path().is_ident("derive") {
+            let derives = attr.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)?;
+            let is_repr = |path: &syn::Path| path.segments.last()
+                .is_some_and(|seg| seg.ident == "Serialize_repr" || seg.ident == "Deserialize_repr");
+            if derives.iter().any(is_repr) {
+                return Ok(EnumRepr::Int);
+            }
+        }
+        else if attr.path().is_ident("burr") {
+            for meta in attr.parse_args_with(Punctuated::<BurrMeta, Token![,]>::parse_terminated)? {
+                match meta {
+                    BurrMeta::KeywordValue(meta) if meta.path == "repr" => {
+                        let value = &meta.value;
+                        let repr: LitStr = syn::parse2(quote::quote!(#value))?;
+                        match repr.value().as_str() {
+                            "int" => return Ok(EnumRepr::Int),
+                            _ => return Err(syn::Error::new_spanned(repr, "expected `repr = \"int\"`")),
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    Ok(serde_enum_repr(attrs))
+}
+
+/// #[burr(enum_style = "T")]
+/// Controls how an enum without fields is written, overriding the target's choice
+pub fn enum_style(attrs: &[Attribute]) -> syn::Result<Option<EnumStyle>> {
+    for attr in attrs {
+        if attr.path().is_ident("burr") {
+            for meta in attr.parse_args_with(Punctuated::<BurrMeta, Token![,]>::parse_terminated)? {
+                match meta {
+                    BurrMeta::KeywordValue(meta) if meta.path == "enum_style" => {
+                        let value = &meta.value;
+                        let style: LitStr = syn::parse2(quote::quote!(#value))?;
+                        return match EnumStyle::parse(&style.value()) {
+                            Some(style) => Ok(Some(style)),
+                            None => Err(syn::Error::new_spanned(style, "expected one of `union`, `enum`, `const_enum`, or `union_with_values`")),
+                        };
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// `serde_compat` attribute:
+/// #[serde(skip)]
+/// Removes a variant from the output type
+pub fn serde_skip(attrs: &[Attribute]) -> bool {
+    let (ser, de) = serde_skips(attrs);
+    ser && de
+}
+
+/// `serde_compat` attributes:
+/// #[serde(skip_serializing)]
+/// #[serde(skip_deserializing)]
+/// Gets the way a variant is left out of, when it is only skipped one way
+pub fn serde_skip_in(attrs: &[Attribute]) -> Option<Direction> {
+    match serde_skips(attrs) {
+        (true, false) => Some(Direction::Serialize),
+        (false, true) => Some(Direction::Deserialize),
+        _ => None,
+    }
+}
+
+/// Whether something is skipped when serializing and deserializing
+fn serde_skips(attrs: &[Attribute]) -> (bool, bool) {
+    let (mut ser, mut de) = (false, false);
+    for attr in attrs {
+        if attr.path().is_ident("serde") {
+            if let Ok(items) = attr.parse_args_with(Punctuated::<BurrMeta, Token![,]>::parse_terminated) {
+                for meta in items {
+                    match meta {
+                        BurrMeta::Path(path) if path.is_ident("skip") => {
+                            return (true, true)
+                        }
+                        BurrMeta::Path(path) if path.is_ident("skip_serializing") => ser = true,
+                        BurrMeta::Path(path) if path.is_ident("skip_deserializing") => de = true,
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    (ser, de)
+}
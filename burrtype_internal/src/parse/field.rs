@@ -0,0 +1,517 @@
+use super::{BurrMeta, TypeValue};
+use crate::ir::{Direction, IntStyle, IrLiterals, leak_str};
+use quote::ToTokens;
+use syn::ext::IdentExt;
+use syn::{Expr, ExprLit, Field, GenericArgument, Lit, parse_quote, PathArguments, Token, Type};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+
+pub struct FlaggedField {
+    /// prevents this field from appearing in export
+    pub ignore: bool,
+    /// substitute type's members
+    pub flatten: bool,
+    /// may be missing when deserializing
+    pub default: bool,
+    /// may be missing when serializing
+    pub skip_serializing_if: bool,
+    /// left out when going one way only
+    pub skip: Option<Direction>,
+    /// overrides type
+    pub ty: Option<Type>,
+    /// overrides type when deserializing, when it differs
+    pub de_ty: Option<Type>,
+    /// target language types written as is
+    pub literals: IrLiterals,
+    /// how wide integers are written, over the target's choice
+    pub int_style: Option<IntStyle>,
+    /// original data
+    pub field: Field,
+}
+
+/// Tries to parse a `T` out of `Option<T>` types
+pub fn option(ty: Type) -> (Type, bool) {
+    if let Type::Path(path) = &ty {
+        // We don't care about the path to `Option`, only that the root type is `Option`
+        if let Some(last) = path.path.segments.last() {
+            if last.ident == "Option" {
+                // `Option<T>` has exactly one parameter, and it's a Type
+                if let PathArguments::AngleBracketed(args) = &last.arguments {
+                    if let GenericArgument::Type(ty) = args.args.first().expect("Option should contain exactly one argument") {
+                        return (ty.clone(), true)
+                    }
+                }
+            }
+        }
+    }
+
+    (ty, false)
+}
+
+/// Tries to parse a `T` out of `Vec<T>` types
+pub fn vec(ty: Type) -> (Type, bool) {
+    if let Type::Path(path) = &ty {
+        // We don't care about the path to `Option`, only that the root type is `Option`
+        if let Some(last) = path.path.segments.last() {
+            if last.ident == "Vec" {
+                // `Vec<T>` has exactly one parameter, and it's a Type
+                if let PathArguments::AngleBracketed(args) = &last.arguments {
+                    if let GenericArgument::Type(ty) = args.args.first().expect("Vec should contain exactly one argument") {
+                        return (ty.clone(), true)
+                    }
+                }
+            }
+        }
+    }
+
+    (ty, false)
+}
+
+/// Checks for map types, which serde writes as objects with a key for each entry
+pub fn map(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path.path.segments.last()
+            .is_some_and(|last| matches!(last.ident.to_string().as_str(), "HashMap" | "BTreeMap" | "IndexMap" | "Map")),
+        _ => false,
+    }
+}
+
+/// Serde helper modules that change how a field is written, along with the type it is written as instead
+/// Helpers missing here can be given the type they write with `#[burr(with_type = T)]`
+const SERDE_HELPERS: &[(&str, &str)] = &[
+    ("chrono::serde::ts_seconds", "i64"),
+    ("chrono::serde::ts_seconds_option", "i64"),
+    ("chrono::serde::ts_milliseconds", "i64"),
+    ("chrono::serde::ts_milliseconds_option", "i64"),
+    ("chrono::serde::ts_microseconds", "i64"),
+    ("chrono::serde::ts_microseconds_option", "i64"),
+    ("chrono::serde::ts_nanoseconds", "i64"),
+    ("chrono::serde::ts_nanoseconds_option", "i64"),
+    ("humantime_serde", "String"),
+    ("humantime_serde::option", "String"),
+    ("time::serde::rfc3339", "String"),
+    ("time::serde::rfc3339::option", "String"),
+    ("time::serde::rfc2822", "String"),
+    ("time::serde::rfc2822::option", "String"),
+    ("time::serde::iso8601", "String"),
+    ("time::serde::iso8601::option", "String"),
+    ("time::serde::timestamp", "i64"),
+    ("time::serde::timestamp::option", "i64"),
+    ("serde_with::rust::display_fromstr", "String"),
+];
+
+/// Types from `serde_with` used with `#[serde_as(as = "...")]`, along with the type they write a value as
+const SERDE_AS_TYPES: &[(&str, &str)] = &[
+    ("serde_with::DisplayFromStr", "String"),
+    ("serde_with::TimestampSeconds", "i64"),
+    ("serde_with::TimestampMilliSeconds", "i64"),
+    ("serde_with::TimestampMicroSeconds", "i64"),
+    ("serde_with::TimestampNanoSeconds", "i64"),
+    ("serde_with::DurationSeconds", "u64"),
+    ("serde_with::DurationMilliSeconds", "u64"),
+    ("serde_with::DurationMicroSeconds", "u64"),
+    ("serde_with::DurationNanoSeconds", "u64"),
+    ("serde_with::hex::Hex", "String"),
+    ("serde_with::base64::Base64", "String"),
+];
+
+/// Looks up a path in one of the helper tables
+/// A path with more than one segment has to be a helper's full path, such as `chrono::serde::ts_seconds`
+/// A bare name, such as `ts_seconds`, finds the helper ending in it only when no other helper does
+/// Either way a module of your own can share a name with one of these, so give `#[burr(with_type = T)]` when it writes something else
+fn lookup(table: &[(&str, &'static str)], path: &str) -> Option<Type> {
+    let path = path.trim_start_matches("::");
+    let mut found = table.iter().filter(|(helper, _)| match path.contains("::") {
+        true => *helper == path,
+        false => helper.rsplit("::").next() == Some(path),
+    });
+    match (found.next(), found.next()) {
+        (Some((_, wire)), None) => syn::parse_str(wire).ok(),
+        _ => None,
+    }
+}
+
+/// Gets the type a field is written as when it goes through a serde helper, if the helper is a known one
+/// Helpers given as functions, such as `ts_seconds::serialize`, are looked up by their module
+/// The field keeps its `Option` and `Vec`, since module helpers are picked to suit them
+pub fn serde_helper_type(helper: &str, field: &Type) -> Option<Type> {
+    let helper = helper.trim();
+    let helper = helper.strip_suffix("::serialize")
+        .or_else(|| helper.strip_suffix("::deserialize"))
+        .unwrap_or(helper);
+
+    // `#[serde_as]` turns `#[serde_as(as = "T")]` into `#[serde(with = "::serde_with::As::<T>")]`
+    if let Some(start) = helper.find("As::<") {
+        let inner = helper[start + 5..].strip_suffix('>')?;
+        return serde_as_type(&syn::parse_str(inner).ok()?);
+    }
+
+    Some(like_field(lookup(SERDE_HELPERS, helper)?, field))
+}
+
+/// Wraps the type a module helper writes in the field's own `Option` and `Vec`, since such helpers are picked to suit them
+fn like_field(wire: Type, field: &Type) -> Type {
+    let (ty, optional) = option(field.clone());
+    let (_, array) = vec(ty);
+    let wire: Type = if array { parse_quote!(Vec<#wire>) } else { wire };
+    if optional { parse_quote!(Option<#wire>) } else { wire }
+}
+
+/// Gets the type a field is written as from the type given to `#[serde_as(as = "...")]`
+/// `Option` and `Vec` are written out in the `as` type the same as the field, so they are kept from there
+pub fn serde_as_type(ty: &Type) -> Option<Type> {
+    let (ty, optional) = option(ty.clone());
+    let (ty, array) = vec(ty);
+    let Type::Path(path) = &ty else {
+        return None;
+    };
+    let path = path.path.segments.iter().map(|segment| segment.ident.to_string()).collect::<Vec<_>>().join("::");
+    let wire = lookup(SERDE_AS_TYPES, &path)?;
+    let wire: Type = if array { parse_quote!(Vec<#wire>) } else { wire };
+    Some(if optional { parse_quote!(Option<#wire>) } else { wire })
+}
+
+/// The types a field is written and read as through serde helpers, when a known helper is used either way
+#[derive(Default)]
+struct SerdeHelpers {
+    serialize: Option<Type>,
+    deserialize: Option<Type>,
+    /// Whether any helper is used when writing and reading, known or not
+    used: (bool, bool),
+    /// The type given with `#[burr(with_type = T)]`, along with where it was given
+    given: Option<(Type, Expr)>,
+}
+
+impl SerdeHelpers {
+    /// Picks up `with`, `serialize_with` or `deserialize_with` from `#[serde]`, or `as` from `#[serde_as]`
+    #[cfg(feature = "serde_compat")]
+    fn parse(&mut self, meta: &TypeValue, field: &Type) -> syn::Result<()> {
+        let key = meta.path.to_string();
+        if !matches!(key.as_str(), "with" | "serialize_with" | "deserialize_with" | "as") {
+            return Ok(());
+        }
+        let Some(helper) = string(&meta.value) else {
+            return Err(syn::Error::new_spanned(&meta.value, "expected a string"));
+        };
+        let wire = match key.as_str() {
+            "as" => serde_as_type(&syn::parse_str(&helper)?),
+            _ => serde_helper_type(&helper, field),
+        };
+        match key.as_str() {
+            "serialize_with" => {
+                self.serialize = wire;
+                self.used.0 = true;
+            }
+            "deserialize_with" => {
+                self.deserialize = wire;
+                self.used.1 = true;
+            }
+            _ => {
+                self.serialize = wire.clone();
+                self.deserialize = wire;
+                self.used = (true, true);
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the type a helper writes from `with_type = T`, or `with_type = "T"` for types that are not paths
+    fn parse_given(&mut self, meta: &TypeValue) -> syn::Result<()> {
+        let value = &meta.value;
+        let ty = match string(value) {
+            Some(ty) => syn::parse_str(&ty).map_err(|e| syn::Error::new_spanned(value, e))?,
+            None => parse_quote!(#value),
+        };
+        self.given = Some((ty, value.clone()));
+        Ok(())
+    }
+
+    /// Gets the type a field is written as, along with the type it is read as when that differs
+    /// A type given with `#[burr(with_type = T)]` stands in for whatever the helpers on the field were taken to write
+    fn types(mut self, field: &Type) -> syn::Result<(Option<Type>, Option<Type>)> {
+        if let Some((given, value)) = self.given.take() {
+            // without `serde_compat` helpers are never read, so there is nothing to check against
+            if cfg!(feature = "serde_compat") && self.used == (false, false) {
+                return Err(syn::Error::new_spanned(value, "`with_type` names the type a serde helper writes, but this field uses none"));
+            }
+            let given = like_field(given, field);
+            if self.used.0 {
+                self.serialize = Some(given.clone());
+            }
+            if self.used.1 {
+                self.deserialize = Some(given);
+            }
+        }
+
+        if self.serialize.is_none() && self.deserialize.is_none() {
+            return Ok((None, None));
+        }
+        let serialize = self.serialize.unwrap_or_else(|| field.clone());
+        let deserialize = self.deserialize.unwrap_or_else(|| field.clone());
+        let same = serialize.to_token_stream().to_string() == deserialize.to_token_stream().to_string();
+        Ok((Some(serialize), (!same).then_some(deserialize)))
+    }
+}
+
+/// Targets given a literal type with `#[burr(name = "...")]`, while any target can be given one with `#[burr(target(name = "..."))]`
+const LITERAL_TARGETS: &[&str] = &["ts"];
+
+/// Reads the type a target writes as is from `name = "..."`, replacing any given before
+fn target_literal(literals: &mut IrLiterals, meta: &TypeValue) -> syn::Result<()> {
+    let Some(ty) = string(&meta.value) else {
+        return Err(syn::Error::new_spanned(&meta.value, "expected a string"));
+    };
+    let target = meta.path.unraw().to_string();
+    literals.targets.retain(|(name, _)| *name != target);
+    literals.targets.push((leak_str(target), leak_str(ty)));
+    Ok(())
+}
+
+/// Reads how wide integers are written from `int_style = "..."`
+fn int_style(meta: &TypeValue) -> syn::Result<IntStyle> {
+    string(&meta.value)
+        .and_then(|style| IntStyle::parse(&style))
+        .ok_or_else(|| syn::Error::new_spanned(&meta.value, "expected one of `number`, `bigint`, or `string`"))
+}
+
+/// Pulls the text out of a string literal
+fn string(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(ExprLit { lit: Lit::Str(lit), .. }) => Some(lit.value()),
+        _ => None,
+    }
+}
+
+/// Named fields can have the following attributes:
+/// #[burr(flatten)]
+/// #[burr(ignore)]
+/// #[burr(type = T)]
+/// #[burr(type = "literal")]
+/// #[burr(ts = "literal")]
+/// #[burr(target(ts = "literal", ...))]
+/// #[burr(int_style = "number" | "bigint" | "string")]
+/// #[burr(with_type = T)]
+/// As well as the `serde_compat` attributes `skip`, `skip_serializing`, `skip_deserializing`, `flatten`, `default`, `skip_serializing_if`,
+/// `with`, `serialize_with`, `deserialize_with` and `#[serde_as(as = "T")]`
+pub fn named_field_attrs(field: &Field) -> syn::Result<FlaggedField> {
+    let mut ignore = false;
+    let mut flatten = false;
+    // only serde sets these
+    #[cfg_attr(not(feature = "serde_compat"), allow(unused_mut))]
+    let mut default = false;
+    #[cfg_attr(not(feature = "serde_compat"), allow(unused_mut))]
+    let mut skip_serializing_if = false;
+    #[cfg_attr(not(feature = "serde_compat"), allow(unused_mut))]
+    let (mut skip_ser, mut skip_de) = (false, false);
+    #[cfg_attr(not(feature = "serde_compat"), allow(unused_mut))]
+    let mut helpers = SerdeHelpers::default();
+    let mut ty = None;
+    let mut literals = IrLiterals::default();
+    let mut style = None;
+
+    // parse attributes
+    for attr in &field.attrs {
+        #[cfg(feature = "serde_compat")]
+        if attr.path().is_ident("serde") || attr.path().is_ident("serde_as") {
+            match attr.parse_args_with(Punctuated::<BurrMeta, Token![,]>::parse_terminated) {
+                Ok(items) => {
+                    for meta in items {
+                        match meta {
+                            BurrMeta::Path(path) if path.is_ident("skip") => {
+                                ignore = true;
+                            }
+                            BurrMeta::Path(path) if path.is_ident("flatten") => {
+                                flatten = true;
+                            }
+                            BurrMeta::Path(path) if path.is_ident("default") => {
+                                default = true;
+                            }
+                            BurrMeta::KeywordValue(meta) if meta.path == "default" => {
+                                default = true;
+                            }
+                            BurrMeta::KeywordValue(meta) if meta.path == "skip_serializing_if" => {
+                                skip_serializing_if = true;
+                            }
+                            BurrMeta::Path(path) if path.is_ident("skip_serializing") => {
+                                skip_ser = true;
+                            }
+                            BurrMeta::Path(path) if path.is_ident("skip_deserializing") => {
+                                skip_de = true;
+                            }
+                            BurrMeta::KeywordValue(meta) => {
+                                helpers.parse(&meta, &field.ty)?;
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        if attr.path().is_ident("burr") {
+            match attr.parse_args_with(Punctuated::<BurrMeta, Token![,]>::parse_terminated) {
+                Ok(items) => {
+                    for meta in items {
+                        match meta {
+                            BurrMeta::Path(path) if path.is_ident("ignore") => {
+                                ignore = true;
+                            }
+                            BurrMeta::Path(path) if path.is_ident("flatten") => {
+                                flatten = true;
+                            }
+                            BurrMeta::KeywordValue(meta) if meta.path == "type" => {
+                                let value = &meta.value;
+                                match string(value) {
+                                    Some(value) => literals.any = Some(leak_str(value)),
+                                    None => ty = Some(parse_quote!(#value)),
+                                }
+                            }
+                            BurrMeta::KeywordValue(meta) if meta.path == "int_style" => {
+                                style = Some(int_style(&meta)?);
+                            }
+                            BurrMeta::KeywordValue(meta) if meta.path == "with_type" => {
+                                helpers.parse_given(&meta)?;
+                            }
+                            BurrMeta::KeywordValue(meta) if LITERAL_TARGETS.iter().any(|target| meta.path == target) => {
+                                target_literal(&mut literals, &meta)?;
+                            }
+                            BurrMeta::List(list) if list.path.is_ident("target") => {
+                                for meta in list.parse_args_with(Punctuated::<TypeValue, Token![,]>::parse_terminated)? {
+                                    target_literal(&mut literals, &meta)?;
+                                }
+                            }
+                            _ => {
+                                return Err(syn::Error::new(meta.span(), "unknown attribute"))
+                            }
+                        }
+                    }
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    let skip = match (skip_ser, skip_de) {
+        (true, false) => Some(Direction::Serialize),
+        (false, true) => Some(Direction::Deserialize),
+        _ => None,
+    };
+
+    // `#[burr(type = T)]` stands for both ways
+    let (ty, de_ty) = match ty {
+        Some(ty) => (Some(ty), None),
+        None => helpers.types(&field.ty)?,
+    };
+
+    Ok(FlaggedField {
+        // skipped both ways is the same as `#[serde(skip)]`
+        ignore: ignore || (skip_ser && skip_de),
+        flatten,
+        default,
+        skip_serializing_if,
+        skip,
+        ty,
+        de_ty,
+        literals,
+        int_style: style,
+        field: field.clone(),
+    })
+}
+
+/// Unnamed fields can have the following attributes:
+/// #[burr(ignore)]
+/// #[burr(type = T)]
+/// #[burr(type = "literal")]
+/// #[burr(ts = "literal")]
+/// #[burr(target(ts = "literal", ...))]
+/// #[burr(int_style = "number" | "bigint" | "string")]
+/// #[burr(with_type = T)]
+/// As well as the `serde_compat` attributes `skip`, `with`, `serialize_with`, `deserialize_with` and `#[serde_as(as = "T")]`
+/// Only the type a field is written as is kept, since tuples have nowhere to put a second one
+pub fn unnamed_field_attrs(field: &Field) -> syn::Result<FlaggedField> {
+    let mut ignore = false;
+    #[cfg_attr(not(feature = "serde_compat"), allow(unused_mut))]
+    let mut helpers = SerdeHelpers::default();
+    let mut ty = None;
+    let mut literals = IrLiterals::default();
+    let mut style = None;
+
+    // parse attributes
+    for attr in &field.attrs {
+        #[cfg(feature = "serde_compat")]
+        if attr.path().is_ident("serde") || attr.path().is_ident("serde_as") {
+            match attr.parse_args_with(Punctuated::<BurrMeta, Token![,]>::parse_terminated) {
+                Ok(items) => {
+                    for meta in items {
+                        match meta {
+                            BurrMeta::Path(path) if path.is_ident("skip") => {
+                                ignore = true;
+                            }
+                            BurrMeta::KeywordValue(meta) => {
+                                helpers.parse(&meta, &field.ty)?;
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        if attr.path().is_ident("burr") {
+            match attr.parse_args_with(Punctuated::<BurrMeta, Token![,]>::parse_terminated) {
+                Ok(items) => {
+                    for meta in items {
+                        match meta {
+                            BurrMeta::Path(path) if path.is_ident("ignore") => {
+                                ignore = true;
+                            }
+                            BurrMeta::KeywordValue(meta) if meta.path == "type" => {
+                                let value = &meta.value;
+                                match string(value) {
+                                    Some(value) => literals.any = Some(leak_str(value)),
+                                    None => ty = Some(parse_quote!(#value)),
+                                }
+                            }
+                            BurrMeta::KeywordValue(meta) if meta.path == "int_style" => {
+                                style = Some(int_style(&meta)?);
+                            }
+                            BurrMeta::KeywordValue(meta) if meta.path == "with_type" => {
+                                helpers.parse_given(&meta)?;
+                            }
+                            BurrMeta::KeywordValue(meta) if LITERAL_TARGETS.iter().any(|target| meta.path == target) => {
+                                target_literal(&mut literals, &meta)?;
+                            }
+                            BurrMeta::List(list) if list.path.is_ident("target") => {
+                                for meta in list.parse_args_with(Punctuated::<TypeValue, Token![,]>::parse_terminated)? {
+                                    target_literal(&mut literals, &meta)?;
+                                }
+                            }
+                            _ => {
+                                return Err(syn::Error::new(meta.span(), "unknown attribute"))
+                            }
+                        }
+                    }
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    let ty = match ty {
+        Some(ty) => Some(ty),
+        None => helpers.types(&field.ty)?.0,
+    };
+
+    Ok(FlaggedField {
+        ignore,
+        flatten: false,
+        default: false,
+        skip_serializing_if: false,
+        skip: None,
+        ty,
+        de_ty: None,
+        literals,
+        int_style: style,
+        field: field.clone(),
+    })
+}
\ No newline at end of file
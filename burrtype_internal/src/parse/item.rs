@@ -0,0 +1,337 @@
+use super::*;
+use crate::ir::*;
+use proc_macro2::{Ident, Span};
+use syn::{Data, DataEnum, DeriveInput, Expr, ExprLit, ExprUnary, Fields, FieldsNamed, FieldsUnnamed, ItemType, Lit, Type, TypePath, UnOp};
+
+/// Answers questions about types while building IR straight from syntax
+/// The derive macro leaves these to the compiler, but we have to work them out ourselves
+pub trait TypeResolver {
+    /// Gets the key for a type as written in a field
+    fn resolve(&mut self, ty: &TypePath) -> TypeKey;
+    /// Gets what a named struct or enum adds to a struct it is flattened into with `#[serde(flatten)]`
+    fn flatten(&mut self, ty: &TypePath) -> syn::Result<IrFlatten>;
+}
+
+/// Builds the same IR the derive macro would generate for an item
+pub fn item_ir<R: TypeResolver>(input: &DeriveInput, id: TypeKey, path: &'static str, resolver: &mut R) -> syn::Result<IrItem> {
+    let DeriveInput { attrs, ident: name, data, .. } = input;
+
+    let ident = rename(attrs, name);
+    let module = burr_mod(attrs).map(leak_str);
+
+    if let Some(proxy) = serde_proxy(attrs, data)? {
+        let mut ir_type = |ty: &Type| proxy_type(ty).map(|(path, optional, array)| IrType {
+            id: resolver.resolve(&path),
+            path,
+            optional,
+            array,
+        });
+
+        return Ok(IrAlias {
+            ident,
+            id,
+            path,
+            ty: ir_type(&proxy.serialize)?,
+            de_ty: proxy.deserialize.as_ref().map(&mut ir_type).transpose()?,
+            target: id,
+            r#mod: module,
+            #[cfg(feature = "docs")]
+            docs: docs(attrs).map(leak_str),
+        }.into());
+    }
+
+    Ok(match data {
+        Data::Struct(inner) => match &inner.fields {
+            Fields::Named(fields) => {
+                let IrFlatten { fields, flattened } = named_fields_ir(fields, serde_rename_all(attrs), serde_default(attrs), resolver)?;
+                IrNamedStruct {
+                    ident,
+                    id,
+                    path,
+                    fields,
+                    flattened,
+                    tag: serde_struct_tag(attrs, name),
+                    r#mod: module,
+                    #[cfg(feature = "docs")]
+                    docs: docs(attrs).map(leak_str),
+                }.into()
+            }
+            Fields::Unnamed(fields) => IrTupleStruct {
+                ident,
+                id,
+                path,
+                fields: unnamed_fields_ir(fields, resolver, "Option types unsupported for tuple structs")?,
+                r#mod: module,
+                #[cfg(feature = "docs")]
+                docs: docs(attrs).map(leak_str),
+            }.into(),
+            Fields::Unit => IrUnitStruct {
+                ident,
+                id,
+                path,
+                r#mod: module,
+                #[cfg(feature = "docs")]
+                docs: docs(attrs).map(leak_str),
+            }.into(),
+        },
+        Data::Enum(inner) => {
+            let rename_all = serde_rename_all(attrs);
+            let rename_all_fields = serde_rename_all_fields(attrs);
+            let repr = enum_repr(attrs)?;
+            let mut variants = Vec::new();
+
+            // Discriminants only decide how an enum is serialized when none of its variants have fields
+            let with_fields = inner.variants.iter().find(|var| !matches!(var.fields, Fields::Unit));
+            let discriminants = match (repr, with_fields) {
+                (EnumRepr::Int, Some(var)) => return Err(syn::Error::new_spanned(var, "only enums without fields can be represented as integers")),
+                (_, Some(_)) => Vec::new(),
+                (_, None) => discriminants(inner)?,
+            };
+
+            for (n, var) in inner.variants.iter().enumerate() {
+                if serde_skip(&var.attrs) {
+                    continue;
+                }
+
+                let (ident, de_ident) = rename_all.names(&var.attrs, &var.ident);
+                let aliases = serde_aliases(&var.attrs);
+                let skip = serde_skip_in(&var.attrs);
+
+                variants.push(match &var.fields {
+                    Fields::Named(fields) => {
+                        let IrFlatten { fields, flattened } = named_fields_ir(fields, serde_rename_all(&var.attrs).or(rename_all_fields), false, resolver)?;
+                        IrEnumStructVariant {
+                            ident,
+                            de_ident,
+                            aliases,
+                            skip,
+                            fields,
+                            flattened,
+                            #[cfg(feature = "docs")]
+                            docs: docs(&var.attrs).map(leak_str),
+                        }.into()
+                    }
+                    Fields::Unnamed(fields) => IrEnumTupleVariant {
+                        ident,
+                        de_ident,
+                        aliases,
+                        skip,
+                        fields: unnamed_fields_ir(fields, resolver, "Option types unsupported for tuple variants")?,
+                        #[cfg(feature = "docs")]
+                        docs: docs(&var.attrs).map(leak_str),
+                    }.into(),
+                    Fields::Unit => IrEnumUnitVariant {
+                        ident,
+                        de_ident,
+                        aliases,
+                        skip,
+                        discriminant: discriminants.get(n).copied(),
+                        other: serde_other(&var.attrs),
+                        #[cfg(feature = "docs")]
+                        docs: docs(&var.attrs).map(leak_str),
+                    }.into(),
+                });
+            }
+
+            IrEnum {
+                ident,
+                id,
+                path,
+                variants,
+                repr,
+                style: enum_style(attrs)?,
+                r#mod: module,
+                #[cfg(feature = "docs")]
+                docs: docs(attrs).map(leak_str),
+            }.into()
+        }
+        Data::Union(_) => return Err(syn::Error::new_spanned(name, "unions are unsupported")),
+    })
+}
+
+/// Works out the discriminant of each variant the way the compiler would for an enum without fields
+/// The derive macro reads them from the compiled enum, but here only integer literals can be evaluated
+fn discriminants(data: &DataEnum) -> syn::Result<Vec<i128>> {
+    let mut next = Some(0i128);
+    data.variants.iter()
+        .map(|var| {
+            if let Some((_, expr)) = &var.discriminant {
+                next = Some(int_literal(expr)?);
+            }
+            let value = next.ok_or_else(|| syn::Error::new_spanned(var, "discriminants above `i128::MAX` cannot be exported"))?;
+            next = value.checked_add(1);
+            Ok(value)
+        })
+        .collect()
+}
+
+fn int_literal(expr: &Expr) -> syn::Result<i128> {
+    match expr {
+        Expr::Lit(ExprLit { lit: Lit::Int(lit), .. }) => lit.base10_parse()
+            .map_err(|_| syn::Error::new_spanned(lit, "discriminants above `i128::MAX` cannot be exported")),
+        Expr::Unary(ExprUnary { op: UnOp::Neg(_), expr, .. }) => int_literal(expr).map(|value| -value),
+        Expr::Paren(inner) => int_literal(&inner.expr),
+        Expr::Group(inner) => int_literal(&inner.expr),
+        _ => Err(syn::Error::new_spanned(expr, "discriminants must be integer literals to be read from source")),
+    }
+}
+
+/// Builds the IR for the fields of a named struct or struct variant
+/// `default` is set by `#[serde(default)]` on a struct, which serde does not allow for enums
+pub fn named_fields_ir<R: TypeResolver>(fields: &FieldsNamed, rename_all: RenameAll, default: bool, resolver: &mut R) -> syn::Result<IrFlatten> {
+    let mut out = IrFlatten::default();
+
+    for field in &fields.named {
+        let ir = named_field_attrs(field)?;
+        if ir.ignore {
+            continue;
+        }
+
+        let ty = ir.ty.as_ref().unwrap_or(&ir.field.ty);
+        if ir.flatten {
+            let (ty, optional) = option(ty.clone());
+            let flatten = match map(&ty) {
+                true => IrFlatten { fields: Vec::new(), flattened: vec![IrFlattened::Map] },
+                false => resolver.flatten(&type_path(&ty)?)?,
+            };
+            let flatten = flatten.with_default(default || ir.default).with_optional(optional);
+            out.fields.extend(flatten.fields);
+            out.flattened.extend(flatten.flattened);
+            continue;
+        }
+
+        let mut ir_type = |ty: &Type| proxy_type(ty).map(|(path, optional, array)| IrType {
+            id: resolver.resolve(&path),
+            path,
+            optional,
+            array,
+        });
+        let (ident, de_ident) = rename_all.names(&field.attrs, field.ident.as_ref().unwrap());
+
+        out.fields.push(IrNamedField {
+            ident,
+            de_ident,
+            aliases: serde_aliases(&field.attrs),
+            ty: ir_type(ty)?,
+            de_ty: ir.de_ty.as_ref().map(&mut ir_type).transpose()?,
+            literals: ir.literals,
+            int_style: ir.int_style,
+            skip: ir.skip,
+            default: default || ir.default,
+            skip_serializing_if: ir.skip_serializing_if,
+            #[cfg(feature = "docs")]
+            docs: docs(&field.attrs).map(leak_str),
+        });
+    }
+
+    Ok(out)
+}
+
+/// Builds the IR for the fields of a tuple struct or tuple variant
+fn unnamed_fields_ir<R: TypeResolver>(fields: &FieldsUnnamed, resolver: &mut R, optional_error: &str) -> syn::Result<Vec<IrUnnamedField>> {
+    let mut out = Vec::new();
+
+    for field in &fields.unnamed {
+        let ir = unnamed_field_attrs(field)?;
+        if ir.ignore {
+            continue;
+        }
+
+        let ty = ir.ty.as_ref().unwrap_or(&ir.field.ty);
+        let (ty, optional) = option(ty.clone());
+        let (ty, array) = vec(ty);
+
+        if optional {
+            return Err(syn::Error::new_spanned(field, optional_error));
+        }
+
+        let path = type_path(&ty)?;
+        out.push(IrUnnamedField {
+            ty: IrType {
+                id: resolver.resolve(&path),
+                path,
+                optional,
+                array,
+            },
+            literals: ir.literals,
+            int_style: ir.int_style,
+            #[cfg(feature = "docs")]
+            docs: docs(&field.attrs).map(leak_str),
+        });
+    }
+
+    Ok(out)
+}
+
+/// Applies `#[serde(rename = "T")]` to an identifier
+fn rename(attrs: &[syn::Attribute], default: &Ident) -> Ident {
+    serde_rename(attrs).map_or_else(|| default.clone(), |name| Ident::new(&name, Span::call_site()))
+}
+
+/// Only plain paths can be described by the IR, the same restriction the derive macro has
+pub fn type_path(ty: &Type) -> syn::Result<TypePath> {
+    match ty {
+        Type::Path(path) => Ok(path.clone()),
+        _ => Err(syn::Error::new_spanned(ty, "only path types are supported")),
+    }
+}
+
+/// Gets the type a constant is described by, along with whether it is an array
+/// Constants are often borrowed, such as `&str` or `&[u32]`, so references and slices are looked through
+pub fn const_type(ty: &Type) -> syn::Result<(TypePath, bool)> {
+    fn deref(ty: &Type) -> &Type {
+        match ty {
+            Type::Reference(inner) => deref(&inner.elem),
+            Type::Paren(inner) => deref(&inner.elem),
+            _ => ty,
+        }
+    }
+
+    match deref(ty) {
+        Type::Slice(inner) => Ok((type_path(deref(&inner.elem))?, true)),
+        Type::Array(inner) => Ok((type_path(deref(&inner.elem))?, true)),
+        ty => {
+            let (ty, array) = vec(ty.clone());
+            Ok((type_path(&ty)?, array))
+        }
+    }
+}
+
+/// Gets the type an alias stands for, along with whether it is optional and whether it is an array
+pub fn alias_type(item: &ItemType) -> syn::Result<(TypePath, bool, bool)> {
+    if !item.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&item.generics, "generic aliases are unsupported"));
+    }
+
+    proxy_type(&item.ty)
+}
+
+/// Gets the type a container goes through serde as, along with whether it is optional and whether it is an array
+pub fn proxy_type(ty: &Type) -> syn::Result<(TypePath, bool, bool)> {
+    let (ty, optional) = option(ty.clone());
+    let (ty, array) = vec(ty);
+    Ok((type_path(&ty)?, optional, array))
+}
+
+/// Builds the same IR `#[burr]` would generate for a type alias
+/// Fields written with the alias are expected to resolve to `id`, since we cannot see through the alias like the compiler does
+pub fn alias_ir<R: TypeResolver>(item: &ItemType, id: TypeKey, path: &'static str, resolver: &mut R) -> syn::Result<IrItem> {
+    let (ty, optional, array) = alias_type(item)?;
+
+    Ok(IrAlias {
+        ident: item.ident.clone(),
+        id,
+        path,
+        ty: IrType {
+            id: resolver.resolve(&ty),
+            path: ty,
+            optional,
+            array,
+        },
+        de_ty: None,
+        target: id,
+        r#mod: burr_mod(&item.attrs).map(leak_str),
+        #[cfg(feature = "docs")]
+        docs: docs(&item.attrs).map(leak_str),
+    }.into())
+}
@@ -0,0 +1,156 @@
+mod runner;
+mod workspace;
+
+use anyhow::{bail, Context};
+use burrtype::config::{Config, CONFIG_NAME};
+use burrtype::schema::{self, Schema, SchemaDiff};
+use burrtype::watch::Watcher;
+use clap::{Parser, Subcommand};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// Cargo invokes us as `cargo-burrtype burrtype <args>`
+#[derive(Parser)]
+#[command(name = "cargo", bin_name = "cargo")]
+enum Cli {
+    Burrtype(Args),
+}
+
+/// Exports your types as described by a `burrtype.toml`
+#[derive(clap::Args)]
+#[command(version, about)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+    /// Path to the config file, searched for upwards from the current directory by default
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+    /// Package whose types are exported
+    #[arg(short, long, global = true)]
+    package: Option<String>,
+    /// Path to Cargo.toml
+    #[arg(long, global = true)]
+    manifest_path: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Clone)]
+enum Command {
+    /// Writes every export
+    Generate {
+        /// Keeps running, exporting again whenever the package's sources or the config change
+        #[arg(long)]
+        watch: bool,
+    },
+    /// Fails if any export is out of date, without writing anything
+    Check,
+    /// Prints every registered type with the module it is assigned to
+    List,
+    /// Writes a portable JSON description of every exported type
+    Schema {
+        /// File to write to, instead of printing
+        #[arg(short, long)]
+        out: Option<PathBuf>,
+    },
+    /// Compares two schemas, failing if any change would break consumers
+    Diff {
+        /// The schema to compare against, such as one from your main branch
+        old: PathBuf,
+        /// The changed schema, which is taken from the package when left out
+        new: Option<PathBuf>,
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+}
+
+#[derive(clap::ValueEnum, Clone, Copy)]
+enum Format {
+    Text,
+    Json,
+}
+
+impl Command {
+    /// Gets the runner's name for this command, along with its arguments
+    fn runner_args(&self) -> (&'static str, Vec<OsString>) {
+        match self {
+            Command::Generate { .. } => ("generate", Vec::new()),
+            Command::Check => ("check", Vec::new()),
+            Command::List => ("list", Vec::new()),
+            Command::Schema { out } => ("schema", out.iter().map(|path| path.clone().into_os_string()).collect()),
+            Command::Diff { .. } => ("schema", Vec::new()),
+        }
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let Cli::Burrtype(args) = Cli::parse();
+
+    // Comparing two files needs nothing from the workspace
+    if let Command::Diff { old, new: Some(new), format } = &args.command {
+        let diff = schema::diff(&Schema::load(old)?, &Schema::load(new)?);
+        report_diff(&diff, *format);
+    }
+
+    let config_path = match &args.config {
+        Some(path) => path.clone(),
+        None => {
+            let start = match &args.manifest_path {
+                Some(manifest) => manifest.parent().map(Path::to_path_buf).unwrap_or_default(),
+                None => std::env::current_dir()?,
+            };
+            find_config(&start).with_context(|| format!("could not find {CONFIG_NAME} in {} or its parents", start.display()))?
+        }
+    };
+    let config_path = config_path.canonicalize().with_context(|| format!("could not open {}", config_path.display()))?;
+    let config = Config::load(&config_path)?;
+
+    let metadata = workspace::Metadata::load(args.manifest_path.as_deref(), config_path.parent().unwrap())?;
+    let package = metadata.select(args.package.as_deref().or(config.package.as_deref()), &config_path)?;
+    if package.lib().is_none() {
+        bail!("package `{}` has no library target, so its types cannot be linked into an exporter", package.name);
+    }
+
+    let runner = runner::Runner::generate(&metadata, package)?;
+
+    if let Command::Diff { old, format, .. } = &args.command {
+        let Some(current) = runner.capture("schema", &config_path)? else {
+            bail!("failed to build the current schema");
+        };
+        let diff = schema::diff(&Schema::load(old)?, &Schema::from_json(&current)?);
+        report_diff(&diff, *format);
+    }
+
+    let (command, command_args) = args.command.runner_args();
+    let code = runner.run(command, &config_path, &command_args)?;
+
+    if let Command::Generate { watch: true } = args.command {
+        let dir = package.dir().unwrap_or(Path::new("."));
+        let mut watcher = Watcher::new([dir.join("src"), package.manifest_path.clone(), config_path.clone()]);
+        println!("watching {} for changes", dir.join("src").display());
+
+        loop {
+            for path in watcher.wait()? {
+                println!("changed {}", path.display());
+            }
+            // Build failures are reported by cargo, and we keep watching for the fix
+            runner.run("generate", &config_path, &[])?;
+        }
+    }
+
+    std::process::exit(code);
+}
+
+/// Prints a diff and exits, failing if anything breaks
+fn report_diff(diff: &SchemaDiff, format: Format) -> ! {
+    match format {
+        Format::Text => println!("{diff}"),
+        Format::Json => println!("{}", diff.to_json()),
+    }
+    std::process::exit(if diff.is_breaking() { 1 } else { 0 });
+}
+
+/// Looks for a config file in the directory and each of its parents
+fn find_config(start: &Path) -> Option<PathBuf> {
+    start.ancestors()
+        .map(|dir| dir.join(CONFIG_NAME))
+        .find(|path| path.is_file())
+}
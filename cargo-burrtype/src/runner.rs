@@ -0,0 +1,119 @@
+use crate::workspace::{cargo, Metadata, Package};
+use anyhow::{bail, Context};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+/// A small binary crate linking against the user's crate, which populates the registry of types
+pub struct Runner {
+    manifest_path: PathBuf,
+    target_dir: PathBuf,
+}
+
+impl Runner {
+    /// Writes the runner crate for a package into the target directory
+    pub fn generate(metadata: &Metadata, package: &Package) -> anyhow::Result<Self> {
+        let lib = package.lib().context("package has no library target")?;
+        let Some(burrtype) = package.burrtype() else {
+            bail!("package `{}` does not depend on burrtype", package.name);
+        };
+        let Some(package_dir) = package.dir() else {
+            bail!("package `{}` has no directory", package.name);
+        };
+
+        let burrtype = match &burrtype.path {
+            Some(path) => format!("path = {}", toml_str(path)),
+            None => format!("version = {}", toml::Value::String(burrtype.req.clone())),
+        };
+
+        let dir = metadata.target_directory.join("burrtype").join(format!("runner-{}", package.name));
+        let manifest = format!(r#"# Generated by cargo-burrtype, do not edit
+[package]
+name = "burrtype-runner"
+version = "0.0.0"
+edition = "2021"
+publish = false
+
+[workspace]
+
+[dependencies]
+{name} = {{ path = {path} }}
+burrtype = {{ {burrtype}, features = ["runner"] }}
+"#,
+            name = package.name,
+            path = toml_str(package_dir),
+        );
+        let main = format!(r#"// Generated by cargo-burrtype, do not edit
+// Linking the crate is what registers its types
+extern crate {ident};
+
+fn main() -> std::process::ExitCode {{
+    burrtype::runner::main()
+}}
+"#,
+            ident = lib.name.replace('-', "_"),
+        );
+
+        write_if_changed(&dir.join("Cargo.toml"), &manifest)?;
+        write_if_changed(&dir.join("src").join("main.rs"), &main)?;
+
+        // Start from the workspace's lockfile so the exporter builds the same versions
+        if let Ok(lock) = fs::read_to_string(metadata.workspace_root.join("Cargo.lock")) {
+            write_if_changed(&dir.join("Cargo.lock"), &lock)?;
+        }
+
+        Ok(Runner {
+            manifest_path: dir.join("Cargo.toml"),
+            target_dir: metadata.target_directory.clone(),
+        })
+    }
+
+    /// Builds and runs the runner, returning its exit code
+    pub fn run(&self, command: &str, config: &Path, args: &[OsString]) -> anyhow::Result<i32> {
+        let status = cargo()
+            .args(["run", "--quiet", "--manifest-path"])
+            .arg(&self.manifest_path)
+            .env("CARGO_TARGET_DIR", &self.target_dir)
+            .arg("--")
+            .arg(command)
+            .arg(config)
+            .args(args)
+            .status()
+            .context("failed to run `cargo run`")?;
+
+        Ok(status.code().unwrap_or(1))
+    }
+
+    /// Builds and runs the runner, returning what it printed if it succeeded
+    /// Build errors and warnings still go to stderr
+    pub fn capture(&self, command: &str, config: &Path) -> anyhow::Result<Option<String>> {
+        let output = cargo()
+            .args(["run", "--quiet", "--manifest-path"])
+            .arg(&self.manifest_path)
+            .env("CARGO_TARGET_DIR", &self.target_dir)
+            .arg("--")
+            .arg(command)
+            .arg(config)
+            .stderr(Stdio::inherit())
+            .output()
+            .context("failed to run `cargo run`")?;
+
+        Ok(output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned()))
+    }
+}
+
+fn toml_str(path: &Path) -> toml::Value {
+    toml::Value::String(path.to_string_lossy().into_owned())
+}
+
+/// Avoids touching files cargo watches when nothing changed
+fn write_if_changed(path: &Path, contents: &str) -> anyhow::Result<()> {
+    if fs::read_to_string(path).is_ok_and(|existing| existing == contents) {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents).with_context(|| format!("failed to write {}", path.display()))
+}
@@ -0,0 +1,95 @@
+use anyhow::{bail, Context};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The parts of `cargo metadata` we care about
+#[derive(Deserialize)]
+pub struct Metadata {
+    pub packages: Vec<Package>,
+    pub workspace_root: PathBuf,
+    pub target_directory: PathBuf,
+}
+
+#[derive(Deserialize)]
+pub struct Package {
+    pub name: String,
+    pub manifest_path: PathBuf,
+    pub targets: Vec<Target>,
+    pub dependencies: Vec<Dependency>,
+}
+
+#[derive(Deserialize)]
+pub struct Target {
+    pub name: String,
+    pub kind: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct Dependency {
+    pub name: String,
+    pub req: String,
+    pub kind: Option<String>,
+    pub path: Option<PathBuf>,
+}
+
+/// Gets the cargo we were invoked by, so toolchain overrides carry through
+pub fn cargo() -> Command {
+    Command::new(std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into()))
+}
+
+impl Metadata {
+    /// Reads the workspace containing either the given manifest or the directory
+    pub fn load(manifest_path: Option<&Path>, dir: &Path) -> anyhow::Result<Self> {
+        let mut command = cargo();
+        command.args(["metadata", "--format-version", "1", "--no-deps"]).current_dir(dir);
+        if let Some(path) = manifest_path {
+            command.arg("--manifest-path").arg(path);
+        }
+
+        let output = command.output().context("failed to run `cargo metadata`")?;
+        if !output.status.success() {
+            bail!("`cargo metadata` failed:\n{}", String::from_utf8_lossy(&output.stderr));
+        }
+
+        serde_json::from_slice(&output.stdout).context("failed to read `cargo metadata` output")
+    }
+
+    /// Picks the package to export from
+    /// Without a name, we pick the package closest to the config file, or the only package in the workspace
+    pub fn select(&self, name: Option<&str>, config: &Path) -> anyhow::Result<&Package> {
+        if let Some(name) = name {
+            return self.packages.iter()
+                .find(|package| package.name == name)
+                .with_context(|| format!("package `{name}` is not part of the workspace"));
+        }
+
+        let closest = self.packages.iter()
+            .filter(|package| package.dir().is_some_and(|dir| config.starts_with(dir)))
+            .max_by_key(|package| package.dir().map_or(0, |dir| dir.components().count()));
+        if let Some(package) = closest {
+            return Ok(package);
+        }
+
+        match self.packages.as_slice() {
+            [package] => Ok(package),
+            _ => bail!("could not tell which package to export from, set `package` in the config or pass `--package`"),
+        }
+    }
+}
+
+impl Package {
+    pub fn dir(&self) -> Option<&Path> {
+        self.manifest_path.parent()
+    }
+
+    /// Gets the library target, which is what the exporter links against
+    pub fn lib(&self) -> Option<&Target> {
+        self.targets.iter().find(|target| target.kind.iter().any(|kind| kind == "lib" || kind == "rlib"))
+    }
+
+    /// Finds how this package depends on burrtype, so the exporter uses the same version
+    pub fn burrtype(&self) -> Option<&Dependency> {
+        self.dependencies.iter().find(|dep| dep.name == "burrtype" && dep.kind.is_none())
+    }
+}
@@ -1,15 +1,31 @@
 export type Bar = Foo
 
+export const DEFAULT_FOO = {"one":1,"two":""} as const;
+
 export interface Foo {
   one: number,
   two: string,
 }
 
+/** Identifies a `Foo` */
+export type FooId = number
+
+export interface FooRef {
+  id: FooId,
+  parent?: FooId,
+  children: FooId[],
+}
+
+/** Largest page a client may request */
+export const MAX_PAGE_SIZE = 100 as const;
+
 export type Stuff =
   | "red"
   | "two"
 ;
 
+export const STUFF = ["red", "two"] as const;
+
 export type Enum =
   | { Struct: { foo: Foo, bar: string }}
   | { TinyTuple: string }
@@ -23,12 +39,22 @@ export type Enum =
     }}
 ;
 
+export interface Listing {
+  owner: FooId,
+  price: Price,
+}
+
 export interface NamedStruct {
+  /** @precision-unsafe */
   foo: number,
   ty: number,
   opt?: Foo,
 }
 
+export type Price = number
+
+export const SUPPORTED_LOCALES = ["en","fr"] as const;
+
 export type TupleStruct = [number, Foo]
 
 export type UnitStruct = null
@@ -38,12 +64,22 @@ export interface Vecs {
   two?: number[],
 }
 
+/** @precision-unsafe */
 export type DeepTupleStruct = number
 
-export type Things =
-  | "One"
-  | "Two"
-;
+export enum Things {
+  One = "One",
+  Two = "Two",
+}
+
+/** Read and written differently, so targets splitting input and output types write this twice */
+export interface Account {
+  userName: string,
+  email: Email,
+  password?: string,
+  /** @precision-unsafe */
+  createdAt?: number,
+}
 
 /** An enum's variants correlate with struct variants */
 export type AdjacentlyTaggedEnum =
@@ -64,6 +100,16 @@ export type AdjacentlyTaggedEnum =
     }
 ;
 
+/** serde writes this as the string inside, so it is exported as an alias of it */
+export type Email = string
+
+/** A message along with anything a newer client sent that this version does not know about */
+export type Envelope = {
+  id: number,
+  volume?: number,
+  muted?: boolean,
+} & InternallyTaggedEnum & Record<string, unknown>
+
 /** An enum's variants correlate with struct variants */
 export type InternallyTaggedEnum =
   | { type: "Struct", foo: Foo, bar: string }
@@ -79,6 +125,26 @@ export type InternallyTaggedEnum =
     }
 ;
 
+/** Unknown kinds are read as `Other`, so older readers keep working as kinds are added */
+export type Notice =
+  | { kind: "Message", sentBy: string, body: string }
+  | { kind: string }
+;
+
+/** Written by a hand-rolled serializer, so the integer representation is declared explicitly */
+export const enum Opcode {
+  Ping = 1,
+  Pong = 2,
+  Close = 8,
+}
+
+export interface Page {
+  items: Foo[],
+  /** Left out on the last page */
+  next?: string,
+  tags?: string[],
+}
+
 export interface RenamedStruct {
   FOO: Stuff,
   optional?: Foo,
@@ -86,13 +152,39 @@ export interface RenamedStruct {
   two: string,
 }
 
+/** How long to wait before trying again, written as text such as "1m 30s" */
+export interface Retry {
+  after: string,
+  give_up_after?: string,
+  /** Time spent waiting so far, written the way serde writes durations by default */
+  waited: { secs: number, nanos: number },
+  /** Passed along as whatever the client sent */
+  context: Record<string, unknown>,
+  /** Where to report giving up, which is always a secure link */
+  report_to: `https://${string}`,
+}
+
+/** Anything left out is filled in from `Default`, so every field is optional */
+export interface Settings {
+  volume?: number,
+  muted?: boolean,
+}
+
+/** Outcome of a request, sent as its numeric code */
+export enum StatusCode {
+  Ok = 200,
+  /** The requested item does not exist */
+  NotFound = 404,
+  Internal = 500,
+}
+
 /** An enum's variants correlate with struct variants */
 export type UntaggedEnum =
   | { foo: Foo, bar: string }
-  /** Unit variant will be a string, but the newtype below will also capture a string
+  /** Unit variant will be `null`, since serde has nothing else to write for it
 In untagged enum representations, serde will attempt them top-to-bottom
 So we place more specific cases before general ones */
-  | "unit"
+  | null
   | string
   | [Stuff, Stuff]
   /** Bigger structs can expand to a better format */
@@ -102,3 +194,12 @@ So we place more specific cases before general ones */
       six: TupleStruct,
     }
 ;
+
+/** Carries its own name, so it can be told apart from other messages on the same channel */
+export interface Ping {
+  type: "ping",
+  /** @precision-unsafe */
+  sent_at: number,
+  /** Snowflake ID of the sender, too large for a `number`, so clients read it with a `bigint`-aware JSON parser */
+  sender: bigint,
+}
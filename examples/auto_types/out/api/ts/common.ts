@@ -1,6 +1,20 @@
 export type Bar = Foo
 
+export const DEFAULT_FOO = {"one":1,"two":""} as const;
+
 export interface Foo {
   one: number,
   two: string,
 }
+
+/** Identifies a `Foo` */
+export type FooId = number
+
+export interface FooRef {
+  id: FooId,
+  parent?: FooId,
+  children: FooId[],
+}
+
+/** Largest page a client may request */
+export const MAX_PAGE_SIZE = 100 as const;
@@ -2,3 +2,5 @@ export type Stuff =
   | "red"
   | "two"
 ;
+
+export const STUFF = ["red", "two"] as const;
@@ -1,3 +1,4 @@
+/** @precision-unsafe */
 export type DeepTupleStruct = number
 
 export type Things =
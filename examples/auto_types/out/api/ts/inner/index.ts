@@ -1,5 +1,5 @@
 import { DeepTupleStruct, Things } from './core'
-import { Foo } from '../common'
+import { Foo, FooId } from '../common'
 
 export type Enum =
   | { Struct: { foo: Foo, bar: string }}
@@ -14,12 +14,22 @@ export type Enum =
     }}
 ;
 
+export interface Listing {
+  owner: FooId,
+  price: Price,
+}
+
 export interface NamedStruct {
+  /** @precision-unsafe */
   foo: number,
   ty: number,
   opt?: Foo,
 }
 
+export type Price = number
+
+export const SUPPORTED_LOCALES = ["en","fr"] as const;
+
 export type TupleStruct = [number, Foo]
 
 export type UnitStruct = null
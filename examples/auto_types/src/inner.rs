@@ -24,6 +24,16 @@ pub struct NamedStruct {
 #[burr(mod = "inner")]
 pub struct TupleStruct(pub u32, pub Foo);
 
+#[burrtype::burr(mod = "inner")]
+pub type Price = rust_decimal::Decimal;
+
+#[derive(burrtype::Burr, serde::Serialize, serde::Deserialize, Debug)]
+#[burr(mod = "inner")]
+pub struct Listing {
+    pub owner: crate::FooId,
+    pub price: Price,
+}
+
 #[derive(burrtype::Burr, serde::Serialize, serde::Deserialize, Debug)]
 #[burr(mod = "inner")]
 pub struct UnitStruct;
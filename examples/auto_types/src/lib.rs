@@ -1,3 +1,6 @@
+// Example types favor placeholder names and variants that only exist to be exported
+#![allow(dead_code, clippy::disallowed_names)]
+
 mod inner;
 mod serde_test;
 
@@ -11,3 +14,24 @@ pub struct Foo {
 
 #[derive(Burr, serde::Serialize, serde::Deserialize, Debug)]
 pub struct Bar(pub Foo);
+
+/// Largest page a client may request
+#[burr]
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+#[burr(mod = "inner")]
+pub const SUPPORTED_LOCALES: &[&str] = &["en", "fr"];
+
+#[burr]
+pub const DEFAULT_FOO: Foo = Foo { one: 1, two: String::new() };
+
+/// Identifies a `Foo`
+#[burr]
+pub type FooId = u32;
+
+#[derive(Burr, serde::Serialize, serde::Deserialize, Debug)]
+pub struct FooRef {
+    pub id: FooId,
+    pub parent: Option<FooId>,
+    pub children: Vec<FooId>,
+}
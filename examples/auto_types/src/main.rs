@@ -1,5 +1,5 @@
 // `#[warn(unused_imports)]` false positive - this is needed to bring types into scope
-#[allow(unused_imports)]
+#[allow(unused_imports, clippy::single_component_path_imports)]
 use auto_types;
 use burrtype::prelude::*;
 
@@ -9,17 +9,22 @@ use burrtype::prelude::*;
 fn main() -> anyhow::Result<()> {
     println!("--- export ---\n");
 
-    Burrxporter::new()
+    let mut exporter = Burrxporter::new();
+    exporter
         .resolve_all("common")
-        .with_root("out/api")
-        .export("ts", TypeScript::new()
-            .with_type_name::<rust_decimal::Decimal>("number")
-        )?
-        .export("bundled.ts", TypeScript::new()
-            .with_file_map(ModFileMap::Inline)
-            .with_type_name::<rust_decimal::Decimal>("number")
-        )?
-    ;
+        .with_root("out/api");
+
+    let report = exporter.export("ts", TypeScript::new()
+        .with_type_name::<rust_decimal::Decimal>("number")
+    )?;
+    println!("{report}");
+
+    let report = exporter.export("bundled.ts", TypeScript::new()
+        .with_file_map(ModFileMap::Inline)
+        .with_enum_style(EnumStyle::Enum)
+        .with_type_name::<rust_decimal::Decimal>("number")
+    )?;
+    println!("{report}");
 
     println!("\n--- done! ---");
     Ok(())
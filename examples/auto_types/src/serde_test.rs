@@ -3,12 +3,32 @@ use crate::inner::{bar::DeepTupleStruct, TupleStruct};
 
 #[derive(burrtype::Burr, serde::Serialize, serde::Deserialize, Debug)]
 #[serde(rename_all = "lowercase")]
-#[burr(mod = "core/serde")]
+#[burr(mod = "core/serde", enum_style = "union_with_values")]
 pub enum Stuff {
     Red,
     Two,
 }
 
+/// Outcome of a request, sent as its numeric code
+#[derive(burrtype::Burr, serde_repr::Serialize_repr, serde_repr::Deserialize_repr, Debug)]
+#[burr(mod = "serde")]
+#[repr(u16)]
+pub enum StatusCode {
+    Ok = 200,
+    /// The requested item does not exist
+    NotFound = 404,
+    Internal = 500,
+}
+
+/// Written by a hand-rolled serializer, so the integer representation is declared explicitly
+#[derive(burrtype::Burr, Debug)]
+#[burr(mod = "serde", repr = "int", enum_style = "const_enum")]
+pub enum Opcode {
+    Ping = 1,
+    Pong,
+    Close = 8,
+}
+
 #[derive(burrtype::Burr, serde::Serialize, serde::Deserialize, Debug)]
 #[burr(mod = "serde")]
 #[serde(rename = "RenamedStruct")]
@@ -22,6 +42,102 @@ pub struct NamedStruct {
 }
 
 
+/// Anything left out is filled in from `Default`, so every field is optional
+#[derive(burrtype::Burr, serde::Serialize, serde::Deserialize, Debug, Default)]
+#[burr(mod = "serde")]
+#[serde(default)]
+pub struct Settings {
+    pub volume: u8,
+    pub muted: bool,
+}
+
+/// A message along with anything a newer client sent that this version does not know about
+#[derive(burrtype::Burr, serde::Serialize, serde::Deserialize, Debug)]
+#[burr(mod = "serde")]
+pub struct Envelope {
+    pub id: u32,
+    #[serde(flatten)]
+    pub message: InternallyTaggedEnum,
+    #[serde(flatten)]
+    pub settings: Option<Settings>,
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+#[derive(burrtype::Burr, serde::Serialize, serde::Deserialize, Debug)]
+#[burr(mod = "serde")]
+pub struct Page {
+    pub items: Vec<Foo>,
+    /// Left out on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// Carries its own name, so it can be told apart from other messages on the same channel
+#[derive(burrtype::Burr, serde::Serialize, serde::Deserialize, Debug)]
+#[burr(mod = "serde")]
+#[serde(tag = "type", rename = "ping")]
+pub struct Ping {
+    pub sent_at: u64,
+    /// Snowflake ID of the sender, too large for a `number`, so clients read it with a `bigint`-aware JSON parser
+    #[burr(int_style = "bigint")]
+    pub sender: u64,
+}
+
+/// How long to wait before trying again, written as text such as "1m 30s"
+#[derive(burrtype::Burr, serde::Serialize, serde::Deserialize, Debug)]
+#[burr(mod = "serde")]
+pub struct Retry {
+    #[serde(with = "humantime_serde")]
+    pub after: std::time::Duration,
+    #[serde(default, with = "humantime_serde::option")]
+    pub give_up_after: Option<std::time::Duration>,
+    /// Time spent waiting so far, written the way serde writes durations by default
+    pub waited: std::time::Duration,
+    /// Passed along as whatever the client sent
+    #[burr(type = "Record<string, unknown>")]
+    pub context: serde_json::Map<String, serde_json::Value>,
+    /// Where to report giving up, which is always a secure link
+    #[burr(ts = "`https://${string}`")]
+    pub report_to: String,
+}
+
+/// Unknown kinds are read as `Other`, so older readers keep working as kinds are added
+#[derive(burrtype::Burr, serde::Serialize, serde::Deserialize, Debug)]
+#[burr(mod = "serde")]
+#[serde(tag = "kind", rename_all_fields = "camelCase")]
+pub enum Notice {
+    Message {
+        sent_by: String,
+        #[serde(alias = "text")]
+        body: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+/// serde writes this as the string inside, so it is exported as an alias of it
+#[derive(burrtype::Burr, serde::Serialize, serde::Deserialize, Debug)]
+#[burr(mod = "serde")]
+#[serde(transparent)]
+pub struct Email(pub String);
+
+/// Read and written differently, so targets splitting input and output types write this twice
+#[derive(burrtype::Burr, serde::Serialize, serde::Deserialize, Debug)]
+#[burr(mod = "serde")]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct Account {
+    pub user_name: String,
+    pub email: Email,
+    #[serde(rename(deserialize = "pass"), skip_serializing)]
+    pub password: String,
+    #[serde(skip_deserializing)]
+    pub created_at: u64,
+}
+
+
 #[derive(burrtype::Burr, serde::Serialize, serde::Deserialize, Debug)]
 #[burr(mod = "serde")]
 #[serde(rename_all = "snake_case")]
@@ -32,7 +148,7 @@ pub enum UntaggedEnum {
         foo: Foo,
         bar: String,
     },
-    /// Unit variant will be a string, but the newtype below will also capture a string
+    /// Unit variant will be `null`, since serde has nothing else to write for it
     /// In untagged enum representations, serde will attempt them top-to-bottom
     /// So we place more specific cases before general ones
     Unit,
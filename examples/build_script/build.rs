@@ -0,0 +1,13 @@
+use burrtype::prelude::*;
+
+/// This example demonstrates exporting your types from a build script, so they are regenerated on every `cargo build`
+/// The exporter scans this crate's sources, since types collected by `#[derive(Burr)]` only exist in the final binary
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut exporter = burrtype::build::exporter()?;
+    exporter
+        .resolve_all("common")
+        .with_root("out/api");
+
+    exporter.export("ts", TypeScript::new())?;
+    Ok(())
+}
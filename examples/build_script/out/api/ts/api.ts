@@ -0,0 +1,8 @@
+import { Scope, User } from './common'
+
+export interface Session {
+  user: User,
+  /** @precision-unsafe */
+  expires: number,
+  scopes: Scope[],
+}
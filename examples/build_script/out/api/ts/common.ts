@@ -0,0 +1,12 @@
+export type Scope =
+  | "read"
+  | "write"
+  | "admin"
+;
+
+/** A registered user */
+export interface User {
+  id: number,
+  displayName: string,
+  email?: string,
+}
@@ -0,0 +1,23 @@
+// Example types only exist to be exported
+#![allow(dead_code)]
+
+mod users;
+
+use burrtype::prelude::*;
+use users::User;
+
+#[derive(Burr, serde::Serialize, serde::Deserialize, Debug)]
+#[burr(mod = "api")]
+pub struct Session {
+    pub user: User,
+    pub expires: u64,
+    pub scopes: Vec<Scope>,
+}
+
+#[derive(Burr, serde::Serialize, serde::Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Scope {
+    Read,
+    Write,
+    Admin,
+}
@@ -0,0 +1,10 @@
+use burrtype::prelude::*;
+
+/// A registered user
+#[derive(Burr, serde::Serialize, serde::Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    pub id: u32,
+    pub display_name: String,
+    pub email: Option<String>,
+}
@@ -22,6 +22,11 @@ export type Enum =
 /** A unit struct has no shape nor fields */
 export type UnitStruct = null
 
+export type Stuff =
+  | "red"
+  | "two"
+;
+
 export interface RenamedStruct {
   FOO: Stuff,
   optional?: Foo,
@@ -32,16 +37,16 @@ Even below when this field is substituted in using #[serde(flatten)] */
 }
 
 /** An enum's variants correlate with struct variants */
-export type InternallyTaggedEnum =
-  | { type: "Struct", foo: Foo, bar: string }
-  | { type: "Unit" }
+export type UntaggedEnum =
+  | { foo: Foo, bar: string }
+  /** Unit variant will be `null`, since serde has nothing else to write for it
+In untagged enum representations, serde will attempt them top-to-bottom
+So we place more specific cases before general ones */
+  | null
+  | string
+  | [Stuff, Stuff]
+  /** Bigger structs can expand to a better format */
   | {
-      type: "BigStruct",
-      /** comments work at all levels
-Even below when this field is substituted in using #[serde(flatten)] */
-      one: number,
-      two: string,
-      /** It doesn't matter where types are, we can reference them */
       THREE: DeepTupleStruct,
       FOUR?: RenamedStruct,
       six: TupleStruct,
@@ -68,16 +73,16 @@ export type AdjacentlyTaggedEnum =
 ;
 
 /** An enum's variants correlate with struct variants */
-export type UntaggedEnum =
-  | { foo: Foo, bar: string }
-  /** Unit variant will be a string, but the newtype below will also capture a string
-In untagged enum representations, serde will attempt them top-to-bottom
-So we place more specific cases before general ones */
-  | "unit"
-  | string
-  | [Stuff, Stuff]
-  /** Bigger structs can expand to a better format */
+export type InternallyTaggedEnum =
+  | { type: "Struct", foo: Foo, bar: string }
+  | { type: "Unit" }
   | {
+      type: "BigStruct",
+      /** comments work at all levels
+Even below when this field is substituted in using #[serde(flatten)] */
+      one: number,
+      two: string,
+      /** It doesn't matter where types are, we can reference them */
       THREE: DeepTupleStruct,
       FOUR?: RenamedStruct,
       six: TupleStruct,
@@ -92,11 +97,6 @@ Even below when this field is substituted in using #[serde(flatten)] */
   two: string,
 }
 
-export type Stuff =
-  | "red"
-  | "two"
-;
-
 /** The simplest enum of all unit types */
 export type Things =
   | "One"
@@ -104,12 +104,18 @@ export type Things =
 ;
 
 /** We can assign a module at the type level */
-/** Why do we care about such things */
+/**
+ * Why do we care about such things
+ * @precision-unsafe
+ */
 export type DeepTupleStruct = number
 
 export interface NamedStruct {
-  /** Type alias allows us to treat one type like another
-Here we treat a newtype like its known inner type */
+  /**
+   * Type alias allows us to treat one type like another
+Here we treat a newtype like its known inner type
+   * @precision-unsafe
+   */
   foo: number,
   /** Rust reserved keywords should resolve properly for other languages */
   ty: number,
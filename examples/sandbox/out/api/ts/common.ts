@@ -6,11 +6,6 @@ Even below when this field is substituted in using #[serde(flatten)] */
   two: string,
 }
 
-export type Stuff =
-  | "red"
-  | "two"
-;
-
 /** The simplest enum of all unit types */
 export type Things =
   | "One"
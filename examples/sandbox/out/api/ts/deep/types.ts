@@ -1,3 +1,6 @@
 /** We can assign a module at the type level */
-/** Why do we care about such things */
+/**
+ * Why do we care about such things
+ * @precision-unsafe
+ */
 export type DeepTupleStruct = number
@@ -1,6 +1,11 @@
 import { DeepTupleStruct } from '../deep/types'
-import { Foo, Stuff } from '../common'
 import { TupleStruct } from '../types'
+import { Foo } from '../common'
+
+export type Stuff =
+  | "red"
+  | "two"
+;
 
 export interface RenamedStruct {
   FOO: Stuff,
@@ -12,16 +17,16 @@ Even below when this field is substituted in using #[serde(flatten)] */
 }
 
 /** An enum's variants correlate with struct variants */
-export type InternallyTaggedEnum =
-  | { type: "Struct", foo: Foo, bar: string }
-  | { type: "Unit" }
+export type UntaggedEnum =
+  | { foo: Foo, bar: string }
+  /** Unit variant will be `null`, since serde has nothing else to write for it
+In untagged enum representations, serde will attempt them top-to-bottom
+So we place more specific cases before general ones */
+  | null
+  | string
+  | [Stuff, Stuff]
+  /** Bigger structs can expand to a better format */
   | {
-      type: "BigStruct",
-      /** comments work at all levels
-Even below when this field is substituted in using #[serde(flatten)] */
-      one: number,
-      two: string,
-      /** It doesn't matter where types are, we can reference them */
       THREE: DeepTupleStruct,
       FOUR?: RenamedStruct,
       six: TupleStruct,
@@ -48,16 +53,16 @@ export type AdjacentlyTaggedEnum =
 ;
 
 /** An enum's variants correlate with struct variants */
-export type UntaggedEnum =
-  | { foo: Foo, bar: string }
-  /** Unit variant will be a string, but the newtype below will also capture a string
-In untagged enum representations, serde will attempt them top-to-bottom
-So we place more specific cases before general ones */
-  | "unit"
-  | string
-  | [Stuff, Stuff]
-  /** Bigger structs can expand to a better format */
+export type InternallyTaggedEnum =
+  | { type: "Struct", foo: Foo, bar: string }
+  | { type: "Unit" }
   | {
+      type: "BigStruct",
+      /** comments work at all levels
+Even below when this field is substituted in using #[serde(flatten)] */
+      one: number,
+      two: string,
+      /** It doesn't matter where types are, we can reference them */
       THREE: DeepTupleStruct,
       FOUR?: RenamedStruct,
       six: TupleStruct,
@@ -1,8 +1,11 @@
 import { Foo } from './common'
 
 export interface NamedStruct {
-  /** Type alias allows us to treat one type like another
-Here we treat a newtype like its known inner type */
+  /**
+   * Type alias allows us to treat one type like another
+Here we treat a newtype like its known inner type
+   * @precision-unsafe
+   */
   foo: number,
   /** Rust reserved keywords should resolve properly for other languages */
   ty: number,
@@ -1,3 +1,6 @@
+// Example types favor placeholder names and variants that only exist to be exported
+#![allow(clippy::disallowed_names)]
+
 use burrtype::prelude::*;
 
 #[derive(Burr, serde::Serialize, serde::Deserialize, Debug)]
@@ -12,8 +15,7 @@ pub struct Foo {
 #[derive(Burr, serde::Serialize, serde::Deserialize, Debug)]
 pub struct Bar(pub Foo);
 
-/// Aim soon to support #[burrmod] for organizing a set of items within an inline module
-/// Note this will not work for file-based modules, only inline ones
+/// Types can be organized however suits the crate, and assigned to export modules separately
 pub mod inner {
     pub mod bar {
         #[derive(burrtype::Burr, serde::Serialize, serde::Deserialize, Debug)]
@@ -90,6 +92,9 @@ pub mod inner {
     }
 }
 
+/// `#[burrmod]` describes everything exported within an inline module, so its layout can drive the export tree
+/// Note this will not work for file-based modules, only inline ones
+#[burrmod(name = "serde")]
 pub mod serde_test {
     use super::inner::*;
 
@@ -123,7 +128,7 @@ pub mod serde_test {
             foo: super::Foo,
             bar: String,
         },
-        /// Unit variant will be a string, but the newtype below will also capture a string
+        /// Unit variant will be `null`, since serde has nothing else to write for it
         /// In untagged enum representations, serde will attempt them top-to-bottom
         /// So we place more specific cases before general ones
         Unit,
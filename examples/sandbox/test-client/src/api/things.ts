@@ -1,6 +1,6 @@
 import { NamedStruct, TupleStruct } from './types'
-import { Foo, Stuff, Things } from './common'
 import { DeepTupleStruct } from './deep'
+import { Foo, Things } from './common'
 
 export type Bar = Foo
 
@@ -26,6 +26,11 @@ export type Enum =
 /** A unit struct has no shape nor fields */
 export type UnitStruct = null
 
+export type Stuff =
+  | "red"
+  | "two"
+;
+
 export interface RenamedStruct {
   FOO: Stuff,
   optional?: Foo,
@@ -36,16 +41,16 @@ Even below when this field is substituted in using #[serde(flatten)] */
 }
 
 /** An enum's variants correlate with struct variants */
-export type InternallyTaggedEnum =
-  | { type: "Struct", foo: Foo, bar: string }
-  | { type: "Unit" }
+export type UntaggedEnum =
+  | { foo: Foo, bar: string }
+  /** Unit variant will be `null`, since serde has nothing else to write for it
+In untagged enum representations, serde will attempt them top-to-bottom
+So we place more specific cases before general ones */
+  | null
+  | string
+  | [Stuff, Stuff]
+  /** Bigger structs can expand to a better format */
   | {
-      type: "BigStruct",
-      /** comments work at all levels
-Even below when this field is substituted in using #[serde(flatten)] */
-      one: number,
-      two: string,
-      /** It doesn't matter where types are, we can reference them */
       THREE: DeepTupleStruct,
       FOUR?: RenamedStruct,
       six: TupleStruct,
@@ -72,16 +77,16 @@ export type AdjacentlyTaggedEnum =
 ;
 
 /** An enum's variants correlate with struct variants */
-export type UntaggedEnum =
-  | { foo: Foo, bar: string }
-  /** Unit variant will be a string, but the newtype below will also capture a string
-In untagged enum representations, serde will attempt them top-to-bottom
-So we place more specific cases before general ones */
-  | "unit"
-  | string
-  | [Stuff, Stuff]
-  /** Bigger structs can expand to a better format */
+export type InternallyTaggedEnum =
+  | { type: "Struct", foo: Foo, bar: string }
+  | { type: "Unit" }
   | {
+      type: "BigStruct",
+      /** comments work at all levels
+Even below when this field is substituted in using #[serde(flatten)] */
+      one: number,
+      two: string,
+      /** It doesn't matter where types are, we can reference them */
       THREE: DeepTupleStruct,
       FOUR?: RenamedStruct,
       six: TupleStruct,
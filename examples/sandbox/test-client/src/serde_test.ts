@@ -64,7 +64,7 @@ async function untagged_enum_unit(client: AxiosInstance) {
     let result = await client.get<UntaggedEnum>('/untagged_enum_unit')
     console.log("untagged_enum_unit: ", result.data)
 
-    let data: UntaggedEnum = "unit"
+    let data: UntaggedEnum = null
 
     let ret = await client.post<UntaggedEnum>('/untagged_enum_unit', data)
     assert_eq(data, ret.data)
@@ -0,0 +1,117 @@
+//! Checks what serde writes for every sandbox type against what we export for it
+//! The types live in another crate, so samples are given as functions rather than through `Sample`
+
+use burrtype_conformance::Harness;
+use sandbox::inner::bar::DeepTupleStruct;
+use sandbox::inner::{self, Enum, PhantomType, Things, TupleStruct, UnitStruct};
+use sandbox::serde_test::{self, AdjacentlyTaggedEnum, InternallyTaggedEnum, Stuff, UntaggedEnum};
+use sandbox::{Bar, Foo};
+
+fn foo() -> Foo {
+    Foo { one: 1, two: "two".to_string() }
+}
+
+fn named() -> inner::NamedStruct {
+    inner::NamedStruct { foo: PhantomType(1), ty: rust_decimal::Decimal::new(314, 2), opt: Some(foo()) }
+}
+
+fn renamed() -> serde_test::NamedStruct {
+    serde_test::NamedStruct { foo: Stuff::Red, opt: None, more: foo() }
+}
+
+fn tuple() -> TupleStruct {
+    TupleStruct(3, foo())
+}
+
+fn foos() -> Vec<Foo> {
+        vec![foo()]
+}
+
+fn bars() -> Vec<Bar> {
+        vec![Bar(foo())]
+}
+
+fn deep_tuple_structs() -> Vec<DeepTupleStruct> {
+        vec![DeepTupleStruct(u64::MAX)]
+}
+
+fn inner_named_structs() -> Vec<inner::NamedStruct> {
+        vec![named(), inner::NamedStruct { opt: None, ..named() }]
+}
+
+fn tuple_structs() -> Vec<TupleStruct> {
+        vec![tuple()]
+}
+
+fn unit_structs() -> Vec<UnitStruct> {
+        vec![UnitStruct]
+}
+
+fn things() -> Vec<Things> {
+        vec![Things::One, Things::Two]
+}
+
+fn enums() -> Vec<Enum> {
+        vec![
+            Enum::Struct { foo: foo(), bar: "bar".to_string() },
+            Enum::TinyTuple("tiny".to_string()),
+            Enum::Tuple(Things::One, Things::Two),
+            Enum::Unit,
+            Enum::BigStruct { one: foo(), three: DeepTupleStruct(3), four: Some(named()), five: tuple() },
+        ]
+}
+
+fn stuffs() -> Vec<Stuff> {
+        vec![Stuff::Red, Stuff::Two]
+}
+
+fn serde_named_structs() -> Vec<serde_test::NamedStruct> {
+        vec![renamed(), serde_test::NamedStruct { opt: Some(foo()), ..renamed() }]
+}
+
+fn untagged_enums() -> Vec<UntaggedEnum> {
+        vec![
+            UntaggedEnum::Struct { foo: foo(), bar: "bar".to_string() },
+            UntaggedEnum::Unit,
+            UntaggedEnum::TinyTuple("tiny".to_string()),
+            UntaggedEnum::Tuple(Stuff::Red, Stuff::Two),
+            UntaggedEnum::BigStruct { three: DeepTupleStruct(3), four: Some(renamed()), five: tuple() },
+        ]
+}
+
+fn adjacently_tagged_enums() -> Vec<AdjacentlyTaggedEnum> {
+        vec![
+            AdjacentlyTaggedEnum::Struct { foo: foo(), bar: "bar".to_string() },
+            AdjacentlyTaggedEnum::TinyTuple("tiny".to_string()),
+            AdjacentlyTaggedEnum::Tuple(Stuff::Red, Stuff::Two),
+            AdjacentlyTaggedEnum::Unit,
+            AdjacentlyTaggedEnum::BigStruct { three: DeepTupleStruct(3), four: None, five: tuple() },
+        ]
+}
+
+fn internally_tagged_enums() -> Vec<InternallyTaggedEnum> {
+        vec![
+            InternallyTaggedEnum::Struct { foo: foo(), bar: "bar".to_string() },
+            InternallyTaggedEnum::Unit,
+            InternallyTaggedEnum::BigStruct { more: foo(), three: DeepTupleStruct(3), four: Some(renamed()), five: tuple() },
+        ]
+}
+
+#[test]
+fn sandbox_types_match_serde() {
+    Harness::new()
+        .with_samples(foos)
+        .with_samples(bars)
+        .with_samples(deep_tuple_structs)
+        .with_samples(inner_named_structs)
+        .with_samples(tuple_structs)
+        .with_samples(unit_structs)
+        .with_samples(things)
+        .with_samples(enums)
+        .with_samples(stuffs)
+        .with_samples(serde_named_structs)
+        .with_samples(untagged_enums)
+        .with_samples(adjacently_tagged_enums)
+        .with_samples(internally_tagged_enums)
+        .assert();
+}
@@ -0,0 +1,447 @@
+//! Exporting from build scripts
+//!
+//! The `TYPES` slice is only populated once the final binary is linked, so a build script cannot use `Burrxporter::new`
+//! Instead, we scan the crate's sources with `syn` and build the same IR the derive would have generated
+//!
+//! ```no_run
+//! // build.rs
+//! use burrtype::prelude::*;
+//!
+//! fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let mut exporter = burrtype::build::exporter()?;
+//!     exporter.resolve_all("common").with_root("out/api");
+//!     exporter.export("ts", TypeScript::new())?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Types are keyed by their Rust path rather than their `TypeId` here, see `TypeScript::with_path_name` for mapping foreign types
+//! Well-known types imported by their full path, such as `std::time::Duration`, still get their `TypeId` so targets write them as they would with the derive
+//! Constants exported with `#[burr]` are not picked up, since their values only exist once the crate is compiled
+//! For the same reason, enum discriminants must be integer literals, and enums serialized as integers need `#[derive(Serialize_repr)]` or `#[burr(repr = "int")]`
+
+use crate::export::{Burrxporter, ExportError, known_key};
+use burrtype_internal::ir::{IrFlatten, IrFlattened, IrItem, IrType, TypeKey, leak_str, paths_match};
+use burrtype_internal::parse::{self, TypeResolver};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use syn::{Attribute, DeriveInput, Expr, Item, ItemMod, ItemType, Lit, Meta, TypePath, UseTree};
+use syn::ext::IdentExt;
+use syn::punctuated::Punctuated;
+
+/// The results of scanning a crate's sources
+#[derive(Debug, Default)]
+pub struct Scan {
+    /// Every item deriving `Burr`, along with aliases marked `#[burr]`
+    pub items: Vec<IrItem>,
+    /// Every source file that was read
+    pub files: Vec<PathBuf>,
+}
+
+impl Scan {
+    /// Tells cargo to rerun the build script whenever any scanned source file changes
+    pub fn rerun_if_changed(&self) {
+        for file in &self.files {
+            println!("cargo:rerun-if-changed={}", file.display());
+        }
+    }
+
+    /// Builds an exporter from the scanned items
+    pub fn into_exporter(self) -> Burrxporter {
+        Burrxporter::from_items(self.items)
+    }
+}
+
+/// Scans the crate currently being built and sets up an exporter for its types
+/// Intended to be called from `build.rs`, this also tells cargo to rerun the build script when a scanned source changes
+pub fn exporter() -> Result<Burrxporter, ExportError> {
+    let dir = PathBuf::from(env("CARGO_MANIFEST_DIR")?);
+    let name = env("CARGO_PKG_NAME")?.replace('-', "_");
+
+    let lib = dir.join("src").join("lib.rs");
+    let entry = if lib.exists() { lib } else { dir.join("src").join("main.rs") };
+
+    let scan = scan(&name, entry)?;
+    scan.rerun_if_changed();
+    Ok(scan.into_exporter())
+}
+
+fn env(key: &'static str) -> Result<String, ExportError> {
+    std::env::var(key).map_err(|_| ExportError::MissingEnv(key))
+}
+
+/// Scans a crate's sources for items deriving `Burr`, starting from its root file and following its modules
+/// `crate_name` is the name the crate is imported with, used to build the Rust path of each item
+pub fn scan<P: AsRef<Path>>(crate_name: &str, entry: P) -> Result<Scan, ExportError> {
+    let mut scanner = Scanner::default();
+    let entry = entry.as_ref();
+    let dir = entry.parent().map(Path::to_path_buf).unwrap_or_default();
+    scanner.scan_file(entry, dir, vec![crate_name.to_string()])?;
+
+    // every item's path has to be known before any field can be resolved
+    let mut known = HashMap::new();
+    let mut order = Vec::new();
+    let mut aliases = Vec::new();
+    for (m, module) in scanner.mods.iter().enumerate() {
+        for (i, item) in module.items.iter().enumerate() {
+            let path = leak_str(format!("{}::{}", module.path.join("::"), item.ident.unraw()));
+            known.insert(path, Loc::Item(m, i));
+            order.push((path, m, i));
+        }
+        for (i, item) in module.aliases.iter().enumerate() {
+            let path = leak_str(format!("{}::{}", module.path.join("::"), item.ident.unraw()));
+            known.insert(path, Loc::Alias);
+            aliases.push((path, m, i));
+        }
+    }
+
+    let index = Index { mods: &scanner.mods, known };
+    let mut items = Vec::new();
+    for (path, m, i) in order {
+        let module = &scanner.mods[m];
+        let mut resolver = ModResolver { index: &index, module: m, depth: 0 };
+        let ir = parse::item_ir(&module.items[i], TypeKey::Path(path), path, &mut resolver).map_err(|source| ExportError::Parse {
+            path: module.file.clone(),
+            source,
+        })?;
+        items.push(ir);
+    }
+    // fields written with an alias resolve to its path, so there is nothing for the exporter to match up later
+    for (path, m, i) in aliases {
+        let module = &scanner.mods[m];
+        let mut resolver = ModResolver { index: &index, module: m, depth: 0 };
+        let ir = parse::alias_ir(&module.aliases[i], TypeKey::Path(path), path, &mut resolver).map_err(|source| ExportError::Parse {
+            path: module.file.clone(),
+            source,
+        })?;
+        items.push(ir);
+    }
+
+    Ok(Scan { items, files: scanner.files })
+}
+
+/// A single module, either a file or an inline `mod` block
+#[derive(Debug)]
+struct ScannedMod {
+    file: PathBuf,
+    /// Absolute path to this module, starting with the crate name
+    path: Vec<String>,
+    /// Names brought into scope with `use`, mapped to their absolute paths
+    uses: HashMap<String, Vec<String>>,
+    /// Paths whose contents were brought into scope with `use path::*`
+    globs: Vec<Vec<String>>,
+    items: Vec<DeriveInput>,
+    /// Type aliases marked `#[burr]`
+    aliases: Vec<ItemType>,
+}
+
+#[derive(Default)]
+struct Scanner {
+    mods: Vec<ScannedMod>,
+    files: Vec<PathBuf>,
+}
+
+impl Scanner {
+    /// Reads a source file as the module at `path`
+    /// `dir` is where its out-of-line child modules live
+    fn scan_file(&mut self, file: &Path, dir: PathBuf, path: Vec<String>) -> Result<(), ExportError> {
+        let source = std::fs::read_to_string(file)?;
+        let parsed = syn::parse_file(&source).map_err(|source| ExportError::Parse {
+            path: file.to_path_buf(),
+            source,
+        })?;
+        self.files.push(file.to_path_buf());
+        self.scan_items(file, &parsed.items, dir, path)
+    }
+
+    fn scan_items(&mut self, file: &Path, items: &[Item], dir: PathBuf, path: Vec<String>) -> Result<(), ExportError> {
+        let index = self.mods.len();
+        self.mods.push(ScannedMod {
+            file: file.to_path_buf(),
+            path: path.clone(),
+            uses: HashMap::new(),
+            globs: Vec::new(),
+            items: Vec::new(),
+            aliases: Vec::new(),
+        });
+
+        for item in items {
+            match item {
+                Item::Use(item) => {
+                    let module = &mut self.mods[index];
+                    collect_uses(&item.tree, Vec::new(), &path, &mut module.uses, &mut module.globs);
+                }
+                Item::Struct(item) if derives_burr(&item.attrs) => {
+                    self.mods[index].items.push(item.clone().into());
+                }
+                Item::Enum(item) if derives_burr(&item.attrs) => {
+                    self.mods[index].items.push(item.clone().into());
+                }
+                Item::Type(item) if has_burr_attr(&item.attrs) => {
+                    self.mods[index].aliases.push(item.clone());
+                }
+                Item::Mod(item) => {
+                    let mut child = path.clone();
+                    child.push(item.ident.unraw().to_string());
+                    self.scan_mod(file, item, &dir, child)?;
+                }
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn scan_mod(&mut self, file: &Path, item: &ItemMod, dir: &Path, path: Vec<String>) -> Result<(), ExportError> {
+        let name = item.ident.unraw().to_string();
+
+        if let Some((_, items)) = &item.content {
+            return self.scan_items(file, items, dir.join(&name), path);
+        }
+
+        // `#[path = "..."]` is relative to the directory of the file declaring the module
+        if let Some(custom) = path_attr(&item.attrs) {
+            let base = file.parent().map(Path::to_path_buf).unwrap_or_default();
+            let target = base.join(custom);
+            let dir = target.parent().map(Path::to_path_buf).unwrap_or_default();
+            return self.scan_file(&target, dir, path);
+        }
+
+        let flat = dir.join(format!("{name}.rs"));
+        if flat.exists() {
+            return self.scan_file(&flat, dir.join(&name), path);
+        }
+
+        let nested = dir.join(&name).join("mod.rs");
+        if nested.exists() {
+            return self.scan_file(&nested, dir.join(&name), path);
+        }
+
+        Err(ExportError::ModNotFound { name, path: file.to_path_buf() })
+    }
+}
+
+/// Checks for `Burr` in a `#[derive(...)]`
+fn derives_burr(attrs: &[Attribute]) -> bool {
+    attrs.iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| attr.parse_args_with(Punctuated::<syn::Path, syn::Token![,]>::parse_terminated).ok())
+        .flatten()
+        .any(|path| path.segments.last().is_some_and(|seg| seg.ident == "Burr"))
+}
+
+/// Checks for a `#[burr]` attribute, with or without arguments
+fn has_burr_attr(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| attr.path().segments.last().is_some_and(|seg| seg.ident == "burr"))
+}
+
+/// Gets the value of `#[path = "..."]`
+fn path_attr(attrs: &[Attribute]) -> Option<String> {
+    attrs.iter().find_map(|attr| match &attr.meta {
+        Meta::NameValue(meta) if meta.path.is_ident("path") => match &meta.value {
+            Expr::Lit(expr) => match &expr.lit {
+                Lit::Str(lit) => Some(lit.value()),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// Flattens a `use` tree into the names it brings into scope
+fn collect_uses(tree: &UseTree, mut prefix: Vec<String>, module: &[String], uses: &mut HashMap<String, Vec<String>>, globs: &mut Vec<Vec<String>>) {
+    match tree {
+        UseTree::Path(path) => {
+            prefix.push(path.ident.unraw().to_string());
+            collect_uses(&path.tree, prefix, module, uses, globs);
+        }
+        UseTree::Name(name) => {
+            let ident = name.ident.unraw().to_string();
+            // `use foo::{self}` imports `foo` itself
+            let alias = if ident == "self" { prefix.last().cloned().unwrap_or_default() } else { ident.clone() };
+            if ident != "self" {
+                prefix.push(ident);
+            }
+            uses.insert(alias, absolute(prefix, module));
+        }
+        UseTree::Rename(rename) => {
+            let ident = rename.ident.unraw().to_string();
+            if ident != "self" {
+                prefix.push(ident);
+            }
+            uses.insert(rename.rename.unraw().to_string(), absolute(prefix, module));
+        }
+        UseTree::Glob(_) => globs.push(absolute(prefix, module)),
+        UseTree::Group(group) => {
+            for tree in &group.items {
+                collect_uses(tree, prefix.clone(), module, uses, globs);
+            }
+        }
+    }
+}
+
+/// Rewrites `crate`, `self`, and `super` relative to the given module
+/// Anything else is left as-is, and could be relative to the module or point at another crate
+fn absolute(path: Vec<String>, module: &[String]) -> Vec<String> {
+    let mut segments = path.into_iter().peekable();
+    let mut out = match segments.peek().map(String::as_str) {
+        Some("crate") => {
+            segments.next();
+            vec![module[0].clone()]
+        }
+        Some("self") => {
+            segments.next();
+            module.to_vec()
+        }
+        Some("super") => {
+            let mut out = module.to_vec();
+            while segments.peek().is_some_and(|s| s == "super") {
+                segments.next();
+                out.pop();
+            }
+            out
+        }
+        _ => Vec::new(),
+    };
+    out.extend(segments);
+    out
+}
+
+/// Where a scanned item can be found
+#[derive(Copy, Clone)]
+enum Loc {
+    /// Module and item index of an item deriving `Burr`
+    Item(usize, usize),
+    /// Aliases are only ever referred to by path
+    Alias,
+}
+
+struct Index<'s> {
+    mods: &'s [ScannedMod],
+    /// Absolute paths of every scanned item
+    known: HashMap<&'static str, Loc>,
+}
+
+impl<'s> Index<'s> {
+    fn lookup(&self, path: &[String]) -> Option<(&'static str, Loc)> {
+        self.known.get_key_value(path.join("::").as_str()).map(|(path, loc)| (*path, *loc))
+    }
+}
+
+/// Resolves types as written within a particular module
+struct ModResolver<'i, 's> {
+    index: &'i Index<'s>,
+    module: usize,
+    /// Guards against types that flatten themselves
+    depth: usize,
+}
+
+impl<'i, 's> ModResolver<'i, 's> {
+    /// Finds the item a type refers to, if it was one we scanned
+    fn find(&self, ty: &TypePath) -> Option<(&'static str, Loc)> {
+        let module = &self.index.mods[self.module];
+        let written: Vec<String> = ty.path.segments.iter().map(|seg| seg.ident.unraw().to_string()).collect();
+        let first = written.first()?;
+
+        let mut candidates = Vec::new();
+        if matches!(first.as_str(), "crate" | "self" | "super") {
+            candidates.push(absolute(written.clone(), &module.path));
+        }
+        else {
+            if let Some(used) = module.uses.get(first) {
+                let mut path = used.clone();
+                path.extend(written[1..].iter().cloned());
+                candidates.push(absolute(path, &module.path));
+            }
+
+            let mut local = module.path.clone();
+            local.extend(written.iter().cloned());
+            candidates.push(local);
+
+            for glob in &module.globs {
+                let mut path = glob.clone();
+                path.extend(written.iter().cloned());
+                candidates.push(path);
+            }
+
+            // paths through the crate by name, such as in examples and tests
+            candidates.push(written.clone());
+        }
+
+        for candidate in &candidates {
+            if let Some(found) = self.index.lookup(candidate) {
+                return Some(found);
+            }
+
+            // `use` paths without `crate::` may also start from the current module
+            let mut relative = module.path.clone();
+            relative.extend(candidate.iter().cloned());
+            if let Some(found) = self.index.lookup(&relative) {
+                return Some(found);
+            }
+        }
+
+        // fall back to any single item whose path ends the same way, such as items re-exported under another name
+        let written = written.join("::");
+        let mut matches = self.index.known.iter().filter(|(path, _)| paths_match(path, &written));
+        match (matches.next(), matches.next()) {
+            (Some((path, loc)), None) if TypeKey::from_std_path(&written).is_none() => Some((path, *loc)),
+            _ => None,
+        }
+    }
+
+    /// The best absolute path we can come up with for a type we did not scan
+    fn foreign_path(&self, ty: &TypePath) -> String {
+        let module = &self.index.mods[self.module];
+        let written: Vec<String> = ty.path.segments.iter().map(|seg| seg.ident.unraw().to_string()).collect();
+        let path = match written.first().and_then(|first| module.uses.get(first)) {
+            Some(used) => {
+                let mut path = used.clone();
+                path.extend(written[1..].iter().cloned());
+                path
+            }
+            None => written,
+        };
+        absolute(path, &module.path).join("::")
+    }
+}
+
+impl<'i, 's> TypeResolver for ModResolver<'i, 's> {
+    fn resolve(&mut self, ty: &TypePath) -> TypeKey {
+        if let Some((path, _)) = self.find(ty) {
+            return TypeKey::Path(path);
+        }
+
+        let path = self.foreign_path(ty);
+        TypeKey::from_std_path(&path).or_else(|| known_key(&path)).unwrap_or_else(|| TypeKey::Path(leak_str(path)))
+    }
+
+    fn flatten(&mut self, ty: &TypePath) -> syn::Result<IrFlatten> {
+        let Some((_, Loc::Item(m, i))) = self.find(ty) else {
+            return Err(syn::Error::new_spanned(ty, "flattened types must also derive Burr"));
+        };
+        if self.depth > 32 {
+            return Err(syn::Error::new_spanned(ty, "flattened types cannot contain themselves"));
+        }
+
+        let item = &self.index.mods[m].items[i];
+        let fields = match &item.data {
+            syn::Data::Struct(syn::DataStruct { fields: syn::Fields::Named(fields), .. }) => fields,
+            // enums keep their shape, and are written alongside the fields around them
+            syn::Data::Enum(_) => return Ok(IrFlatten {
+                fields: Vec::new(),
+                flattened: vec![IrFlattened::Enum(IrType {
+                    id: self.resolve(ty),
+                    path: ty.clone(),
+                    optional: false,
+                    array: false,
+                })],
+            }),
+            _ => return Err(syn::Error::new_spanned(ty, "only named structs, enums and maps can be flattened")),
+        };
+
+        let mut resolver = ModResolver { index: self.index, module: m, depth: self.depth + 1 };
+        parse::named_fields_ir(fields, parse::serde_rename_all(&item.attrs), parse::serde_default(&item.attrs), &mut resolver)
+    }
+}
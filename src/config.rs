@@ -0,0 +1,266 @@
+//! Describes exports in a file rather than code
+//! Both TOML and JSON are supported, picked by the file's extension
+//!
+//! ```toml
+//! root = "out/api"
+//! default_mod = "common"
+//! # "all" exports every registered type, "exports" only what the modules below depend on
+//! resolve = "exports"
+//! # applies to every target
+//! type_names = { "rust_decimal::Decimal" = "number" }
+//!
+//! [mods.bar]
+//! types = ["my_crate::Bar"]
+//!
+//! [mods.bar.mods.baz]
+//! # leading segments can be left out, as long as only one type matches
+//! types = ["inner::Baz"]
+//!
+//! [[target]]
+//! lang = "typescript"
+//! path = "ts"
+//! file_map = "decompose_all"
+//! # how enums without fields are written: "union", "enum", "const_enum", or "union_with_values"
+//! enum_style = "enum"
+//! # how integers wider than 32 bits are written: "number" marks them as losing precision, "bigint" needs a parser that makes them, "string" suits those written as text
+//! int_style = "bigint"
+//! # overrides the names above for this target only
+//! type_names = { "my_crate::Bar" = "Bar2" }
+//! # writes types read differently from how they are written as `{Name}Input` and `{Name}Output`
+//! input_output = true
+//! ```
+
+use crate::export::{BurrMod, Burrxporter, ExportError, ExportReport};
+use crate::targets::typescript::{EnumStyle, IntStyle, ModFileMap, TsFormatter, TypeScript};
+use burrtype_internal::ir::leak_str;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use serde::Deserialize;
+
+/// Name of the config file `cargo burrtype` looks for
+pub const CONFIG_NAME: &str = "burrtype.toml";
+
+/// Everything needed to run a set of exports
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// The package whose types are exported
+    /// Only used by `cargo burrtype`, when it cannot tell which package the config belongs to
+    pub package: Option<String>,
+    /// Root path for exports
+    /// Relative paths start from the directory containing the config file
+    pub root: Option<PathBuf>,
+    /// Module that types without a `#[burr(mod = "...")]` attribute are written to
+    #[serde(default = "default_mod")]
+    pub default_mod: String,
+    #[serde(default)]
+    pub resolve: Resolve,
+    /// The module tree, with types named by their Rust path
+    #[serde(default)]
+    pub mods: BTreeMap<String, ModConfig>,
+    /// Names written in place of types by every target, keyed by their Rust path
+    #[serde(default)]
+    pub type_names: BTreeMap<String, String>,
+    #[serde(default, rename = "target")]
+    pub targets: Vec<TargetConfig>,
+}
+
+/// Controls which types are exported besides those listed in modules
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Resolve {
+    /// Every registered type, see `Burrxporter::resolve_all`
+    #[default]
+    All,
+    /// Types that listed types depend on, see `Burrxporter::resolve_exports`
+    Exports,
+    /// Only the listed types
+    None,
+}
+
+/// A module and the types it exports
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModConfig {
+    /// Rust paths of the types in this module
+    #[serde(default)]
+    pub types: Vec<String>,
+    #[serde(default)]
+    pub mods: BTreeMap<String, ModConfig>,
+}
+
+impl ModConfig {
+    fn build(&self, name: &str, exporter: &Burrxporter) -> Result<BurrMod, ExportError> {
+        let mut bm = BurrMod::new(name);
+        for path in &self.types {
+            bm = bm.with_item(exporter.type_by_path(path)?.clone());
+        }
+        for (name, child) in &self.mods {
+            bm = bm.with_mod(child.build(name, exporter)?);
+        }
+        Ok(bm)
+    }
+}
+
+fn default_mod() -> String {
+    "common".to_string()
+}
+
+/// A single export and its options
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "lang")]
+pub enum TargetConfig {
+    #[serde(rename = "typescript")]
+    TypeScript(TsConfig),
+}
+
+/// Options for exporting to TypeScript
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TsConfig {
+    /// Output path, relative to the root
+    pub path: PathBuf,
+    #[serde(default = "default_file_map")]
+    pub file_map: ModFileMap,
+    pub max_items_per_line: Option<usize>,
+    /// How enums without fields are written
+    #[serde(default)]
+    pub enum_style: EnumStyle,
+    /// How integers wider than 32 bits are written: `number`, marked as losing precision, `bigint`, or `string`
+    #[serde(default)]
+    pub int_style: IntStyle,
+    /// Names written in place of types, keyed by their Rust path
+    #[serde(default)]
+    pub type_names: BTreeMap<String, String>,
+    /// Writes types serde reads differently from how it writes them as `{Name}Input` and `{Name}Output`
+    #[serde(default)]
+    pub input_output: bool,
+}
+
+fn default_file_map() -> ModFileMap {
+    ModFileMap::DecomposeAll
+}
+
+impl TsConfig {
+    /// Builds the target these options describe
+    /// Names for registered types are keyed by their `TypeId`, while any other path is matched against the path written for a field
+    pub fn target(&self, exporter: &Burrxporter) -> Result<TypeScript<'_>, ExportError> {
+        let mut formatter = TsFormatter::pretty();
+        if let Some(n) = self.max_items_per_line {
+            formatter = formatter.with_max_items_per_line(n);
+        }
+
+        let mut target = TypeScript::new()
+            .with_formatter(formatter)
+            .with_file_map(self.file_map)
+            .with_enum_style(self.enum_style)
+            .with_int_style(self.int_style)
+            .with_input_output(self.input_output);
+        for (path, name) in &self.type_names {
+            match exporter.type_by_path(path) {
+                Ok(item) => {
+                    target.type_map.insert(item.type_id(), name);
+                }
+                Err(ExportError::UnknownPath(_)) => target = target.with_path_name(leak_str(path.as_str()), name),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(target)
+    }
+}
+
+impl TargetConfig {
+    /// Output path, relative to the root
+    pub fn path(&self) -> &Path {
+        match self {
+            TargetConfig::TypeScript(ts) => &ts.path,
+        }
+    }
+
+    /// Runs this export, or only compares it against what is on disk when `dry_run` is set
+    pub fn export(&self, exporter: &Burrxporter, dry_run: bool) -> Result<ExportReport, ExportError> {
+        match self {
+            TargetConfig::TypeScript(ts) if dry_run => exporter.check(&ts.path, ts.target(exporter)?),
+            TargetConfig::TypeScript(ts) => exporter.export(&ts.path, ts.target(exporter)?),
+        }
+    }
+}
+
+impl Config {
+    /// Reads a config from a file, as JSON if it has a `.json` extension and TOML otherwise
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ExportError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        let invalid = |message: String| ExportError::Config {
+            path: path.to_path_buf(),
+            message,
+        };
+
+        let mut config: Config = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(&text).map_err(|e| invalid(e.to_string()))?
+        }
+        else {
+            toml::from_str(&text).map_err(|e| invalid(e.to_string()))?
+        };
+
+        // Make the root independent of wherever we happen to be running from
+        let base = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        config.root = Some(match config.root.take() {
+            Some(root) => base.join(root),
+            None => base,
+        });
+
+        // Names given for a specific target win over those given for all targets
+        for target in &mut config.targets {
+            match target {
+                TargetConfig::TypeScript(ts) => {
+                    for (path, name) in &config.type_names {
+                        ts.type_names.entry(path.clone()).or_insert_with(|| name.clone());
+                    }
+                }
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Builds an exporter from all registered types, with the modules and targets this config describes
+    pub fn exporter(&self) -> Result<Burrxporter, ExportError> {
+        let mut exporter = Burrxporter::new();
+        if let Some(root) = &self.root {
+            exporter.with_root(root);
+        }
+
+        for (name, bm) in &self.mods {
+            let bm = bm.build(name, &exporter)?;
+            exporter.with_mod(bm);
+        }
+
+        match self.resolve {
+            Resolve::All => { exporter.resolve_all(&self.default_mod); }
+            Resolve::Exports => { exporter.resolve_exports(&self.default_mod); }
+            Resolve::None => {}
+        }
+
+        exporter.targets = self.targets.clone();
+        Ok(exporter)
+    }
+}
+
+impl Burrxporter {
+    /// Builds an exporter as described by a config file
+    /// Run its exports with `export_all`
+    pub fn from_config<P: AsRef<Path>>(path: P) -> Result<Self, ExportError> {
+        Config::load(path)?.exporter()
+    }
+
+    /// Runs every export described by the config
+    pub fn export_all(&self) -> Result<Vec<ExportReport>, ExportError> {
+        self.targets.iter().map(|target| target.export(self, false)).collect()
+    }
+
+    /// Compares every export described by the config against what is on disk without touching any files
+    pub fn check_all(&self) -> Result<Vec<ExportReport>, ExportError> {
+        self.targets.iter().map(|target| target.export(self, true)).collect()
+    }
+}
@@ -1,17 +1,19 @@
 mod burrmod;
+mod known;
+mod report;
 mod target;
+mod writer;
 
-use std::any::TypeId;
 use std::collections::{HashMap, HashSet};
 pub use burrmod::*;
+pub use known::*;
+pub use report::*;
 pub use target::*;
+pub use writer::*;
 
-use std::fs;
-use std::fs::File;
-use std::io::{BufWriter, Error as IoError, Write};
+use std::io::Error as IoError;
 use std::path::{Path, PathBuf};
-use path_macro::path;
-use burrtype_internal::ir::IrItem;
+use burrtype_internal::ir::{IrAlias, IrItem, TypeKey, paths_match};
 
 #[derive(thiserror::Error, Debug)]
 pub enum ExportError {
@@ -19,6 +21,22 @@ pub enum ExportError {
     IoError(#[from] IoError),
     #[error("data store disconnected")]
     InvalidTarget,
+    #[error("failed to parse {path}: {source}")]
+    Parse { path: PathBuf, source: syn::Error },
+    #[error("could not find the file for module `{name}` declared in {path}")]
+    ModNotFound { name: String, path: PathBuf },
+    #[error("invalid config {path}: {message}")]
+    Config { path: PathBuf, message: String },
+    #[error("no registered type matches the path `{0}`")]
+    UnknownPath(String),
+    #[error("the path `{path}` matches several registered types: {}", matches.join(", "))]
+    AmbiguousPath { path: String, matches: Vec<String> },
+    #[error("invalid schema: {0}")]
+    Schema(String),
+    #[error("schema version {found} is not supported, expected version {expected}")]
+    SchemaVersion { found: u32, expected: u32 },
+    #[error("environment variable `{0}` is not set, is this running from a build script?")]
+    MissingEnv(&'static str),
 }
 
 /// Builds and exports a collection of modules representing your public API
@@ -28,30 +46,70 @@ pub enum ExportError {
 /// - Enums
 /// - Constants
 /// - Typedefs
+///
 /// Supports exporting to different languages
 pub struct Burrxporter {
     pub mods: Vec<BurrMod>,
     pub root: Option<PathBuf>,
-    pub type_registry: HashMap<TypeId, IrItem>,
+    pub type_registry: HashMap<TypeKey, IrItem>,
+    /// Exports described by a config file, run with `export_all`
+    #[cfg(feature = "config")]
+    pub targets: Vec<crate::config::TargetConfig>,
+}
+
+impl Default for Burrxporter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Burrxporter {
     pub fn new() -> Self {
-        let mut type_registry = HashMap::with_capacity(crate::TYPES.len());
-        type_registry.extend(crate::TYPES.iter().map(|ty_fn| {
-            let tr = ty_fn();
-            (tr.type_id(), tr)
-        }));
+        Self::from_items(crate::TYPES.iter().map(|ty_fn| ty_fn()))
+    }
+
+    /// Builds an exporter from the given items instead of those collected by `#[derive(Burr)]`
+    /// Useful where the `TYPES` slice is unavailable, such as within a build script
+    pub fn from_items<I: IntoIterator<Item = IrItem>>(items: I) -> Self {
+        let mut type_registry: HashMap<TypeKey, IrItem> = items.into_iter().map(|ir| (ir.type_id(), ir)).collect();
+
+        let aliases = aliases(type_registry.values());
+        for item in type_registry.values_mut() {
+            burrmod::resolve_aliases(item, &aliases);
+        }
 
         Burrxporter {
             mods: Vec::new(),
             root: None,
             type_registry,
+            #[cfg(feature = "config")]
+            targets: Vec::new(),
+        }
+    }
+
+    /// Finds a registered type by its Rust path
+    /// Paths may leave out leading segments, so `api::User` finds `my_crate::api::User` as long as no other type also matches
+    pub fn type_by_path(&self, path: &str) -> Result<&IrItem, ExportError> {
+        if let Some(item) = self.type_registry.values().find(|item| item.path() == path) {
+            return Ok(item);
+        }
+
+        let mut matches: Vec<&IrItem> = self.type_registry.values().filter(|item| paths_match(item.path(), path)).collect();
+        match matches.len() {
+            0 => Err(ExportError::UnknownPath(path.to_string())),
+            1 => Ok(matches.remove(0)),
+            _ => {
+                let mut matches: Vec<String> = matches.iter().map(|item| item.path().to_string()).collect();
+                matches.sort();
+                Err(ExportError::AmbiguousPath { path: path.to_string(), matches })
+            }
         }
     }
 
     pub fn with_mod<M: Into<BurrMod>>(&mut self, r#mod: M) -> &mut Self {
-        self.mods.push(r#mod.into());
+        let mut bm = r#mod.into();
+        bm.resolve_aliases(&aliases(self.type_registry.values()));
+        self.mods.push(bm);
         self
     }
 
@@ -61,10 +119,23 @@ impl Burrxporter {
         self
     }
 
-    /// Adds output target with configuration
-    pub fn export<P: AsRef<Path>, T: Target>(&mut self, to: P, target: T) -> Result<&mut Self, ExportError> {
-        target.export(to.as_ref(), &self);
-        Ok(self)
+    /// Writes to an output target with configuration
+    /// Files whose contents did not change are left untouched, and files a previous export wrote that are no longer produced are deleted
+    /// Returns a report of every file touched, which is also forwarded to `log` or `tracing` when either feature is enabled
+    pub fn export<P: AsRef<Path>, T: Target>(&self, to: P, target: T) -> Result<ExportReport, ExportError> {
+        let to = to.as_ref();
+        let files = target.render(to, self)?;
+        let report = self.write_files(to, files, false)?;
+        report.emit();
+        Ok(report)
+    }
+
+    /// Compares an export against what is already on disk without touching any files
+    /// The report describes what `export` would do, so an up-to-date export reports no changes
+    pub fn check<P: AsRef<Path>, T: Target>(&self, to: P, target: T) -> Result<ExportReport, ExportError> {
+        let to = to.as_ref();
+        let files = target.render(to, self)?;
+        self.write_files(to, files, true)
     }
 
     /// Collects and resolves all types for export
@@ -76,7 +147,7 @@ impl Burrxporter {
             (ir.clone(), if let Some(path) = ir.mod_override() { path } else { default })
         }).collect();
 
-        items.sort_by(|(_, a), (_, b)| a.cmp(b));
+        items.sort_by_key(|(_, a)| *a);
 
         for (ir, path) in items {
             let path = PathBuf::from(path);
@@ -112,8 +183,8 @@ impl Burrxporter {
         while dirty {
             dirty = false;
 
-            let mut exporting = HashSet::<TypeId>::new();
-            let mut importing = HashSet::<TypeId>::new();
+            let mut exporting = HashSet::<TypeKey>::new();
+            let mut importing = HashSet::<TypeKey>::new();
             for om in &self.mods {
                 exporting.extend(om.pull_exports());
                 importing.extend(om.pull_fields());
@@ -132,7 +203,7 @@ impl Burrxporter {
             if !diff.is_empty() {
                 dirty = true;
 
-                diff.sort_by(|(_, a), (_, b)| a.cmp(b));
+                diff.sort_by_key(|(_, a)| *a);
 
                 for (ir, path) in diff {
                     let (bm, auto) = get_or_create_mod(&mut self.mods, Path::new(path)).unwrap();
@@ -157,29 +228,21 @@ impl Burrxporter {
         }
         self
     }
+}
 
-    /// Gets the writer for a file path
-    /// Creates the directory and file if it does not exist, truncates if it does
-    #[allow(dead_code)]
-    pub(crate) fn open_writer(&self, to: &Path) -> Result<impl Write, ExportError> {
-        let path = self.root.as_ref().map_or_else(|| to.to_path_buf(), |root| path!(root / to));
-
-        // Extract parent and ensure it exists
-        let parent = match path.parent() {
-            Some(path) => path,
-            None => return Err(ExportError::InvalidTarget),
-        };
-        fs::create_dir_all(parent)?;
-
-        // Creates the file and returns it
-        let file = File::create(path)?;
-        Ok(BufWriter::new(file))
-    }
+/// Collects every registered alias
+fn aliases<'i, I: IntoIterator<Item = &'i IrItem>>(items: I) -> Vec<IrAlias> {
+    items.into_iter()
+        .filter_map(|item| match item {
+            IrItem::Alias(alias) => Some(alias.clone()),
+            _ => None,
+        })
+        .collect()
 }
 
 /// Gets a module at the specified path, or creates the necessary module tree as needed
 /// todo: convert the return type to a more descriptive error type when we are ready to reorganize things for error handling
-fn get_or_create_mod<'m, 'p>(mods: &'m mut Vec<BurrMod>, path: &'p Path) -> Option<(&'m mut BurrMod, bool)> {
+fn get_or_create_mod<'m>(mods: &'m mut Vec<BurrMod>, path: &Path) -> Option<(&'m mut BurrMod, bool)> {
     let mut created = false;
 
     if path.components().count() == 0 {
@@ -196,7 +259,7 @@ fn get_or_create_mod<'m, 'p>(mods: &'m mut Vec<BurrMod>, path: &'p Path) -> Opti
     }
     else {
         created = true;
-        mods.push(BurrMod::new(cname.to_owned()));
+        mods.push(BurrMod::new(cname.into_owned()));
         mods.last_mut().unwrap()
     };
 
@@ -208,7 +271,7 @@ fn get_or_create_mod<'m, 'p>(mods: &'m mut Vec<BurrMod>, path: &'p Path) -> Opti
         }
         else {
             created = true;
-            search.children.push(BurrMod::new(cname.to_owned()));
+            search.children.push(BurrMod::new(cname.into_owned()));
             search.children.last_mut().unwrap()
         };
     }
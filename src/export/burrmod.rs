@@ -1,15 +1,14 @@
-use std::any::TypeId;
 use std::collections::{HashMap, HashSet};
-use burrtype_internal::ir::{IrEnumVariant, IrItem};
-use burrtype_internal::prelude::IrExt;
+use burrtype_internal::ir::{IrAlias, IrItem, IrMod, TypeKey};
+use burrtype_internal::prelude::{IrExt, ModExt};
 
 /// A collection of items to export
 #[derive(Clone, Debug)]
 pub struct BurrMod {
     pub name: String,
-    pub exports: Vec<TypeId>,
-    pub auto_exports: Vec<TypeId>,
-    pub types: HashMap<TypeId, IrItem>,
+    pub exports: Vec<TypeKey>,
+    pub auto_exports: Vec<TypeKey>,
+    pub types: HashMap<TypeKey, IrItem>,
     pub children: Vec<BurrMod>,
 }
 
@@ -24,8 +23,30 @@ impl BurrMod {
         }
     }
 
+    /// Builds a module from an inline Rust module marked `#[burrmod]`
+    /// Pass the type the attribute generates, such as `BurrMod::from_mod::<inner::InnerMod>()` for `mod inner`
+    pub fn from_mod<M: ModExt>() -> Self {
+        M::get_ir().into()
+    }
+
+    /// Adds a module's contents to this one, merging in any children marked `flatten`
+    fn merge(&mut self, ir: IrMod) {
+        for item in ir.items {
+            self.exports.push(item.type_id());
+            self.types.insert(item.type_id(), item);
+        }
+        for child in ir.mods {
+            if child.flatten {
+                self.merge(child);
+            }
+            else {
+                self.children.push(child.into());
+            }
+        }
+    }
+
     /// Gets a flat set of all types being exported by a module
-    pub(crate) fn pull_exports(&self) -> HashSet<TypeId> {
+    pub(crate) fn pull_exports(&self) -> HashSet<TypeKey> {
         let mut types = HashSet::new();
         types.extend(self.exports.iter());
         types.extend(self.auto_exports.iter());
@@ -37,24 +58,11 @@ impl BurrMod {
     }
 
     /// Gets a flat set of all types being used by a module
-    pub(crate) fn pull_fields(&self) -> HashSet<TypeId> {
+    pub(crate) fn pull_fields(&self) -> HashSet<TypeKey> {
         let mut fields = HashSet::new();
-        // iterate fields for each type and add field's TypeId to set
-        for (_, item) in &self.types {
-            match item {
-                IrItem::UnitStruct(_) => {}
-                IrItem::NamedStruct(ir) => fields.extend(ir.fields.iter().map(|f| f.ty.id)),
-                IrItem::TupleStruct(ir) => fields.extend(ir.fields.iter().map(|ty| ty.ty.id)),
-                IrItem::Enum(ir) => {
-                    for var in &ir.variants {
-                        match var {
-                            IrEnumVariant::Struct(ir) => fields.extend(ir.fields.iter().map(|f| f.ty.id)),
-                            IrEnumVariant::Tuple(ir) => fields.extend(ir.fields.iter().map(|f| f.ty.id)),
-                            _ => {}
-                        }
-                    }
-                }
-            }
+        // iterate fields for each type and add field's TypeKey to set
+        for item in self.types.values() {
+            fields.extend(item.all_field_types());
         }
         // then repeat this recursively
         for child in &self.children {
@@ -63,6 +71,27 @@ impl BurrMod {
         fields
     }
 
+    /// Keeps only the literal types one target writes, throughout the module tree
+    #[cfg(feature = "typescript")]
+    pub(crate) fn pick_literals(&mut self, target: &str) {
+        for item in self.types.values_mut() {
+            item.pick_literals(target);
+        }
+        for child in &mut self.children {
+            child.pick_literals(target);
+        }
+    }
+
+    /// Points types written with an alias at that alias, throughout the module tree
+    pub(crate) fn resolve_aliases(&mut self, aliases: &[IrAlias]) {
+        for item in self.types.values_mut() {
+            resolve_aliases(item, aliases);
+        }
+        for child in &mut self.children {
+            child.resolve_aliases(aliases);
+        }
+    }
+
     pub fn with_name<S: Into<String>>(mut self, name: S) -> Self {
         self.name = name.into();
         self
@@ -71,7 +100,14 @@ impl BurrMod {
     pub fn with_type<T: IrExt>(mut self) -> Self {
         let item = T::get_ir();
         self.types.insert(item.type_id(), item.clone());
-        self.exports.push(item.type_id().clone());
+        self.exports.push(item.type_id());
+        self
+    }
+
+    /// Adds an item directly, such as one found with `Burrxporter::type_by_path`
+    pub fn with_item(mut self, item: IrItem) -> Self {
+        self.exports.push(item.type_id());
+        self.types.insert(item.type_id(), item);
         self
     }
 
@@ -79,4 +115,35 @@ impl BurrMod {
         self.children.push(r#mod.into());
         self
     }
-}
\ No newline at end of file
+
+    /// Adds an inline Rust module marked `#[burrmod]` as a child, or merges its contents into this one if it is marked `flatten`
+    pub fn with_burrmod<M: ModExt>(mut self) -> Self {
+        let ir = M::get_ir();
+        if ir.flatten {
+            self.merge(ir);
+        }
+        else {
+            self.children.push(ir.into());
+        }
+        self
+    }
+}
+
+impl From<IrMod> for BurrMod {
+    fn from(ir: IrMod) -> Self {
+        let mut bm = BurrMod::new(ir.name);
+        bm.merge(ir);
+        bm
+    }
+}
+
+/// Points types written with an alias at that alias
+/// Aliases share a `TypeId` with the type they stand for, so the path a type was written with is all that tells them apart
+pub(crate) fn resolve_aliases(item: &mut IrItem, aliases: &[IrAlias]) {
+    let own = item.type_id();
+    for ty in item.types_mut() {
+        if let Some(alias) = aliases.iter().find(|alias| alias.id != own && alias.matches(ty)) {
+            ty.id = alias.id;
+        }
+    }
+}
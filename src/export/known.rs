@@ -0,0 +1,78 @@
+use std::sync::OnceLock;
+use burrtype_internal::ir::{TypeKey, paths_match};
+
+/// Types from `std` and popular crates that targets know how to write without being told
+/// Crates other than `std` are behind cargo features named after them
+/// Without a `TypeId` to go on, the scanner and schemas find these by their full path
+fn known_types() -> &'static [(&'static str, TypeKey)] {
+    static KNOWN: OnceLock<Vec<(&'static str, TypeKey)>> = OnceLock::new();
+    KNOWN.get_or_init(|| vec![
+        ("std::time::Duration", TypeKey::of::<std::time::Duration>()),
+        ("std::time::SystemTime", TypeKey::of::<std::time::SystemTime>()),
+        ("std::path::PathBuf", TypeKey::of::<std::path::PathBuf>()),
+        ("std::path::Path", TypeKey::of::<std::path::Path>()),
+        ("std::net::IpAddr", TypeKey::of::<std::net::IpAddr>()),
+        ("std::net::Ipv4Addr", TypeKey::of::<std::net::Ipv4Addr>()),
+        ("std::net::Ipv6Addr", TypeKey::of::<std::net::Ipv6Addr>()),
+        ("std::net::SocketAddr", TypeKey::of::<std::net::SocketAddr>()),
+        ("std::net::SocketAddrV4", TypeKey::of::<std::net::SocketAddrV4>()),
+        ("std::net::SocketAddrV6", TypeKey::of::<std::net::SocketAddrV6>()),
+        #[cfg(feature = "serde_json")]
+        ("serde_json::Value", TypeKey::of::<serde_json::Value>()),
+        #[cfg(feature = "serde_json")]
+        ("serde_json::Number", TypeKey::of::<serde_json::Number>()),
+        #[cfg(feature = "serde_json")]
+        ("serde_json::Map", TypeKey::of::<serde_json::Map<String, serde_json::Value>>()),
+        #[cfg(feature = "uuid")]
+        ("uuid::Uuid", TypeKey::of::<uuid::Uuid>()),
+        // every time zone is written the same way, so paths without the zone take the first
+        #[cfg(feature = "chrono")]
+        ("chrono::DateTime", TypeKey::of::<chrono::DateTime<chrono::Utc>>()),
+        #[cfg(feature = "chrono")]
+        ("chrono::DateTime", TypeKey::of::<chrono::DateTime<chrono::FixedOffset>>()),
+        #[cfg(feature = "chrono")]
+        ("chrono::NaiveDateTime", TypeKey::of::<chrono::NaiveDateTime>()),
+        #[cfg(feature = "chrono")]
+        ("chrono::NaiveDate", TypeKey::of::<chrono::NaiveDate>()),
+        #[cfg(feature = "chrono")]
+        ("chrono::NaiveTime", TypeKey::of::<chrono::NaiveTime>()),
+        #[cfg(feature = "time")]
+        ("time::OffsetDateTime", TypeKey::of::<time::OffsetDateTime>()),
+        #[cfg(feature = "time")]
+        ("time::PrimitiveDateTime", TypeKey::of::<time::PrimitiveDateTime>()),
+        #[cfg(feature = "time")]
+        ("time::Date", TypeKey::of::<time::Date>()),
+        #[cfg(feature = "time")]
+        ("time::Time", TypeKey::of::<time::Time>()),
+        #[cfg(feature = "rust_decimal")]
+        ("rust_decimal::Decimal", TypeKey::of::<rust_decimal::Decimal>()),
+        #[cfg(feature = "url")]
+        ("url::Url", TypeKey::of::<url::Url>()),
+        #[cfg(feature = "bytes")]
+        ("bytes::Bytes", TypeKey::of::<bytes::Bytes>()),
+        #[cfg(feature = "bytes")]
+        ("bytes::BytesMut", TypeKey::of::<bytes::BytesMut>()),
+    ])
+}
+
+/// Gets the key of a well-known type from its full path, such as `std::time::Duration` or `uuid::Uuid`
+/// Paths through `core` or `alloc` are treated as going through `std`
+pub fn known_key(path: &str) -> Option<TypeKey> {
+    let segments: Vec<&str> = path.split("::").map(str::trim).filter(|s| !s.is_empty()).collect();
+    let path = match segments.split_first() {
+        Some((&"core" | &"alloc", rest)) => format!("std::{}", rest.join("::")),
+        _ => segments.join("::"),
+    };
+
+    known_types().iter().find(|(known, _)| *known == path).map(|(_, key)| *key)
+}
+
+/// Gets the full path of a well-known type from its key
+pub fn known_path(key: &TypeKey) -> Option<&'static str> {
+    known_types().iter().find(|(_, known)| known == key).map(|(path, _)| *path)
+}
+
+/// Gets the keys of every well-known type the given path could name, matching by trailing segments like `paths_match`
+pub fn known_keys_matching(path: &str) -> impl Iterator<Item = TypeKey> + '_ {
+    known_types().iter().filter(move |(known, _)| paths_match(known, path)).map(|(_, key)| *key)
+}
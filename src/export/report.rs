@@ -0,0 +1,99 @@
+use super::{ExportSummary, FileStatus};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use path_slash::*;
+
+/// Something an export could handle, but probably not the way you intended
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExportWarning {
+    /// A field's type was not registered with the exporter or the target, so its Rust name was written instead
+    UnknownType(String),
+    /// A module had nothing to export, so its file was skipped
+    EmptyModule,
+}
+
+impl Display for ExportWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportWarning::UnknownType(name) => write!(f, "type `{name}` is not registered, falling back to its name"),
+            ExportWarning::EmptyModule => write!(f, "module is empty, skipping"),
+        }
+    }
+}
+
+/// Describes what happened to a single file during an export
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileReport {
+    /// Path relative to the exporter's root
+    pub path: PathBuf,
+    pub status: FileStatus,
+    /// Names of the types written to this file
+    pub types: Vec<String>,
+    pub warnings: Vec<ExportWarning>,
+}
+
+/// Describes everything a single export did
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExportReport {
+    /// The path this export was written to, relative to the exporter's root
+    pub target: PathBuf,
+    pub files: Vec<FileReport>,
+}
+
+impl ExportReport {
+    /// Counts files by their status
+    pub fn summary(&self) -> ExportSummary {
+        let mut summary = ExportSummary::default();
+        for file in &self.files {
+            summary.add(file.status);
+        }
+        summary
+    }
+
+    /// Gets files that were written or deleted
+    pub fn changed(&self) -> impl Iterator<Item = &FileReport> {
+        self.files.iter().filter(|file| matches!(file.status, FileStatus::Written | FileStatus::Deleted))
+    }
+
+    /// Gets all warnings along with the file they were raised for
+    pub fn warnings(&self) -> impl Iterator<Item = (&Path, &ExportWarning)> {
+        self.files.iter().flat_map(|file| file.warnings.iter().map(|warning| (file.path.as_path(), warning)))
+    }
+
+    /// Forwards this report to the `log` or `tracing` facades, if enabled
+    #[allow(unused_variables)]
+    pub(crate) fn emit(&self) {
+        for file in &self.files {
+            let path = file.path.to_slash_lossy();
+            let status = file.status;
+            #[cfg(feature = "log")]
+            log::debug!("{path}: {status:?}");
+            #[cfg(feature = "tracing")]
+            tracing::debug!(%path, ?status, "exported file");
+
+            for warning in &file.warnings {
+                #[cfg(feature = "log")]
+                log::warn!("{path}: {warning}");
+                #[cfg(feature = "tracing")]
+                tracing::warn!(%path, %warning, "export warning");
+            }
+        }
+
+        let target = self.target.to_slash_lossy();
+        let summary = self.summary();
+        #[cfg(feature = "log")]
+        log::info!("{target}: {summary}");
+        #[cfg(feature = "tracing")]
+        tracing::info!(%target, %summary, "export finished");
+    }
+}
+
+impl Display for ExportReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.target.to_slash_lossy(), self.summary())?;
+        for (path, warning) in self.warnings() {
+            write!(f, "\n  warning: {}: {warning}", path.to_slash_lossy())?;
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,20 @@
-use crate::export::Burrxporter;
-use std::path::Path;
+use crate::export::{Burrxporter, ExportError, ExportWarning};
+use std::path::{Path, PathBuf};
 
 pub trait Target {
-    fn export(self, to: &Path, exporter: &Burrxporter);
+    /// Renders every file for this target in memory without touching the filesystem
+    fn render(self, to: &Path, exporter: &Burrxporter) -> Result<Vec<RenderedFile>, ExportError>;
 }
 
+/// A file produced by a target, waiting to be written
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RenderedFile {
+    /// Path relative to the exporter's root
+    pub path: PathBuf,
+    /// Full contents of the file
+    /// Empty files are not written
+    pub contents: String,
+    /// Names of the types written to this file
+    pub types: Vec<String>,
+    pub warnings: Vec<ExportWarning>,
+}
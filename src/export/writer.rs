@@ -0,0 +1,187 @@
+use super::{Burrxporter, ExportError, ExportReport, ExportWarning, FileReport, RenderedFile};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use path_macro::path;
+use path_slash::*;
+
+/// Name of the file that tracks what each export wrote, so stale files can be deleted on the next run
+const MANIFEST_NAME: &str = ".burrtype-manifest";
+
+/// The result of writing a single file
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FileStatus {
+    /// The file was created or its contents changed
+    Written,
+    /// The file already had identical contents and was left untouched
+    Unchanged,
+    /// The file was written by a previous export but is no longer produced
+    Deleted,
+    /// The file had nothing to write
+    Skipped,
+}
+
+/// Counts of files touched by a single export
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExportSummary {
+    pub written: usize,
+    pub unchanged: usize,
+    pub deleted: usize,
+    pub skipped: usize,
+}
+
+impl ExportSummary {
+    pub(crate) fn add(&mut self, status: FileStatus) {
+        match status {
+            FileStatus::Written => self.written += 1,
+            FileStatus::Unchanged => self.unchanged += 1,
+            FileStatus::Deleted => self.deleted += 1,
+            FileStatus::Skipped => self.skipped += 1,
+        }
+    }
+}
+
+impl Display for ExportSummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} written, {} unchanged, {} deleted", self.written, self.unchanged, self.deleted)?;
+        if self.skipped > 0 {
+            write!(f, ", {} skipped", self.skipped)?;
+        }
+        Ok(())
+    }
+}
+
+impl Burrxporter {
+    /// Gets the full path for a file relative to our root
+    pub(crate) fn resolve_path(&self, to: &Path) -> PathBuf {
+        self.root.as_ref().map_or_else(|| to.to_path_buf(), |root| path!(root / to))
+    }
+
+    /// Writes the contents to a file path, unless the file already contains exactly that
+    /// Creates the directory and file if it does not exist
+    /// A dry run only reports what would happen
+    pub(crate) fn write_file(&self, to: &Path, contents: &str, dry_run: bool) -> Result<FileStatus, ExportError> {
+        let path = self.resolve_path(to);
+
+        // Leave the file alone, including its mtime, if nothing changed
+        match fs::read(&path) {
+            Ok(existing) if existing == contents.as_bytes() => return Ok(FileStatus::Unchanged),
+            Ok(_) => {}
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        if dry_run {
+            return Ok(FileStatus::Written);
+        }
+
+        // Extract parent and ensure it exists
+        let parent = match path.parent() {
+            Some(path) => path,
+            None => return Err(ExportError::InvalidTarget),
+        };
+        fs::create_dir_all(parent)?;
+
+        fs::write(path, contents)?;
+        Ok(FileStatus::Written)
+    }
+
+    /// Writes all files rendered for the export at `to`, then deletes files a previous export at `to` wrote but this one did not
+    /// A dry run leaves everything untouched, reporting what would have been written or deleted
+    pub(crate) fn write_files(&self, to: &Path, files: Vec<RenderedFile>, dry_run: bool) -> Result<ExportReport, ExportError> {
+        let mut report = ExportReport {
+            target: to.to_path_buf(),
+            files: Vec::with_capacity(files.len()),
+        };
+        let mut current = BTreeSet::new();
+
+        for RenderedFile { path, contents, types, mut warnings } in files {
+            let status = if contents.is_empty() {
+                warnings.push(ExportWarning::EmptyModule);
+                FileStatus::Skipped
+            }
+            else {
+                current.insert(path.to_slash_lossy().to_string());
+                self.write_file(&path, &contents, dry_run)?
+            };
+
+            report.files.push(FileReport { path, status, types, warnings });
+        }
+
+        let mut manifest = Manifest::load(&self.resolve_path(Path::new(MANIFEST_NAME)))?;
+        let previous = manifest.targets.insert(to.to_slash_lossy().to_string(), current.clone()).unwrap_or_default();
+
+        for stale in previous.difference(&current) {
+            let path = PathBuf::from_slash(stale);
+            let full_path = self.resolve_path(&path);
+            let removed = if dry_run {
+                fs::metadata(&full_path).map(|_| ())
+            }
+            else {
+                fs::remove_file(&full_path)
+            };
+
+            match removed {
+                Ok(()) => {
+                    // Clean up directories we may have emptied, ignoring any that still have files
+                    if let Some(parent) = full_path.parent().filter(|_| !dry_run) {
+                        let _ = fs::remove_dir(parent);
+                    }
+                    report.files.push(FileReport {
+                        path,
+                        status: FileStatus::Deleted,
+                        types: Vec::new(),
+                        warnings: Vec::new(),
+                    });
+                }
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        if !dry_run {
+            self.write_file(Path::new(MANIFEST_NAME), &manifest.to_string(), false)?;
+        }
+
+        Ok(report)
+    }
+}
+
+/// Tracks which files each export target wrote
+/// Stored as `target\tfile` lines next to the exported files
+#[derive(Default)]
+struct Manifest {
+    targets: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Manifest {
+    fn load(path: &Path) -> Result<Self, ExportError> {
+        let mut manifest = Manifest::default();
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(manifest),
+            Err(e) => return Err(e.into()),
+        };
+
+        for line in text.lines() {
+            if let Some((target, file)) = line.split_once('\t') {
+                manifest.targets.entry(target.to_string()).or_default().insert(file.to_string());
+            }
+        }
+
+        Ok(manifest)
+    }
+}
+
+impl Display for Manifest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (target, files) in &self.targets {
+            for file in files {
+                writeln!(f, "{target}\t{file}")?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,8 +1,18 @@
 pub mod export;
+#[cfg(feature = "build")]
+pub mod build;
+#[cfg(feature = "config")]
+pub mod config;
+#[cfg(feature = "runner")]
+pub mod runner;
+#[cfg(feature = "schema")]
+pub mod schema;
+#[cfg(feature = "watch")]
+pub mod watch;
 pub mod targets;
 pub mod prelude;
 
-pub use prelude::Burr;
+pub use prelude::{burr, burrmod, Burr};
 // these re-exports are necessary for the proc macro to work without requiring the user to include them as dependencies
 #[doc(hidden)]
 pub use burrtype_internal::ir;
@@ -12,6 +22,10 @@ pub use syn;
 pub use quote;
 #[doc(hidden)]
 pub use linkme;
+#[doc(hidden)]
+pub use serde_json;
+#[doc(hidden)]
+pub mod probe;
 
 #[linkme::distributed_slice]
 pub static TYPES: [fn() -> ir::IrItem] = [..];
\ No newline at end of file
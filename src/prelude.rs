@@ -1,4 +1,4 @@
 pub use crate::export::{BurrMod, Burrxporter};
 #[cfg(feature = "typescript")]
 pub use crate::targets::typescript::*;
-pub use burrtype_derive::Burr;
\ No newline at end of file
+pub use burrtype_derive::{burr, burrmod, Burr};
\ No newline at end of file
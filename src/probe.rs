@@ -0,0 +1,33 @@
+//! Lets generated code ask how a type serializes without requiring it to implement `Serialize`
+//! Relies on autoref specialization, so it only works where the type is named concretely, as in derived code
+//!
+//! ```ignore
+//! use burrtype::probe::{Probe, SerializeProbe, FallbackProbe};
+//! let is_int = (&&Probe(&Status::Ok)).serializes_as_int();
+//! ```
+
+use serde::Serialize;
+
+pub struct Probe<'a, T>(pub &'a T);
+
+/// Picked when the value implements `Serialize`
+pub trait SerializeProbe {
+    fn serializes_as_int(&self) -> bool;
+}
+
+impl<T: Serialize> SerializeProbe for &Probe<'_, T> {
+    fn serializes_as_int(&self) -> bool {
+        serde_json::to_value(self.0).is_ok_and(|value| value.is_number())
+    }
+}
+
+/// Picked for anything else
+pub trait FallbackProbe {
+    fn serializes_as_int(&self) -> bool;
+}
+
+impl<T> FallbackProbe for Probe<'_, T> {
+    fn serializes_as_int(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,110 @@
+//! Entry point for the exporter binaries generated by `cargo burrtype`
+//! These binaries link against the user's crate, so every `#[derive(Burr)]` type is registered
+//!
+//! Invoked as `<binary> <generate|check|list|schema> <path to burrtype.toml> [args...]`
+
+use crate::export::{BurrMod, Burrxporter, ExportError, FileStatus};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// Runs the command given on the command line
+pub fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let (Some(command), Some(config)) = (args.next(), args.next()) else {
+        eprintln!("usage: <generate|check|list|schema> <config> [args...]");
+        return ExitCode::FAILURE;
+    };
+    let args: Vec<String> = args.collect();
+
+    match run(&command, Path::new(&config), &args) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Runs a single command against the config at the given path
+/// `schema` takes an optional path to write to, and prints the schema otherwise
+pub fn run(command: &str, config: &Path, args: &[String]) -> Result<ExitCode, ExportError> {
+    let exporter = Burrxporter::from_config(config)?;
+
+    match command {
+        "generate" => {
+            for report in exporter.export_all()? {
+                println!("{report}");
+                for file in report.changed() {
+                    let action = if file.status == FileStatus::Deleted { "deleted" } else { "wrote" };
+                    println!("  {action} {}", file.path.display());
+                }
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        "check" => {
+            let mut stale = 0;
+            for report in exporter.check_all()? {
+                for file in report.changed() {
+                    let action = if file.status == FileStatus::Deleted { "would delete" } else { "would write" };
+                    println!("{}: {action} {}", report.target.display(), file.path.display());
+                    stale += 1;
+                }
+            }
+
+            if stale == 0 {
+                println!("all exports are up to date");
+                Ok(ExitCode::SUCCESS)
+            }
+            else {
+                println!("{stale} file(s) are out of date, run `cargo burrtype generate` to update them");
+                Ok(ExitCode::FAILURE)
+            }
+        }
+        "list" => {
+            list(&exporter);
+            Ok(ExitCode::SUCCESS)
+        }
+        "schema" => {
+            let schema = exporter.to_schema();
+            match args.first() {
+                Some(path) => {
+                    schema.save(path)?;
+                    println!("wrote schema to {path}");
+                }
+                None => println!("{}", schema.to_json()),
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        _ => {
+            eprintln!("unknown command `{command}`, expected one of generate, check, list, schema");
+            Ok(ExitCode::FAILURE)
+        }
+    }
+}
+
+/// Prints every registered type along with the module it was assigned to
+fn list(exporter: &Burrxporter) {
+    fn walk(bm: &BurrMod, path: PathBuf, lines: &mut Vec<(String, &'static str)>) {
+        let path = path.join(&bm.name);
+        let module = path.to_string_lossy().replace('\\', "/");
+        for id in bm.exports.iter().chain(&bm.auto_exports) {
+            if let Some(ir) = bm.types.get(id) {
+                lines.push((module.clone(), ir.path()));
+            }
+        }
+        for child in &bm.children {
+            walk(child, path.clone(), lines);
+        }
+    }
+
+    let mut lines = Vec::new();
+    for bm in &exporter.mods {
+        walk(bm, PathBuf::new(), &mut lines);
+    }
+    lines.sort();
+
+    let width = lines.iter().map(|(module, _)| module.len()).max().unwrap_or_default();
+    for (module, path) in lines {
+        println!("{module:width$}  {path}");
+    }
+}
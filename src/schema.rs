@@ -0,0 +1,817 @@
+//! A portable description of everything an exporter knows
+//!
+//! The IR holds `TypeId`s and `syn` types which cannot leave the process, so the schema replaces them with strings:
+//! - Items are identified by their Rust path, such as `my_crate::api::User`
+//! - Fields name the item they refer to by that path, alongside the path they were written with
+//!
+//! Loading a schema rebuilds IR keyed by those paths, so targets work as they would with types from `#[derive(Burr)]`
+//! Types registered by `TypeId`, such as with `TypeScript::with_type_name`, need to be given by path instead, see `TypeScript::with_path_name`
+//! Well-known types, such as `std::time::Duration` or `uuid::Uuid` with its cargo feature, are recorded by their full path and keyed by `TypeId` again
+//!
+//! Two schemas can be compared with `diff` to find changes that would break consumers
+
+mod diff;
+
+pub use diff::*;
+
+use crate::export::{BurrMod, Burrxporter, ExportError, known_key, known_path};
+use burrtype_internal::ir::*;
+use proc_macro2::Ident;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use quote::ToTokens;
+use syn::ext::IdentExt;
+use syn::parse::Parser;
+
+/// The version written by this release
+/// Bumped whenever the format changes in a way older loaders would misread
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Everything an exporter knows about its types and modules
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Schema {
+    pub version: u32,
+    #[serde(default)]
+    pub mods: Vec<ModSchema>,
+    /// Every known item, sorted by path
+    #[serde(default)]
+    pub items: Vec<ItemSchema>,
+}
+
+/// A module and the paths of the items it exports
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModSchema {
+    pub name: String,
+    /// Items added explicitly
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exports: Vec<String>,
+    /// Items added while resolving
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub auto_exports: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<ModSchema>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ItemSchema {
+    NamedStruct {
+        #[serde(flatten)]
+        info: ItemInfo,
+        /// Set by `#[serde(tag = "...")]`, the field holding the struct's name
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tag: Option<String>,
+        fields: Vec<NamedFieldSchema>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        flattened: Vec<FlattenedSchema>,
+    },
+    TupleStruct {
+        #[serde(flatten)]
+        info: ItemInfo,
+        fields: Vec<UnnamedFieldSchema>,
+    },
+    UnitStruct {
+        #[serde(flatten)]
+        info: ItemInfo,
+    },
+    Enum {
+        #[serde(flatten)]
+        info: ItemInfo,
+        repr: ReprSchema,
+        /// Set by `#[burr(enum_style = "...")]`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        style: Option<EnumStyle>,
+        variants: Vec<VariantSchema>,
+    },
+    Const {
+        #[serde(flatten)]
+        info: ItemInfo,
+        #[serde(rename = "type")]
+        ty: TypeSchema,
+        value: serde_json::Value,
+    },
+    Alias {
+        #[serde(flatten)]
+        info: ItemInfo,
+        #[serde(rename = "type")]
+        ty: TypeSchema,
+        /// The type read instead, for types serde reads through another, such as with `#[serde(from = "T")]`
+        #[serde(rename = "de_type", default, skip_serializing_if = "Option::is_none")]
+        de_ty: Option<TypeSchema>,
+    },
+}
+
+/// Describes any item
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemInfo {
+    /// The exported name
+    pub name: String,
+    /// Full Rust path
+    pub path: String,
+    /// Set by `#[burr(mod = "...")]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub module: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReprSchema {
+    External,
+    Untagged,
+    Internal { tag: String },
+    Adjacent { tag: String, content: String },
+    /// Serialized as each variant's discriminant
+    Int,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum VariantSchema {
+    Struct {
+        name: String,
+        /// Name it is read with, when that differs from `name`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        de_name: Option<String>,
+        /// Other names it is read with
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        aliases: Vec<String>,
+        /// The way it is left out, when it is only skipped one way
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        skip: Option<Direction>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        docs: Option<String>,
+        fields: Vec<NamedFieldSchema>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        flattened: Vec<FlattenedSchema>,
+    },
+    Tuple {
+        name: String,
+        /// Name it is read with, when that differs from `name`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        de_name: Option<String>,
+        /// Other names it is read with
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        aliases: Vec<String>,
+        /// The way it is left out, when it is only skipped one way
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        skip: Option<Direction>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        docs: Option<String>,
+        fields: Vec<UnnamedFieldSchema>,
+    },
+    Unit {
+        name: String,
+        /// Name it is read with, when that differs from `name`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        de_name: Option<String>,
+        /// Other names it is read with
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        aliases: Vec<String>,
+        /// The way it is left out, when it is only skipped one way
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        skip: Option<Direction>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        docs: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none", with = "discriminant")]
+        discriminant: Option<i128>,
+        /// Read in place of unknown variants
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        other: bool,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamedFieldSchema {
+    pub name: String,
+    /// Name it is read with, when that differs from `name`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub de_name: Option<String>,
+    /// Other names it is read with
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// The way it is left out, when it is only skipped one way
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip: Option<Direction>,
+    #[serde(rename = "type")]
+    pub ty: TypeSchema,
+    /// The type read instead, for fields read through another helper, such as with `#[serde(deserialize_with = "...")]`
+    #[serde(rename = "de_type", default, skip_serializing_if = "Option::is_none")]
+    pub de_ty: Option<TypeSchema>,
+    #[serde(flatten)]
+    pub literals: LiteralsSchema,
+    /// Set by `#[burr(int_style = "...")]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub int_style: Option<IntStyle>,
+    /// May be left out when deserializing
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub default: bool,
+    /// May be left out when serializing
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skip_serializing_if: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs: Option<String>,
+}
+
+impl NamedFieldSchema {
+    /// Name the field is read with
+    pub fn read_name(&self) -> &str {
+        self.de_name.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// Types written as is in place of a field's own
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LiteralsSchema {
+    /// Written by every target without one of its own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub literal: Option<String>,
+    /// Written by a single target, keyed by its name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub target_literals: BTreeMap<String, String>,
+}
+
+impl LiteralsSchema {
+    /// Gets the type written as is by a target, if any
+    pub fn get(&self, target: &str) -> Option<&str> {
+        self.target_literals.get(target).or(self.literal.as_ref()).map(String::as_str)
+    }
+}
+
+/// Something flattened into a struct with `#[serde(flatten)]` that keeps its own shape
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FlattenedSchema {
+    Enum {
+        #[serde(rename = "type")]
+        ty: TypeSchema,
+    },
+    /// Holds every key the struct's fields do not
+    Map,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnnamedFieldSchema {
+    #[serde(rename = "type")]
+    pub ty: TypeSchema,
+    #[serde(flatten)]
+    pub literals: LiteralsSchema,
+    /// Set by `#[burr(int_style = "...")]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub int_style: Option<IntStyle>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs: Option<String>,
+}
+
+/// The type of a field
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypeSchema {
+    /// The path as written in the field, such as `Decimal` or `HashMap<String, u32>`
+    pub path: String,
+    /// Full Rust path of the item this refers to, if it is one we know about
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item: Option<String>,
+    /// Full Rust path of the well-known type this refers to, such as `std::time::Duration` or `uuid::Uuid`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub known: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub array: bool,
+}
+
+impl Schema {
+    /// Reads a schema from JSON, rejecting versions we do not understand
+    pub fn from_json(json: &str) -> Result<Self, ExportError> {
+        #[derive(Deserialize)]
+        struct Version {
+            version: u32,
+        }
+
+        let Version { version } = serde_json::from_str(json).map_err(|e| ExportError::Schema(e.to_string()))?;
+        if version != SCHEMA_VERSION {
+            return Err(ExportError::SchemaVersion { found: version, expected: SCHEMA_VERSION });
+        }
+
+        serde_json::from_str(json).map_err(|e| ExportError::Schema(e.to_string()))
+    }
+
+    /// Writes this schema as pretty-printed JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("schemas are always representable as JSON")
+    }
+
+    /// Reads a schema from a JSON file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ExportError> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Writes this schema to a JSON file, leaving it untouched if nothing changed
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), ExportError> {
+        let path = path.as_ref();
+        let json = self.to_json();
+        if std::fs::read_to_string(path).is_ok_and(|existing| existing == json) {
+            return Ok(());
+        }
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Finds an item by its full Rust path
+    pub fn item(&self, path: &str) -> Option<&ItemSchema> {
+        self.items.iter().find(|item| item.info().path == path)
+    }
+}
+
+impl ItemSchema {
+    pub fn info(&self) -> &ItemInfo {
+        match self {
+            ItemSchema::NamedStruct { info, .. } => info,
+            ItemSchema::TupleStruct { info, .. } => info,
+            ItemSchema::UnitStruct { info } => info,
+            ItemSchema::Enum { info, .. } => info,
+            ItemSchema::Const { info, .. } => info,
+            ItemSchema::Alias { info, .. } => info,
+        }
+    }
+}
+
+impl VariantSchema {
+    pub fn name(&self) -> &str {
+        match self {
+            VariantSchema::Struct { name, .. } => name,
+            VariantSchema::Tuple { name, .. } => name,
+            VariantSchema::Unit { name, .. } => name,
+        }
+    }
+
+    /// Name the variant is read with
+    pub fn read_name(&self) -> &str {
+        match self {
+            VariantSchema::Struct { name, de_name, .. }
+            | VariantSchema::Tuple { name, de_name, .. }
+            | VariantSchema::Unit { name, de_name, .. } => de_name.as_deref().unwrap_or(name),
+        }
+    }
+
+    pub fn aliases(&self) -> &[String] {
+        match self {
+            VariantSchema::Struct { aliases, .. }
+            | VariantSchema::Tuple { aliases, .. }
+            | VariantSchema::Unit { aliases, .. } => aliases,
+        }
+    }
+
+    pub fn skip(&self) -> Option<Direction> {
+        match self {
+            VariantSchema::Struct { skip, .. } => *skip,
+            VariantSchema::Tuple { skip, .. } => *skip,
+            VariantSchema::Unit { skip, .. } => *skip,
+        }
+    }
+}
+
+impl Burrxporter {
+    /// Describes this exporter's modules and types in a portable format
+    /// The root is left out, since where files are written is up to whoever loads the schema
+    pub fn to_schema(&self) -> Schema {
+        // Items in modules may have been added without being registered
+        let mut items: HashMap<TypeKey, &IrItem> = self.type_registry.iter().map(|(key, item)| (*key, item)).collect();
+        fn collect<'a>(bm: &'a BurrMod, items: &mut HashMap<TypeKey, &'a IrItem>) {
+            items.extend(bm.types.iter().map(|(key, item)| (*key, item)));
+            for child in &bm.children {
+                collect(child, items);
+            }
+        }
+        for bm in &self.mods {
+            collect(bm, &mut items);
+        }
+
+        let paths: HashMap<TypeKey, &'static str> = items.iter().map(|(key, item)| (*key, item.path())).collect();
+        let writer = SchemaWriter { paths: &paths };
+
+        let mut items: Vec<ItemSchema> = items.values().map(|item| writer.item(item)).collect();
+        items.sort_by(|a, b| a.info().path.cmp(&b.info().path));
+
+        Schema {
+            version: SCHEMA_VERSION,
+            mods: self.mods.iter().map(|bm| writer.module(bm)).collect(),
+            items,
+        }
+    }
+
+    /// Rebuilds an exporter from a schema
+    /// Items are keyed by their path, since their `TypeId` cannot be recovered
+    pub fn from_schema(schema: &Schema) -> Result<Self, ExportError> {
+        let items = schema.items.iter().map(read_item).collect::<Result<Vec<_>, _>>()?;
+        let mut exporter = Burrxporter::from_items(items);
+        exporter.mods = schema.mods.iter().map(|bm| read_module(bm, &exporter)).collect::<Result<_, _>>()?;
+        Ok(exporter)
+    }
+}
+
+/// Gets docs as an owned string, if they were compiled in
+macro_rules! docs {
+    ($ir:expr) => {{
+        #[cfg(feature = "comments")]
+        let docs = $ir.docs.map(str::to_string);
+        #[cfg(not(feature = "comments"))]
+        let docs = None;
+        docs
+    }};
+}
+struct SchemaWriter<'a> {
+    paths: &'a HashMap<TypeKey, &'static str>,
+}
+
+impl SchemaWriter<'_> {
+    fn module(&self, bm: &BurrMod) -> ModSchema {
+        let paths = |ids: &[TypeKey]| ids.iter().filter_map(|id| bm.types.get(id)).map(|item| item.path().to_string()).collect();
+        ModSchema {
+            name: bm.name.clone(),
+            exports: paths(&bm.exports),
+            auto_exports: paths(&bm.auto_exports),
+            children: bm.children.iter().map(|child| self.module(child)).collect(),
+        }
+    }
+
+    fn item(&self, item: &IrItem) -> ItemSchema {
+        match item {
+            IrItem::NamedStruct(ir) => ItemSchema::NamedStruct {
+                info: info(&ir.ident, ir.path, ir.r#mod, docs!(ir)),
+                tag: ir.tag.as_ref().map(|tag| tag.field.to_string()),
+                fields: ir.fields.iter().map(|field| self.named_field(field)).collect(),
+                flattened: self.flattened(&ir.flattened),
+            },
+            IrItem::TupleStruct(ir) => ItemSchema::TupleStruct {
+                info: info(&ir.ident, ir.path, ir.r#mod, docs!(ir)),
+                fields: ir.fields.iter().map(|field| self.unnamed_field(field)).collect(),
+            },
+            IrItem::UnitStruct(ir) => ItemSchema::UnitStruct {
+                info: info(&ir.ident, ir.path, ir.r#mod, docs!(ir)),
+            },
+            IrItem::Enum(ir) => ItemSchema::Enum {
+                info: info(&ir.ident, ir.path, ir.r#mod, docs!(ir)),
+                repr: match ir.repr {
+                    EnumRepr::External => ReprSchema::External,
+                    EnumRepr::Untagged => ReprSchema::Untagged,
+                    EnumRepr::Internal(tag) => ReprSchema::Internal { tag: tag.to_string() },
+                    EnumRepr::Adjacent { tag, content } => ReprSchema::Adjacent { tag: tag.to_string(), content: content.to_string() },
+                    EnumRepr::Int => ReprSchema::Int,
+                },
+                style: ir.style,
+                variants: ir.variants.iter().map(|var| match var {
+                    IrEnumVariant::Struct(var) => VariantSchema::Struct {
+                        name: var.ident.unraw().to_string(),
+                        de_name: var.de_ident.as_ref().map(|ident| ident.unraw().to_string()),
+                        aliases: var.aliases.iter().map(|ident| ident.unraw().to_string()).collect(),
+                        skip: var.skip,
+                        docs: docs!(var),
+                        fields: var.fields.iter().map(|field| self.named_field(field)).collect(),
+                        flattened: self.flattened(&var.flattened),
+                    },
+                    IrEnumVariant::Tuple(var) => VariantSchema::Tuple {
+                        name: var.ident.unraw().to_string(),
+                        de_name: var.de_ident.as_ref().map(|ident| ident.unraw().to_string()),
+                        aliases: var.aliases.iter().map(|ident| ident.unraw().to_string()).collect(),
+                        skip: var.skip,
+                        docs: docs!(var),
+                        fields: var.fields.iter().map(|field| self.unnamed_field(field)).collect(),
+                    },
+                    IrEnumVariant::Unit(var) => VariantSchema::Unit {
+                        name: var.ident.unraw().to_string(),
+                        de_name: var.de_ident.as_ref().map(|ident| ident.unraw().to_string()),
+                        aliases: var.aliases.iter().map(|ident| ident.unraw().to_string()).collect(),
+                        skip: var.skip,
+                        docs: docs!(var),
+                        discriminant: var.discriminant,
+                        other: var.other,
+                    },
+                }).collect(),
+            },
+            IrItem::Const(ir) => ItemSchema::Const {
+                info: info(&ir.ident, ir.path, ir.r#mod, docs!(ir)),
+                ty: self.ty(&ir.ty),
+                value: serde_json::from_str(&ir.value).expect("constants are serialized as JSON"),
+            },
+            IrItem::Alias(ir) => ItemSchema::Alias {
+                info: info(&ir.ident, ir.path, ir.r#mod, docs!(ir)),
+                ty: self.ty(&ir.ty),
+                de_ty: ir.de_ty.as_ref().map(|ty| self.ty(ty)),
+            },
+        }
+    }
+
+    fn named_field(&self, field: &IrNamedField) -> NamedFieldSchema {
+        NamedFieldSchema {
+            name: field.ident.unraw().to_string(),
+            de_name: field.de_ident.as_ref().map(|ident| ident.unraw().to_string()),
+            aliases: field.aliases.iter().map(|ident| ident.unraw().to_string()).collect(),
+            skip: field.skip,
+            ty: self.ty(&field.ty),
+            de_ty: field.de_ty.as_ref().map(|ty| self.ty(ty)),
+            literals: literals(&field.literals),
+            int_style: field.int_style,
+            default: field.default,
+            skip_serializing_if: field.skip_serializing_if,
+            docs: docs!(field),
+        }
+    }
+
+    fn unnamed_field(&self, field: &IrUnnamedField) -> UnnamedFieldSchema {
+        UnnamedFieldSchema {
+            ty: self.ty(&field.ty),
+            literals: literals(&field.literals),
+            int_style: field.int_style,
+            docs: docs!(field),
+        }
+    }
+
+    fn flattened(&self, flattened: &[IrFlattened]) -> Vec<FlattenedSchema> {
+        flattened.iter()
+            .map(|flattened| match flattened {
+                IrFlattened::Enum(ty) => FlattenedSchema::Enum { ty: self.ty(ty) },
+                IrFlattened::Map => FlattenedSchema::Map,
+            })
+            .collect()
+    }
+
+    fn ty(&self, ty: &IrType) -> TypeSchema {
+        TypeSchema {
+            path: type_path_string(&ty.path),
+            item: self.paths.get(&ty.id).map(|path| path.to_string()),
+            known: known_path(&ty.id).map(str::to_string),
+            optional: ty.optional,
+            array: ty.array,
+        }
+    }
+}
+
+/// Writes discriminants as numbers while they fit in an `i64` or `u64`, and as text past that
+/// Serde cannot hold an `i128` while it looks for the tag of a `VariantSchema`, so only these are ever read
+mod discriminant {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(untagged)]
+    enum Wire {
+        Signed(i64),
+        Unsigned(u64),
+        Text(String),
+    }
+
+    pub fn serialize<S: Serializer>(value: &Option<i128>, serializer: S) -> Result<S::Ok, S::Error> {
+        value.map(|value| {
+            i64::try_from(value).map(Wire::Signed)
+                .or_else(|_| u64::try_from(value).map(Wire::Unsigned))
+                .unwrap_or_else(|_| Wire::Text(value.to_string()))
+        }).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<i128>, D::Error> {
+        Ok(match Option::<Wire>::deserialize(deserializer)? {
+            Some(Wire::Signed(value)) => Some(value.into()),
+            Some(Wire::Unsigned(value)) => Some(value.into()),
+            Some(Wire::Text(text)) => Some(text.parse().map_err(D::Error::custom)?),
+            None => None,
+        })
+    }
+}
+
+fn literals(literals: &IrLiterals) -> LiteralsSchema {
+    LiteralsSchema {
+        literal: literals.any.map(str::to_string),
+        target_literals: literals.targets.iter().map(|(target, ty)| (target.to_string(), ty.to_string())).collect(),
+    }
+}
+
+fn info(ident: &Ident, path: &str, module: Option<&str>, docs: Option<String>) -> ItemInfo {
+    ItemInfo {
+        name: ident.unraw().to_string(),
+        path: path.to_string(),
+        module: module.map(str::to_string),
+        docs,
+    }
+}
+
+/// Writes a type path the way it would usually be written by hand, such as `HashMap<String, u32>`
+fn type_path_string(path: &syn::TypePath) -> String {
+    path.to_token_stream().to_string()
+        .replace(" :: ", "::")
+        .replace(":: ", "::")
+        .replace(" < ", "<")
+        .replace(" <", "<")
+        .replace(" >", ">")
+        .replace(" , ", ", ")
+}
+
+fn read_module(schema: &ModSchema, exporter: &Burrxporter) -> Result<BurrMod, ExportError> {
+    let mut bm = BurrMod::new(&schema.name);
+    for (paths, ids) in [(&schema.exports, &mut bm.exports), (&schema.auto_exports, &mut bm.auto_exports)] {
+        for path in paths {
+            let key = TypeKey::Path(leak_str(path.as_str()));
+            let item = exporter.type_registry.get(&key).ok_or_else(|| ExportError::UnknownPath(path.clone()))?;
+            ids.push(key);
+            bm.types.insert(key, item.clone());
+        }
+    }
+    for child in &schema.children {
+        bm.children.push(read_module(child, exporter)?);
+    }
+    Ok(bm)
+}
+
+#[allow(unused_variables)]
+fn read_item(schema: &ItemSchema) -> Result<IrItem, ExportError> {
+    let ItemInfo { name, path, module, docs } = schema.info();
+    let ident = ident(name)?;
+    let path = leak_str(path.as_str());
+    let id = TypeKey::Path(path);
+    let r#mod = module.as_deref().map(leak_str);
+    #[cfg(feature = "comments")]
+    let docs = docs.as_deref().map(leak_str);
+
+    Ok(match schema {
+        ItemSchema::NamedStruct { tag, fields, flattened, .. } => IrNamedStruct {
+            ident,
+            id,
+            path,
+            fields: fields.iter().map(read_named_field).collect::<Result<_, _>>()?,
+            flattened: flattened.iter().map(read_flattened).collect::<Result<_, _>>()?,
+            tag: tag.as_deref().map(|field| IrStructTag {
+                field: leak_str(field),
+                value: leak_str(name.as_str()),
+            }),
+            #[cfg(feature = "comments")]
+            docs,
+            r#mod,
+        }.into(),
+        ItemSchema::TupleStruct { fields, .. } => IrTupleStruct {
+            ident,
+            id,
+            path,
+            fields: fields.iter().map(read_unnamed_field).collect::<Result<_, _>>()?,
+            #[cfg(feature = "comments")]
+            docs,
+            r#mod,
+        }.into(),
+        ItemSchema::UnitStruct { .. } => IrUnitStruct {
+            ident,
+            id,
+            path,
+            #[cfg(feature = "comments")]
+            docs,
+            r#mod,
+        }.into(),
+        ItemSchema::Enum { repr, style, variants, .. } => IrEnum {
+            ident,
+            id,
+            path,
+            variants: variants.iter().map(read_variant).collect::<Result<_, _>>()?,
+            repr: match repr {
+                ReprSchema::External => EnumRepr::External,
+                ReprSchema::Untagged => EnumRepr::Untagged,
+                ReprSchema::Internal { tag } => EnumRepr::Internal(leak_str(tag.as_str())),
+                ReprSchema::Adjacent { tag, content } => EnumRepr::Adjacent {
+                    tag: leak_str(tag.as_str()),
+                    content: leak_str(content.as_str()),
+                },
+                ReprSchema::Int => EnumRepr::Int,
+            },
+            style: *style,
+            #[cfg(feature = "comments")]
+            docs,
+            r#mod,
+        }.into(),
+        ItemSchema::Const { ty, value, .. } => IrConst {
+            ident,
+            id,
+            path,
+            ty: read_type(ty)?,
+            value: value.to_string(),
+            #[cfg(feature = "comments")]
+            docs,
+            r#mod,
+        }.into(),
+        // Fields were pointed at the alias before the schema was written, so they already refer to it by path
+        ItemSchema::Alias { ty, de_ty, .. } => IrAlias {
+            ident,
+            id,
+            path,
+            ty: read_type(ty)?,
+            de_ty: de_ty.as_ref().map(read_type).transpose()?,
+            target: id,
+            #[cfg(feature = "comments")]
+            docs,
+            r#mod,
+        }.into(),
+    })
+}
+
+#[allow(unused_variables)]
+fn read_variant(schema: &VariantSchema) -> Result<IrEnumVariant, ExportError> {
+    Ok(match schema {
+        VariantSchema::Struct { name, de_name, aliases, skip, docs, fields, flattened } => IrEnumStructVariant {
+            ident: ident(name)?,
+            de_ident: de_name.as_deref().map(ident).transpose()?,
+            aliases: aliases.iter().map(|name| ident(name)).collect::<Result<_, _>>()?,
+            skip: *skip,
+            fields: fields.iter().map(read_named_field).collect::<Result<_, _>>()?,
+            flattened: flattened.iter().map(read_flattened).collect::<Result<_, _>>()?,
+            #[cfg(feature = "comments")]
+            docs: docs.as_deref().map(leak_str),
+        }.into(),
+        VariantSchema::Tuple { name, de_name, aliases, skip, docs, fields } => IrEnumTupleVariant {
+            ident: ident(name)?,
+            de_ident: de_name.as_deref().map(ident).transpose()?,
+            aliases: aliases.iter().map(|name| ident(name)).collect::<Result<_, _>>()?,
+            skip: *skip,
+            fields: fields.iter().map(read_unnamed_field).collect::<Result<_, _>>()?,
+            #[cfg(feature = "comments")]
+            docs: docs.as_deref().map(leak_str),
+        }.into(),
+        VariantSchema::Unit { name, de_name, aliases, skip, docs, discriminant, other } => IrEnumUnitVariant {
+            ident: ident(name)?,
+            de_ident: de_name.as_deref().map(ident).transpose()?,
+            aliases: aliases.iter().map(|name| ident(name)).collect::<Result<_, _>>()?,
+            skip: *skip,
+            discriminant: *discriminant,
+            other: *other,
+            #[cfg(feature = "comments")]
+            docs: docs.as_deref().map(leak_str),
+        }.into(),
+    })
+}
+
+fn read_named_field(schema: &NamedFieldSchema) -> Result<IrNamedField, ExportError> {
+    Ok(IrNamedField {
+        ident: ident(&schema.name)?,
+        de_ident: schema.de_name.as_deref().map(ident).transpose()?,
+        aliases: schema.aliases.iter().map(|name| ident(name)).collect::<Result<_, _>>()?,
+        ty: read_type(&schema.ty)?,
+        de_ty: schema.de_ty.as_ref().map(read_type).transpose()?,
+        literals: read_literals(&schema.literals),
+        int_style: schema.int_style,
+        skip: schema.skip,
+        default: schema.default,
+        skip_serializing_if: schema.skip_serializing_if,
+        #[cfg(feature = "comments")]
+        docs: schema.docs.as_deref().map(leak_str),
+    })
+}
+
+fn read_unnamed_field(schema: &UnnamedFieldSchema) -> Result<IrUnnamedField, ExportError> {
+    Ok(IrUnnamedField {
+        ty: read_type(&schema.ty)?,
+        literals: read_literals(&schema.literals),
+        int_style: schema.int_style,
+        #[cfg(feature = "comments")]
+        docs: schema.docs.as_deref().map(leak_str),
+    })
+}
+
+fn read_literals(schema: &LiteralsSchema) -> IrLiterals {
+    IrLiterals {
+        any: schema.literal.as_deref().map(leak_str),
+        targets: schema.target_literals.iter().map(|(target, ty)| (leak_str(target.as_str()), leak_str(ty.as_str()))).collect(),
+    }
+}
+
+fn read_flattened(schema: &FlattenedSchema) -> Result<IrFlattened, ExportError> {
+    Ok(match schema {
+        FlattenedSchema::Enum { ty } => IrFlattened::Enum(read_type(ty)?),
+        FlattenedSchema::Map => IrFlattened::Map,
+    })
+}
+
+fn read_type(schema: &TypeSchema) -> Result<IrType, ExportError> {
+    let path: syn::TypePath = syn::parse_str(&schema.path)
+        .map_err(|e| ExportError::Schema(format!("invalid type `{}`: {e}", schema.path)))?;
+    let id = match (&schema.item, &schema.known) {
+        (Some(item), _) => TypeKey::Path(leak_str(item.as_str())),
+        (None, Some(known)) => known_key(known).unwrap_or_else(|| TypeKey::Path(leak_str(known.as_str()))),
+        (None, None) => {
+            let written = path.path.segments.iter().map(|segment| segment.ident.to_string()).collect::<Vec<_>>().join("::");
+            TypeKey::from_std_path(&written).unwrap_or_else(|| TypeKey::Path(leak_str(written)))
+        }
+    };
+
+    Ok(IrType {
+        path,
+        id,
+        optional: schema.optional,
+        array: schema.array,
+    })
+}
+
+/// Recreates an identifier
+/// Names are stored without any `r#` prefix, since they describe exported names rather than Rust syntax
+fn ident(name: &str) -> Result<Ident, ExportError> {
+    Ident::parse_any.parse_str(name).map_err(|_| ExportError::Schema(format!("invalid identifier `{name}`")))
+}
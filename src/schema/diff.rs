@@ -0,0 +1,631 @@
+use super::*;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+
+/// Whether a change can break consumers of the JSON we produce or accept
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Breaking,
+    NonBreaking,
+}
+
+/// What changed between two schemas
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ChangeKind {
+    ItemAdded,
+    ItemRemoved,
+    /// The exported name changed, so generated code referring to it breaks even though the JSON does not
+    ItemRenamed { from: String, to: String },
+    /// The Rust path changed but the item looks the same
+    ItemMoved { from: String, to: String },
+    /// Such as a struct becoming an enum
+    ItemKindChanged { from: String, to: String },
+    /// The item is written to another module
+    ModuleChanged { from: Option<String>, to: Option<String> },
+    /// An enum is tagged differently
+    ReprChanged { from: String, to: String },
+    /// A struct's name is written under another field, or no longer is
+    TagChanged { from: Option<String>, to: Option<String> },
+    FieldAdded { optional: bool },
+    FieldRemoved,
+    FieldTypeChanged { from: String, to: String },
+    /// A field is written as another type by one target only, such as with `#[burr(ts = "...")]`
+    TargetTypeChanged { target: String, from: String, to: String },
+    /// A field became optional or required
+    OptionalityChanged { optional: bool },
+    /// A field can now be left out of what we accept, or no longer can
+    DefaultChanged { default: bool },
+    /// A field may now be left out of what we produce, or no longer will be
+    SkipChanged { skipped: bool },
+    /// A field or variant is read under another name, while still written as before
+    ReadNameChanged { from: String, to: String },
+    /// A field or variant is skipped another way, such as by `#[serde(skip_serializing)]`
+    SkippedChanged { from: Option<Direction>, to: Option<Direction> },
+    /// A field or variant can also be read under another name
+    AliasAdded { alias: String },
+    AliasRemoved { alias: String },
+    /// Unknown variants are now read as this one, or no longer are
+    CatchAllChanged { other: bool },
+    /// An enum or map is now flattened in, such as with `#[serde(flatten)]`
+    FlattenedAdded { name: String, optional: bool },
+    FlattenedRemoved { name: String },
+    /// A tuple gained or lost fields
+    TupleLengthChanged { from: usize, to: usize },
+    VariantAdded,
+    VariantRemoved,
+    /// Such as a unit variant becoming a tuple variant
+    VariantKindChanged { from: String, to: String },
+    /// The integer an enum variant is serialized as changed
+    DiscriminantChanged { from: Option<i128>, to: Option<i128> },
+    /// A constant holds something else, which does not change how anything is serialized
+    ValueChanged { from: serde_json::Value, to: serde_json::Value },
+}
+
+impl ChangeKind {
+    pub fn severity(&self) -> Severity {
+        match self {
+            ChangeKind::ItemAdded
+            | ChangeKind::ItemMoved { .. }
+            | ChangeKind::ModuleChanged { .. }
+            | ChangeKind::FieldAdded { optional: true }
+            | ChangeKind::FlattenedAdded { optional: true, .. }
+            | ChangeKind::DefaultChanged { default: true }
+            | ChangeKind::SkipChanged { skipped: false }
+            | ChangeKind::AliasAdded { .. }
+            | ChangeKind::CatchAllChanged { other: true }
+            | ChangeKind::VariantAdded
+            | ChangeKind::ValueChanged { .. } => Severity::NonBreaking,
+            // no longer writing something, or starting to read it, is what consumers notice
+            ChangeKind::SkippedChanged { from, to } if *to == Some(Direction::Serialize) || *from == Some(Direction::Deserialize) => Severity::Breaking,
+            ChangeKind::SkippedChanged { .. } => Severity::NonBreaking,
+            _ => Severity::Breaking,
+        }
+    }
+}
+
+/// A single difference, along with where it was found
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Change {
+    pub severity: Severity,
+    /// Rust path of the item, from the new schema where it exists there
+    pub item: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+    /// Field name, or index for tuples
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    #[serde(flatten)]
+    pub kind: ChangeKind,
+}
+
+/// Every difference between two schemas
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SchemaDiff {
+    pub changes: Vec<Change>,
+}
+
+impl SchemaDiff {
+    pub fn is_breaking(&self) -> bool {
+        self.breaking().next().is_some()
+    }
+
+    pub fn breaking(&self) -> impl Iterator<Item = &Change> {
+        self.changes.iter().filter(|change| change.severity == Severity::Breaking)
+    }
+
+    /// Writes this diff as pretty-printed JSON, for tools to consume
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("diffs are always representable as JSON")
+    }
+}
+
+impl Display for ChangeKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChangeKind::ItemAdded => write!(f, "added"),
+            ChangeKind::ItemRemoved => write!(f, "removed"),
+            ChangeKind::ItemRenamed { from, to } => write!(f, "renamed from `{from}` to `{to}`"),
+            ChangeKind::ItemMoved { from, to } => write!(f, "moved from `{from}` to `{to}`"),
+            ChangeKind::ItemKindChanged { from, to } => write!(f, "changed from a {from} to a {to}"),
+            ChangeKind::ModuleChanged { from, to } => write!(f, "moved from module `{}` to `{}`", from.as_deref().unwrap_or("default"), to.as_deref().unwrap_or("default")),
+            ChangeKind::ReprChanged { from, to } => write!(f, "representation changed from {from} to {to}"),
+            ChangeKind::TagChanged { from, to } => write!(f, "tag changed from {} to {}", tag(from), tag(to)),
+            ChangeKind::FieldAdded { optional: true } => write!(f, "optional field added"),
+            ChangeKind::FieldAdded { optional: false } => write!(f, "required field added"),
+            ChangeKind::FieldRemoved => write!(f, "field removed"),
+            ChangeKind::FieldTypeChanged { from, to } => write!(f, "type changed from `{from}` to `{to}`"),
+            ChangeKind::TargetTypeChanged { target, from, to } => write!(f, "type for `{target}` changed from `{from}` to `{to}`"),
+            ChangeKind::OptionalityChanged { optional: true } => write!(f, "changed from required to optional"),
+            ChangeKind::OptionalityChanged { optional: false } => write!(f, "changed from optional to required"),
+            ChangeKind::DefaultChanged { default: true } => write!(f, "may now be left out when deserializing"),
+            ChangeKind::DefaultChanged { default: false } => write!(f, "is now required when deserializing"),
+            ChangeKind::SkipChanged { skipped: true } => write!(f, "may now be left out when serializing"),
+            ChangeKind::SkipChanged { skipped: false } => write!(f, "is now always serialized"),
+            ChangeKind::ReadNameChanged { from, to } => write!(f, "read as `{to}` instead of `{from}`"),
+            ChangeKind::SkippedChanged { from, to } => write!(f, "changed from {} to {}", skipped(*from), skipped(*to)),
+            ChangeKind::AliasAdded { alias } => write!(f, "may now be read as `{alias}`"),
+            ChangeKind::AliasRemoved { alias } => write!(f, "can no longer be read as `{alias}`"),
+            ChangeKind::CatchAllChanged { other: true } => write!(f, "now read in place of unknown variants"),
+            ChangeKind::CatchAllChanged { other: false } => write!(f, "no longer read in place of unknown variants"),
+            ChangeKind::FlattenedAdded { name, optional: true } => write!(f, "optional `{name}` flattened in"),
+            ChangeKind::FlattenedAdded { name, optional: false } => write!(f, "`{name}` flattened in"),
+            ChangeKind::FlattenedRemoved { name } => write!(f, "`{name}` no longer flattened in"),
+            ChangeKind::TupleLengthChanged { from, to } => write!(f, "changed from {from} to {to} fields"),
+            ChangeKind::VariantAdded => write!(f, "variant added"),
+            ChangeKind::VariantRemoved => write!(f, "variant removed"),
+            ChangeKind::VariantKindChanged { from, to } => write!(f, "variant changed from {from} to {to}"),
+            ChangeKind::ValueChanged { from, to } => write!(f, "value changed from {from} to {to}"),
+            ChangeKind::DiscriminantChanged { from, to } => match (from, to) {
+                (Some(from), Some(to)) => write!(f, "discriminant changed from {from} to {to}"),
+                _ => write!(f, "discriminant changed"),
+            },
+        }
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let severity = match self.severity {
+            Severity::Breaking => "breaking",
+            Severity::NonBreaking => "non-breaking",
+        };
+        write!(f, "{severity}: `{}`", self.item)?;
+        if let Some(variant) = &self.variant {
+            write!(f, " variant `{variant}`")?;
+        }
+        if let Some(field) = &self.field {
+            write!(f, " field `{field}`")?;
+        }
+        write!(f, ": {}", self.kind)
+    }
+}
+
+impl Display for SchemaDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        let breaking = self.breaking().count();
+        write!(f, "{} change(s), {breaking} breaking", self.changes.len())
+    }
+}
+
+/// Compares two schemas, classifying each difference by whether it breaks consumers of the JSON
+/// Items are matched by Rust path, then anything left over is matched by shape to find renamed or moved items
+/// Fields and variants are matched by their exported names, so renaming one shows up as a removal and an addition
+pub fn diff(old: &Schema, new: &Schema) -> SchemaDiff {
+    let old_items: BTreeMap<&str, &ItemSchema> = old.items.iter().map(|item| (item.info().path.as_str(), item)).collect();
+    let new_items: BTreeMap<&str, &ItemSchema> = new.items.iter().map(|item| (item.info().path.as_str(), item)).collect();
+
+    let mut changes = Vec::new();
+    let push = |changes: &mut Vec<Change>, item: &str, kind: ChangeKind| changes.push(Change {
+        severity: kind.severity(),
+        item: item.to_string(),
+        variant: None,
+        field: None,
+        kind,
+    });
+
+    // Pair up items that only changed path, by comparing everything else
+    let mut removed: Vec<&ItemSchema> = old_items.iter().filter(|(path, _)| !new_items.contains_key(*path)).map(|(_, item)| *item).collect();
+    let mut added: Vec<&ItemSchema> = new_items.iter().filter(|(path, _)| !old_items.contains_key(*path)).map(|(_, item)| *item).collect();
+    let mut moved = BTreeMap::new();
+    removed.retain(|old| {
+        match added.iter().position(|new| same_shape(old, new)) {
+            Some(pos) => {
+                let new = added.remove(pos);
+                moved.insert(old.info().path.as_str(), new.info().path.as_str());
+                false
+            }
+            None => true,
+        }
+    });
+
+    for (from, to) in &moved {
+        let (old_name, new_name) = (&old_items[from].info().name, &new_items[to].info().name);
+        let kind = if old_name == new_name {
+            ChangeKind::ItemMoved { from: from.to_string(), to: to.to_string() }
+        }
+        else {
+            ChangeKind::ItemRenamed { from: old_name.clone(), to: new_name.clone() }
+        };
+        push(&mut changes, to, kind);
+    }
+    for item in removed {
+        push(&mut changes, &item.info().path, ChangeKind::ItemRemoved);
+    }
+    for item in added {
+        push(&mut changes, &item.info().path, ChangeKind::ItemAdded);
+    }
+
+    let differ = Differ { moved: &moved };
+    for (path, old) in &old_items {
+        if let Some(new) = new_items.get(path) {
+            differ.item(old, new, &mut changes);
+        }
+    }
+
+    changes.sort_by(|a, b| (a.severity, &a.item).cmp(&(b.severity, &b.item)));
+    SchemaDiff { changes }
+}
+
+/// Whether two items are the same apart from where they live and how they are documented
+fn same_shape(a: &ItemSchema, b: &ItemSchema) -> bool {
+    let strip = |item: &ItemSchema| {
+        let mut item = item.clone();
+        let info = match &mut item {
+            ItemSchema::NamedStruct { info, .. } => info,
+            ItemSchema::TupleStruct { info, .. } => info,
+            ItemSchema::UnitStruct { info } => info,
+            ItemSchema::Enum { info, .. } => info,
+            ItemSchema::Const { info, .. } => info,
+            ItemSchema::Alias { info, .. } => info,
+        };
+        info.path.clear();
+        info.name.clear();
+        info.module = None;
+        info.docs = None;
+        item
+    };
+
+    // Unit structs all look alike and constants and aliases often share values, so only pair them up when the name matches too
+    if matches!(a, ItemSchema::UnitStruct { .. } | ItemSchema::Const { .. } | ItemSchema::Alias { .. }) && a.info().name != b.info().name {
+        return false;
+    }
+    strip(a) == strip(b)
+}
+
+struct Differ<'a> {
+    /// Old paths of moved items, mapped to their new paths
+    moved: &'a BTreeMap<&'a str, &'a str>,
+}
+
+impl Differ<'_> {
+    fn item(&self, old: &ItemSchema, new: &ItemSchema, changes: &mut Vec<Change>) {
+        let path = &new.info().path;
+        let mut push = |kind: ChangeKind| changes.push(Change {
+            severity: kind.severity(),
+            item: path.clone(),
+            variant: None,
+            field: None,
+            kind,
+        });
+
+        if old.info().name != new.info().name {
+            push(ChangeKind::ItemRenamed { from: old.info().name.clone(), to: new.info().name.clone() });
+        }
+        if old.info().module != new.info().module {
+            push(ChangeKind::ModuleChanged { from: old.info().module.clone(), to: new.info().module.clone() });
+        }
+
+        match (old, new) {
+            (ItemSchema::NamedStruct { tag: old_tag, fields: old, flattened: old_flattened, .. }, ItemSchema::NamedStruct { tag: new_tag, fields: new, flattened: new_flattened, .. }) => {
+                if old_tag != new_tag {
+                    push(ChangeKind::TagChanged { from: old_tag.clone(), to: new_tag.clone() });
+                }
+                for kind in self.flattened(old_flattened, new_flattened) {
+                    push(kind);
+                }
+                self.named_fields(old, new, path, None, changes);
+            }
+            (ItemSchema::TupleStruct { fields: old, .. }, ItemSchema::TupleStruct { fields: new, .. }) => {
+                self.unnamed_fields(old, new, path, None, changes);
+            }
+            (ItemSchema::UnitStruct { .. }, ItemSchema::UnitStruct { .. }) => {}
+            (ItemSchema::Enum { repr: old_repr, variants: old, .. }, ItemSchema::Enum { repr: new_repr, variants: new, .. }) => {
+                if old_repr != new_repr {
+                    push(ChangeKind::ReprChanged { from: repr_name(old_repr), to: repr_name(new_repr) });
+                }
+                // Discriminants are only on the wire for enums serialized as integers
+                let int = *old_repr == ReprSchema::Int && *new_repr == ReprSchema::Int;
+                self.variants(old, new, path, int, changes);
+            }
+            (ItemSchema::Const { ty: old_ty, value: old, .. }, ItemSchema::Const { ty: new_ty, value: new, .. }) => {
+                for kind in self.types(old_ty, new_ty) {
+                    push(kind);
+                }
+                if old != new {
+                    push(ChangeKind::ValueChanged { from: old.clone(), to: new.clone() });
+                }
+            }
+            (ItemSchema::Alias { ty: old, de_ty: old_de, .. }, ItemSchema::Alias { ty: new, de_ty: new_de, .. }) => {
+                for kind in self.sided_types((old, old_de.as_ref()), (new, new_de.as_ref())) {
+                    push(kind);
+                }
+            }
+            _ => push(ChangeKind::ItemKindChanged { from: item_kind(old).to_string(), to: item_kind(new).to_string() }),
+        }
+    }
+
+    fn variants(&self, old: &[VariantSchema], new: &[VariantSchema], item: &str, int: bool, changes: &mut Vec<Change>) {
+        let push = |changes: &mut Vec<Change>, variant: &str, kind: ChangeKind| changes.push(Change {
+            severity: kind.severity(),
+            item: item.to_string(),
+            variant: Some(variant.to_string()),
+            field: None,
+            kind,
+        });
+
+        for old_var in old {
+            let Some(new_var) = new.iter().find(|var| var.name() == old_var.name()) else {
+                push(changes, old_var.name(), ChangeKind::VariantRemoved);
+                continue;
+            };
+
+            for kind in sides((old_var.read_name(), old_var.skip()), (new_var.read_name(), new_var.skip())) {
+                push(changes, old_var.name(), kind);
+            }
+            for kind in aliases(old_var.aliases(), new_var.aliases()) {
+                push(changes, old_var.name(), kind);
+            }
+
+            let variant = Some(old_var.name());
+            match (old_var, new_var) {
+                (VariantSchema::Struct { fields: old, flattened: old_flattened, .. }, VariantSchema::Struct { fields: new, flattened: new_flattened, .. }) => {
+                    for kind in self.flattened(old_flattened, new_flattened) {
+                        push(changes, old_var.name(), kind);
+                    }
+                    self.named_fields(old, new, item, variant, changes);
+                }
+                (VariantSchema::Tuple { fields: old, .. }, VariantSchema::Tuple { fields: new, .. }) => {
+                    self.unnamed_fields(old, new, item, variant, changes);
+                }
+                (VariantSchema::Unit { discriminant: old, other: old_other, .. }, VariantSchema::Unit { discriminant: new, other: new_other, .. }) => {
+                    if int && old != new {
+                        push(changes, old_var.name(), ChangeKind::DiscriminantChanged { from: *old, to: *new });
+                    }
+                    if old_other != new_other {
+                        push(changes, old_var.name(), ChangeKind::CatchAllChanged { other: *new_other });
+                    }
+                }
+                _ => push(changes, old_var.name(), ChangeKind::VariantKindChanged {
+                    from: variant_kind(old_var).to_string(),
+                    to: variant_kind(new_var).to_string(),
+                }),
+            }
+        }
+
+        for new_var in new {
+            if !old.iter().any(|var| var.name() == new_var.name()) {
+                push(changes, new_var.name(), ChangeKind::VariantAdded);
+            }
+        }
+    }
+
+    fn named_fields(&self, old: &[NamedFieldSchema], new: &[NamedFieldSchema], item: &str, variant: Option<&str>, changes: &mut Vec<Change>) {
+        let push = |changes: &mut Vec<Change>, field: &str, kind: ChangeKind| changes.push(Change {
+            severity: kind.severity(),
+            item: item.to_string(),
+            variant: variant.map(str::to_string),
+            field: Some(field.to_string()),
+            kind,
+        });
+
+        for old_field in old {
+            match new.iter().find(|field| field.name == old_field.name) {
+                Some(new_field) => {
+                    let kinds = self.field_types(
+                        (&old_field.ty, old_field.de_ty.as_ref(), &old_field.literals),
+                        (&new_field.ty, new_field.de_ty.as_ref(), &new_field.literals),
+                    );
+                    for kind in kinds {
+                        push(changes, &old_field.name, kind);
+                    }
+                    if old_field.default != new_field.default {
+                        push(changes, &old_field.name, ChangeKind::DefaultChanged { default: new_field.default });
+                    }
+                    if old_field.skip_serializing_if != new_field.skip_serializing_if {
+                        push(changes, &old_field.name, ChangeKind::SkipChanged { skipped: new_field.skip_serializing_if });
+                    }
+                    for kind in sides((old_field.read_name(), old_field.skip), (new_field.read_name(), new_field.skip)) {
+                        push(changes, &old_field.name, kind);
+                    }
+                    for kind in aliases(&old_field.aliases, &new_field.aliases) {
+                        push(changes, &old_field.name, kind);
+                    }
+                }
+                None => push(changes, &old_field.name, ChangeKind::FieldRemoved),
+            }
+        }
+
+        for new_field in new {
+            if !old.iter().any(|field| field.name == new_field.name) {
+                // serde fills in missing `Option`s, so those can be left out of what we accept as well
+                push(changes, &new_field.name, ChangeKind::FieldAdded { optional: new_field.ty.optional || new_field.default });
+            }
+        }
+    }
+
+    fn unnamed_fields(&self, old: &[UnnamedFieldSchema], new: &[UnnamedFieldSchema], item: &str, variant: Option<&str>, changes: &mut Vec<Change>) {
+        let push = |changes: &mut Vec<Change>, field: Option<String>, kind: ChangeKind| changes.push(Change {
+            severity: kind.severity(),
+            item: item.to_string(),
+            variant: variant.map(str::to_string),
+            field,
+            kind,
+        });
+
+        if old.len() != new.len() {
+            push(changes, None, ChangeKind::TupleLengthChanged { from: old.len(), to: new.len() });
+            return;
+        }
+
+        for (i, (old, new)) in old.iter().zip(new).enumerate() {
+            for kind in self.field_types((&old.ty, None, &old.literals), (&new.ty, None, &new.literals)) {
+                push(changes, Some(i.to_string()), kind);
+            }
+        }
+    }
+
+    /// Compares field types, following items that moved
+    /// Pairs up flattened enums by the item they refer to, and compares them like fields
+    fn flattened(&self, old: &[FlattenedSchema], new: &[FlattenedSchema]) -> Vec<ChangeKind> {
+        let key = |flattened: &FlattenedSchema, moved: bool| match flattened {
+            FlattenedSchema::Enum { ty } => match ty.item.as_deref() {
+                Some(path) if moved => self.moved.get(path).unwrap_or(&path).to_string(),
+                Some(path) => path.to_string(),
+                None => ty.path.clone(),
+            },
+            FlattenedSchema::Map => "map".to_string(),
+        };
+        let name = |flattened: &FlattenedSchema| match flattened {
+            FlattenedSchema::Enum { ty } => type_name(ty),
+            FlattenedSchema::Map => "map".to_string(),
+        };
+
+        let mut kinds = Vec::new();
+        for old_flattened in old {
+            match new.iter().find(|new| key(new, false) == key(old_flattened, true)) {
+                Some(FlattenedSchema::Enum { ty: new_ty }) => if let FlattenedSchema::Enum { ty: old_ty } = old_flattened {
+                    kinds.extend(self.types(old_ty, new_ty));
+                },
+                Some(FlattenedSchema::Map) => {}
+                None => kinds.push(ChangeKind::FlattenedRemoved { name: name(old_flattened) }),
+            }
+        }
+        for new_flattened in new {
+            if !old.iter().any(|old| key(old, true) == key(new_flattened, false)) {
+                let optional = match new_flattened {
+                    FlattenedSchema::Enum { ty } => ty.optional,
+                    // maps may always be empty
+                    FlattenedSchema::Map => true,
+                };
+                kinds.push(ChangeKind::FlattenedAdded { name: name(new_flattened), optional });
+            }
+        }
+        kinds
+    }
+
+    /// Compares the types of fields, along with any written as is in their place
+    /// Types written as literals are only known by their text, so they are compared as such
+    fn field_types(&self, (old, old_de, old_literals): (&TypeSchema, Option<&TypeSchema>, &LiteralsSchema), (new, new_de, new_literals): (&TypeSchema, Option<&TypeSchema>, &LiteralsSchema)) -> Vec<ChangeKind> {
+        let mut kinds = match (&old_literals.literal, &new_literals.literal) {
+            (None, None) => self.sided_types((old, old_de), (new, new_de)),
+            (from, to) => {
+                let mut kinds = Vec::new();
+                let (from, to) = (from.clone().unwrap_or_else(|| type_name(old)), to.clone().unwrap_or_else(|| type_name(new)));
+                if from != to {
+                    kinds.push(ChangeKind::FieldTypeChanged { from, to });
+                }
+                if old.optional != new.optional {
+                    kinds.push(ChangeKind::OptionalityChanged { optional: new.optional });
+                }
+                kinds
+            }
+        };
+
+        let targets: BTreeSet<&String> = old_literals.target_literals.keys().chain(new_literals.target_literals.keys()).collect();
+        for target in targets {
+            let from = old_literals.get(target).map_or_else(|| type_name(old), str::to_string);
+            let to = new_literals.get(target).map_or_else(|| type_name(new), str::to_string);
+            if from != to {
+                kinds.push(ChangeKind::TargetTypeChanged { target: target.clone(), from, to });
+            }
+        }
+        kinds
+    }
+
+    /// Compares types that may be read as another type than they are written as
+    /// Either side can change on its own, so changes to both are reported once
+    fn sided_types(&self, (old, old_de): (&TypeSchema, Option<&TypeSchema>), (new, new_de): (&TypeSchema, Option<&TypeSchema>)) -> Vec<ChangeKind> {
+        let mut kinds = self.types(old, new);
+        if old_de.is_some() || new_de.is_some() {
+            for kind in self.types(old_de.unwrap_or(old), new_de.unwrap_or(new)) {
+                if !kinds.contains(&kind) {
+                    kinds.push(kind);
+                }
+            }
+        }
+        kinds
+    }
+
+    fn types(&self, old: &TypeSchema, new: &TypeSchema) -> Vec<ChangeKind> {
+        let mut kinds = Vec::new();
+
+        let old_item = old.item.as_deref().map(|path| *self.moved.get(path).unwrap_or(&path));
+        let same = match (old_item, new.item.as_deref()) {
+            (Some(old), Some(new)) => old == new,
+            (None, None) => old.path == new.path,
+            _ => false,
+        };
+        if !same || old.array != new.array {
+            kinds.push(ChangeKind::FieldTypeChanged { from: type_name(old), to: type_name(new) });
+        }
+        if old.optional != new.optional {
+            kinds.push(ChangeKind::OptionalityChanged { optional: new.optional });
+        }
+
+        kinds
+    }
+}
+
+/// Changes to how a field or variant is read, or which way it is skipped
+fn sides((old_name, old_skip): (&str, Option<Direction>), (new_name, new_skip): (&str, Option<Direction>)) -> Vec<ChangeKind> {
+    let mut kinds = Vec::new();
+    if old_name != new_name {
+        kinds.push(ChangeKind::ReadNameChanged { from: old_name.to_string(), to: new_name.to_string() });
+    }
+    if old_skip != new_skip {
+        kinds.push(ChangeKind::SkippedChanged { from: old_skip, to: new_skip });
+    }
+    kinds
+}
+
+/// Names a field or variant gained or lost as aliases
+fn aliases(old: &[String], new: &[String]) -> Vec<ChangeKind> {
+    let removed = old.iter().filter(|alias| !new.contains(alias)).map(|alias| ChangeKind::AliasRemoved { alias: alias.clone() });
+    let added = new.iter().filter(|alias| !old.contains(alias)).map(|alias| ChangeKind::AliasAdded { alias: alias.clone() });
+    removed.chain(added).collect()
+}
+
+fn skipped(skip: Option<Direction>) -> &'static str {
+    match skip {
+        None => "never skipped",
+        Some(Direction::Serialize) => "skipped when serializing",
+        Some(Direction::Deserialize) => "skipped when deserializing",
+    }
+}
+
+fn tag(tag: &Option<String>) -> String {
+    match tag {
+        Some(tag) => format!("`{tag}`"),
+        None => "none".to_string(),
+    }
+}
+
+fn type_name(ty: &TypeSchema) -> String {
+    let name = ty.item.as_deref().unwrap_or(&ty.path);
+    if ty.array { format!("{name}[]") } else { name.to_string() }
+}
+
+fn item_kind(item: &ItemSchema) -> &'static str {
+    match item {
+        ItemSchema::NamedStruct { .. } => "struct",
+        ItemSchema::TupleStruct { .. } => "tuple struct",
+        ItemSchema::UnitStruct { .. } => "unit struct",
+        ItemSchema::Enum { .. } => "enum",
+        ItemSchema::Const { .. } => "constant",
+        ItemSchema::Alias { .. } => "alias",
+    }
+}
+
+fn variant_kind(variant: &VariantSchema) -> &'static str {
+    match variant {
+        VariantSchema::Struct { .. } => "struct",
+        VariantSchema::Tuple { .. } => "tuple",
+        VariantSchema::Unit { .. } => "unit",
+    }
+}
+
+fn repr_name(repr: &ReprSchema) -> String {
+    match repr {
+        ReprSchema::External => "externally tagged".to_string(),
+        ReprSchema::Untagged => "untagged".to_string(),
+        ReprSchema::Internal { tag } => format!("internally tagged by `{tag}`"),
+        ReprSchema::Adjacent { tag, content } => format!("adjacently tagged by `{tag}` and `{content}`"),
+        ReprSchema::Int => "integer".to_string(),
+    }
+}
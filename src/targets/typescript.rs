@@ -4,19 +4,20 @@ mod exporter;
 pub use file::*;
 
 use exporter::*;
-use crate::export::{BurrMod, Burrxporter, Target};
-use std::any::{TypeId};
+use crate::export::{BurrMod, Burrxporter, ExportError, RenderedFile, Target, known_keys_matching};
 use std::borrow::Cow;
-use std::collections::{HashMap};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use path_macro::path;
 use path_slash::*;
-use burrtype_internal::ir::IrItem;
+use burrtype_internal::ir::{IrItem, TypeKey};
+pub use burrtype_internal::ir::{EnumStyle, IntStyle};
 
 /// Determines how we want to map modules to files
 // todo: consider moving this and related logic to some sort of common writer
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize), serde(rename_all = "snake_case"))]
 pub enum ModFileMap {
     /// Everything will be written to one file
     /// All modules will be inlined
@@ -33,13 +34,18 @@ pub enum ModFileMap {
 pub struct TypeScript<'t> {
     pub formatter: TsFormatter<'t>,
     pub mod_file_map: ModFileMap,
+    pub enum_style: EnumStyle,
+    /// how integers wider than 32 bits are written, since they do not all fit in a `number`
+    pub int_style: IntStyle,
     /// replaces Rust types with TS types during export
     /// todo: fill this during phase 1 of export, then consume it for export?
     /// todo:: consider also converting this struct to something similar with another field for this specific purpose?
-    /// todo: also consider that we may simply replace this with <TypeId, TypeRegistration> if the registration can properly boil types down
-    pub type_map: HashMap<TypeId, &'t str>,
+    /// todo: also consider that we may simply replace this with <TypeKey, TypeRegistration> if the registration can properly boil types down
+    pub type_map: HashMap<TypeKey, &'t str>,
     /// types being mapped to other types
-    pub type_overrides: HashMap<TypeId, TypeId>,
+    pub type_overrides: HashMap<TypeKey, TypeKey>,
+    /// writes types serde reads differently from how it writes them as separate `{Name}Input` and `{Name}Output` types
+    pub input_output: bool,
 }
 
 impl<'t> Default for TypeScript<'t> {
@@ -47,51 +53,122 @@ impl<'t> Default for TypeScript<'t> {
         TypeScript {
             formatter: TsFormatter::pretty(),
             mod_file_map: ModFileMap::DecomposeAll,
+            enum_style: EnumStyle::default(),
+            int_style: IntStyle::default(),
             type_map: HashMap::default(),
             type_overrides: Default::default(),
+            input_output: false,
         }
     }
 }
 
 impl<'t> TypeScript<'t> {
+    /// Name this target is given literal types under, such as with `#[burr(ts = "...")]`
+    pub const TARGET: &'static str = "ts";
+
     /// Sets up a new TS target with defaults matching common standards
     pub fn new() -> Self {
         TypeScript::default().with_std_remaps()
     }
 
+    /// Maps builtin types, along with types from `std` and any crates enabled with cargo features such as `uuid` or `chrono`
     pub fn with_std_remaps(mut self) -> Self {
         self.type_map.extend([
-            (TypeId::of::<str>(), "string"),
-            (TypeId::of::<char>(), "string"),
-            (TypeId::of::<String>(), "string"),
-            (TypeId::of::<bool>(), "boolean"),
-            (TypeId::of::<usize>(), "number"),
-            (TypeId::of::<u8>(), "number"),
-            (TypeId::of::<u16>(), "number"),
-            (TypeId::of::<u32>(), "number"),
-            (TypeId::of::<u64>(), "number"),
-            (TypeId::of::<u128>(), "number"),
-            (TypeId::of::<isize>(), "number"),
-            (TypeId::of::<i8>(), "number"),
-            (TypeId::of::<i16>(), "number"),
-            (TypeId::of::<i32>(), "number"),
-            (TypeId::of::<i64>(), "number"),
-            (TypeId::of::<i128>(), "number"),
-            (TypeId::of::<f32>(), "number"),
-            (TypeId::of::<f64>(), "number"),
+            (TypeKey::of::<str>(), "string"),
+            (TypeKey::of::<char>(), "string"),
+            (TypeKey::of::<String>(), "string"),
+            (TypeKey::of::<bool>(), "boolean"),
+            (TypeKey::of::<usize>(), "number"),
+            (TypeKey::of::<u8>(), "number"),
+            (TypeKey::of::<u16>(), "number"),
+            (TypeKey::of::<u32>(), "number"),
+            (TypeKey::of::<u64>(), "number"),
+            (TypeKey::of::<u128>(), "number"),
+            (TypeKey::of::<isize>(), "number"),
+            (TypeKey::of::<i8>(), "number"),
+            (TypeKey::of::<i16>(), "number"),
+            (TypeKey::of::<i32>(), "number"),
+            (TypeKey::of::<i64>(), "number"),
+            (TypeKey::of::<i128>(), "number"),
+            (TypeKey::of::<f32>(), "number"),
+            (TypeKey::of::<f64>(), "number"),
+            (TypeKey::of::<std::time::Duration>(), "{ secs: number, nanos: number }"),
+            (TypeKey::of::<std::time::SystemTime>(), "{ secs_since_epoch: number, nanos_since_epoch: number }"),
+            (TypeKey::of::<std::path::PathBuf>(), "string"),
+            (TypeKey::of::<std::path::Path>(), "string"),
+            (TypeKey::of::<std::net::IpAddr>(), "string"),
+            (TypeKey::of::<std::net::Ipv4Addr>(), "string"),
+            (TypeKey::of::<std::net::Ipv6Addr>(), "string"),
+            (TypeKey::of::<std::net::SocketAddr>(), "string"),
+            (TypeKey::of::<std::net::SocketAddrV4>(), "string"),
+            (TypeKey::of::<std::net::SocketAddrV6>(), "string"),
+            #[cfg(feature = "serde_json")]
+            (TypeKey::of::<serde_json::Value>(), "unknown"),
+            #[cfg(feature = "serde_json")]
+            (TypeKey::of::<serde_json::Number>(), "number"),
+            #[cfg(feature = "serde_json")]
+            (TypeKey::of::<serde_json::Map<String, serde_json::Value>>(), "Record<string, unknown>"),
+            #[cfg(feature = "uuid")]
+            (TypeKey::of::<uuid::Uuid>(), "string"),
+            #[cfg(feature = "chrono")]
+            (TypeKey::of::<chrono::DateTime<chrono::Utc>>(), "string"),
+            #[cfg(feature = "chrono")]
+            (TypeKey::of::<chrono::DateTime<chrono::FixedOffset>>(), "string"),
+            #[cfg(feature = "chrono")]
+            (TypeKey::of::<chrono::NaiveDateTime>(), "string"),
+            #[cfg(feature = "chrono")]
+            (TypeKey::of::<chrono::NaiveDate>(), "string"),
+            #[cfg(feature = "chrono")]
+            (TypeKey::of::<chrono::NaiveTime>(), "string"),
+            #[cfg(feature = "time")]
+            (TypeKey::of::<time::OffsetDateTime>(), time_name(time::OffsetDateTime::UNIX_EPOCH, "[number, number, number, number, number, number, number, number, number]")),
+            #[cfg(feature = "time")]
+            (TypeKey::of::<time::PrimitiveDateTime>(), time_name(time::PrimitiveDateTime::MIN, "[number, number, number, number, number, number]")),
+            #[cfg(feature = "time")]
+            (TypeKey::of::<time::Date>(), time_name(time::Date::MIN, "[number, number]")),
+            #[cfg(feature = "time")]
+            (TypeKey::of::<time::Time>(), time_name(time::Time::MIDNIGHT, "[number, number, number, number]")),
+            // written as text unless `rust_decimal`'s `serde-float` feature is used
+            #[cfg(feature = "rust_decimal")]
+            (TypeKey::of::<rust_decimal::Decimal>(), "string"),
+            #[cfg(feature = "url")]
+            (TypeKey::of::<url::Url>(), "string"),
+            #[cfg(feature = "bytes")]
+            (TypeKey::of::<bytes::Bytes>(), "number[]"),
+            #[cfg(feature = "bytes")]
+            (TypeKey::of::<bytes::BytesMut>(), "number[]"),
         ]);
         self
     }
 
+    /// Controls how integers wider than 32 bits are written, unless overridden with `#[burr(int_style = "...")]`
+    /// Written as a `number`, their fields are documented with `@precision-unsafe` since values past 2^53 lose precision
+    pub fn with_int_style(mut self, int_style: IntStyle) -> Self {
+        self.int_style = int_style;
+        self
+    }
+
     /// Sets the exported name of the given type when writing fields
     pub fn with_type_name<T: ?Sized + 'static>(mut self, name: &'t str) -> Self {
-        self.type_map.insert(TypeId::of::<T>(), name);
+        self.type_map.insert(TypeKey::of::<T>(), name);
+        self
+    }
+
+    /// Sets the exported name of types written with the given Rust path when writing fields
+    /// Paths match by their trailing segments, so `"rust_decimal::Decimal"` also covers fields written as `Decimal`
+    /// Useful when a type's `TypeId` is unavailable, such as when exporting from a build script
+    pub fn with_path_name(mut self, path: &'static str, name: &'t str) -> Self {
+        self.type_map.insert(TypeKey::Path(path), name);
+        // well-known types are keyed by `TypeId` even when scanned, and would otherwise keep their default names
+        for key in known_keys_matching(path) {
+            self.type_map.insert(key, name);
+        }
         self
     }
 
     /// Substitutes F with T when writing fields
     pub fn with_type_remap<F: ?Sized + 'static, T: ?Sized + 'static>(mut self) -> Self {
-        self.type_overrides.insert(TypeId::of::<F>(), TypeId::of::<T>());
+        self.type_overrides.insert(TypeKey::of::<F>(), TypeKey::of::<T>());
         self
     }
 
@@ -106,13 +183,30 @@ impl<'t> TypeScript<'t> {
         self.mod_file_map = mod_file_map;
         self
     }
+
+    /// Controls how enums without fields are written, unless overridden with `#[burr(enum_style = "...")]`
+    pub fn with_enum_style(mut self, enum_style: EnumStyle) -> Self {
+        self.enum_style = enum_style;
+        self
+    }
+
+    /// Writes types that serde reads differently from how it writes them as a `{Name}Input` and a `{Name}Output` type
+    /// Such as types with fields renamed or skipped one way, or fields with defaults
+    /// Types using them are split as well, while every other type is written once as usual
+    pub fn with_input_output(mut self, input_output: bool) -> Self {
+        self.input_output = input_output;
+        self
+    }
 }
 
 impl<'f> Target for TypeScript<'f> {
-    fn export(self, to: &Path, exporter: &Burrxporter) {
+    fn render(self, to: &Path, exporter: &Burrxporter) -> Result<Vec<RenderedFile>, ExportError> {
         // build our export-friendly type and export it
-        let TypeScript { formatter, mod_file_map, type_map, type_overrides } = self;
-        let mods = exporter.mods.clone();
+        let TypeScript { formatter, mod_file_map, enum_style, int_style, type_map, type_overrides, input_output } = self;
+        let mut mods = exporter.mods.clone();
+        for module in &mut mods {
+            module.pick_literals(TypeScript::TARGET);
+        }
 
         // builds the set of files to write
         let mut files = HashMap::new();
@@ -154,8 +248,8 @@ impl<'f> Target for TypeScript<'f> {
         }
 
         // build a map of all types being exported
-        let mut type_registry: HashMap<TypeId, IrItem> = HashMap::new();
-        let mut type_exports: HashMap<TypeId, PathBuf> = HashMap::new();
+        let mut type_registry: HashMap<TypeKey, IrItem> = HashMap::new();
+        let mut type_exports: HashMap<TypeKey, PathBuf> = HashMap::new();
         for file in files.values() {
             // Flatten all items in this file
             let mut flat_items = Vec::new();
@@ -184,16 +278,23 @@ impl<'f> Target for TypeScript<'f> {
             }
         }
 
-        TsExporter {
+        let split = if input_output { split_types(&type_registry, &type_overrides) } else { HashSet::new() };
+
+        Ok(TsExporter {
             exporter,
             formatter,
+            enum_style,
+            int_style,
             files,
             type_registry,
             type_exports,
             type_overrides,
             type_strings: type_map,
+            split,
+            direction: Default::default(),
+            warnings: Default::default(),
         }
-        .export();
+        .render())
     }
 }
 
@@ -226,6 +327,42 @@ fn decompose_all(file: &mut TsFile) -> Vec<TsFile> {
     files
 }
 
+/// `time` writes its types as text with its `serde-human-readable` feature, and as a tuple of their parts without it
+/// Every crate using `time` shares its features, so we ask it how it was built rather than guessing
+#[cfg(feature = "time")]
+fn time_name<T: serde::Serialize>(value: T, parts: &'static str) -> &'static str {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(_)) => "string",
+        _ => parts,
+    }
+}
+
+/// Finds the types written as separate input and output types
+/// Those serde reads differently from how it writes them, along with any type whose fields use one of them
+fn split_types(registry: &HashMap<TypeKey, IrItem>, overrides: &HashMap<TypeKey, TypeKey>) -> HashSet<TypeKey> {
+    let resolve = |mut id: TypeKey| {
+        while let Some(target) = overrides.get(&id) {
+            id = *target;
+        }
+        id
+    };
+
+    let mut split: HashSet<TypeKey> = registry.iter()
+        .filter(|(_, item)| !item.is_symmetric())
+        .map(|(id, _)| *id)
+        .collect();
+    loop {
+        let uses: Vec<TypeKey> = registry.iter()
+            .filter(|(id, item)| !split.contains(id) && item.all_field_types().into_iter().any(|ty| split.contains(&resolve(ty))))
+            .map(|(id, _)| *id)
+            .collect();
+        if uses.is_empty() {
+            return split;
+        }
+        split.extend(uses);
+    }
+}
+
 /// Gets a flat list of all items
 fn pull_flat_items(bm: &BurrMod) -> Vec<&IrItem> {
     let mut items = Vec::new();
@@ -1,36 +1,47 @@
-use super::{TsFile, TsFormatter};
-use crate::export::Burrxporter;
+use super::{EnumStyle, IntStyle, TsFile, TsFormatter};
+use crate::export::{Burrxporter, ExportWarning, RenderedFile};
 use inflector::Inflector;
-use std::any::TypeId;
 use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, HashSet};
-use std::io::Write;
 use std::path::PathBuf;
 use std::sync::atomic::Ordering;
 use path_macro::path;
 use path_slash::*;
-use burrtype_internal::ir::{EnumRepr, IrEnumVariant, IrItem, IrNamedField, IrUnnamedField};
+use syn::Ident;
+use burrtype_internal::ir::{paths_match, Direction, EnumRepr, IrEnumVariant, IrFlattened, IrItem, IrNamedField, IrType, IrUnnamedField, TypeKey};
 
 /// An export-friendly version of the Typescript export builder
 /// Contains files being exported and computed metadata about files and their types
 pub struct TsExporter<'t> {
     pub exporter: &'t Burrxporter,
     pub formatter: TsFormatter<'t>,
+    pub enum_style: EnumStyle,
+    /// how wide integers are written, unless a field picks its own style
+    pub int_style: IntStyle,
     /// maps file paths to files
     pub files: HashMap<PathBuf, TsFile>,
     // /// type information for types being exported
-    pub type_registry: HashMap<TypeId, IrItem>,
+    pub type_registry: HashMap<TypeKey, IrItem>,
     /// types being exported to file paths
-    pub type_exports: HashMap<TypeId, PathBuf>,
+    pub type_exports: HashMap<TypeKey, PathBuf>,
     /// types being mapped to other types
-    pub type_overrides: HashMap<TypeId, TypeId>,
+    pub type_overrides: HashMap<TypeKey, TypeKey>,
     /// types being mapped to string
-    pub type_strings: HashMap<TypeId, &'t str>,
+    pub type_strings: HashMap<TypeKey, &'t str>,
+    /// types written as separate input and output types
+    pub split: HashSet<TypeKey>,
+    /// the side of split types being written, so references to other split types pick the same side
+    pub direction: Cell<Option<Direction>>,
+    /// warnings raised while rendering the current file
+    pub warnings: RefCell<Vec<ExportWarning>>,
 }
 
 impl<'t> TsExporter<'t> {
-    pub fn export(self) {
-        // Export files
+    /// Renders every file in memory, sorted by path
+    pub fn render(self) -> Vec<RenderedFile> {
+        let mut rendered = Vec::with_capacity(self.files.len());
+
         for file in self.files.values() {
             let mut out = String::new();
 
@@ -48,18 +59,18 @@ impl<'t> TsExporter<'t> {
             }
 
             // get all used imports by target
-            let mut import_map: HashMap<PathBuf, HashSet<TypeId>> = HashMap::new();
+            let mut import_map: HashMap<PathBuf, HashSet<TypeKey>> = HashMap::new();
             for id in &field_types {
                 if let Some(target) = self.type_exports.get(id) {
                     import_map
                         .entry(target.clone())
                         .or_default()
-                        .insert(id.clone());
+                        .insert(*id);
                 }
             }
             // remove self-references
             import_map.remove(&file.target);
-            // let import_map: Vec<(PathBuf, HashSet<TypeId>)> = import_map.into_iter().collect();
+            // let import_map: Vec<(PathBuf, HashSet<TypeKey>)> = import_map.into_iter().collect();
 
             // iterate imports and write them
             // for (import, types) in &import_map {
@@ -96,14 +107,12 @@ impl<'t> TsExporter<'t> {
                     }
                 };
                 // write import head
-                out.push_str(&format!("import {{ "));
+                out.push_str("import { ");
                 // write import items
-                for (i, ty) in types.iter().enumerate() {
-                    if i > 0 {
-                        out.push_str(", ");
-                    }
-                    out.push_str(&strip_rust_prefix(format!("{}", ty.ident())));
-                }
+                let names: Vec<String> = types.iter()
+                    .flat_map(|ty| self.sides(ty).map(|suffix| strip_rust_prefix(format!("{}{suffix}", ty.ident()))))
+                    .collect();
+                out.push_str(&names.join(", "));
                 // write import tail
                 out.push_str(&format!(" }} from '{}'\n", full_path.to_slash_lossy()));
 
@@ -131,32 +140,57 @@ impl<'t> TsExporter<'t> {
 
             // separate imports and exports, if any
             if !import_map.is_empty() {
-                out.push_str("\n");
+                out.push('\n');
             }
 
             // write exports
             for (i, item) in file.items.iter().enumerate() {
                 if i > 0 {
-                    out.push_str("\n");
+                    out.push('\n');
                 }
                 out.push_str(&self.format_type(item));
-                out.push_str("\n");
+                out.push('\n');
             }
 
-            // export to file
-            if out.is_empty() {
-                println!("skipping empty file: {}", file.target.to_slash_lossy());
-            }
-            else {
-                println!("writing to: {}", file.target.to_slash_lossy());
-
-                let mut writer = self.exporter.open_writer(&file.target).unwrap();
-                writer.write(out.as_bytes()).unwrap();
-            }
+            rendered.push(RenderedFile {
+                path: file.target.clone(),
+                contents: out,
+                types: file.items.iter()
+                    .flat_map(|item| self.sides(item).map(|suffix| format!("{}{suffix}", export_name(item))))
+                    .collect(),
+                warnings: self.warnings.take(),
+            });
         }
+
+        rendered.sort_by(|a, b| a.path.cmp(&b.path));
+        rendered
+    }
+
+    /// Gets the suffix of each type an item is written as
+    fn sides(&self, item: &IrItem) -> impl Iterator<Item = &'static str> {
+        let sides: &[&str] = if self.split.contains(&item.type_id()) { &["Input", "Output"] } else { &[""] };
+        sides.iter().copied()
     }
 
     fn format_type(&self, item: &IrItem) -> String {
+        if !self.split.contains(&item.type_id()) {
+            return self.format_item(item);
+        }
+
+        // each side is written as serde sees it that way, referring to the same side of other split types
+        let sides = [(Direction::Deserialize, "Input"), (Direction::Serialize, "Output")].map(|(direction, suffix)| {
+            let mut view = item.view(direction);
+            let ident = view.ident_mut();
+            *ident = Ident::new(&format!("{}{suffix}", strip_rust_prefix(ident.to_string()).to_pascal_case()), ident.span());
+
+            self.direction.set(Some(direction));
+            self.format_item(&view)
+        });
+        self.direction.set(None);
+        sides.join("\n\n")
+    }
+
+    fn format_item(&self, item: &IrItem) -> String {
         let mut out = String::new();
         match item {
             IrItem::NamedStruct(ir) => {
@@ -165,28 +199,36 @@ impl<'t> TsExporter<'t> {
                 if let Some(doc) = ir.docs {
                     out.push_str(&format!("{}/** {doc} */\n", self.formatter.get_indentation()));
                 }
-                out.push_str(&format!("{}export interface {} {{\n", self.formatter.get_indentation(), strip_rust_prefix(ir.name()).to_pascal_case()));
+                // interfaces cannot be intersected with anything flattened in, but an object type can
+                let header = if ir.flattened.is_empty() { "interface" } else { "type" };
+                let equals = if ir.flattened.is_empty() { "" } else { "= " };
+                out.push_str(&format!("{}export {header} {} {equals}{{\n", self.formatter.get_indentation(), strip_rust_prefix(ir.name()).to_pascal_case()));
                 self.formatter.depth.fetch_add(1, Ordering::Relaxed);
 
+                // serde writes the tag ahead of every other field
+                if let Some(tag) = &ir.tag {
+                    out.push_str(&format!("{}{}: \"{}\",\n", self.formatter.get_indentation(), tag.field, tag.value));
+                }
+
                 // struct items
                 for field in &ir.fields {
                     #[cfg(feature = "comments")]
-                    if let Some(doc) = field.docs {
-                        out.push_str(&format!("{}/** {doc} */\n", self.formatter.get_indentation()));
-                    }
+                    let docs = field.docs;
+                    #[cfg(not(feature = "comments"))]
+                    let docs = None;
+                    out.push_str(&self.format_field_docs(docs, self.precision_unsafe(&field.ty, field.literals.any, field.int_style), false));
 
-                    out.push_str(&format!("{}{}{}: {}{},\n",
+                    out.push_str(&format!("{}{}{}: {},\n",
                                           self.formatter.get_indentation(),
                                           strip_rust_prefix(field.ident.to_string()),
-                                          if field.ty.optional { "?" } else { "" },
-                                          self.get_field_name(field.ty.id),
-                                          if field.ty.array { "[]" } else { "" },
+                                          if field.is_optional() { "?" } else { "" },
+                                          self.get_literal_name(&field.ty, field.literals.any, field.int_style),
                     ));
                 }
 
                 // struct tail
                 self.formatter.depth.fetch_sub(1, Ordering::Relaxed);
-                out.push_str(&format!("{}}}", self.formatter.get_indentation()));
+                out.push_str(&format!("{}}}{}", self.formatter.get_indentation(), self.format_flattened(&ir.flattened)));
             }
             IrItem::TupleStruct(ir) => {
                 // struct header
@@ -200,13 +242,14 @@ impl<'t> TsExporter<'t> {
                 if ir.fields.len() == 1 {
                     let field = ir.fields.first().unwrap();
                     #[cfg(feature = "comments")]
-                    if let Some(doc) = field.docs {
-                        out.push_str(&format!("{}/** {doc} */\n", self.formatter.get_indentation()));
-                    }
+                    let docs = field.docs;
+                    #[cfg(not(feature = "comments"))]
+                    let docs = None;
+                    out.push_str(&self.format_field_docs(docs, self.precision_unsafe(&field.ty, field.literals.any, field.int_style), false));
                     out.push_str(&format!("{}export type {} = {}",
                                           self.formatter.get_indentation(),
                                           strip_rust_prefix(ir.name()).to_pascal_case(),
-                                          self.get_field_name(field.ty.id),
+                                          self.get_literal_name(&field.ty, field.literals.any, field.int_style),
                     ));
                 }
                 else {
@@ -223,13 +266,14 @@ impl<'t> TsExporter<'t> {
                         }
 
                         #[cfg(feature = "comments")]
-                        if let Some(doc) = field.docs {
-                            out.push_str(&format!("/** {doc} */ "));
-                        }
-                        out.push_str( &self.get_field_name(field.ty.id));
+                        let docs = field.docs;
+                        #[cfg(not(feature = "comments"))]
+                        let docs = None;
+                        out.push_str(&self.format_field_docs(docs, self.precision_unsafe(&field.ty, field.literals.any, field.int_style), true));
+                        out.push_str(&self.get_literal_name(&field.ty, field.literals.any, field.int_style));
                     }
                     // struct tail
-                    out.push_str(&format!("]"));
+                    out.push(']');
                 }
             }
             IrItem::UnitStruct(ir) => {
@@ -246,14 +290,69 @@ impl<'t> TsExporter<'t> {
                     out.push_str(&format!("{}/** {doc} */\n", self.formatter.get_indentation()));
                 }
 
-                out.push_str(&format!("{}export type {} =\n", self.formatter.get_indentation(), strip_rust_prefix(ir.name()).to_pascal_case()));
-                self.formatter.depth.fetch_add(1, Ordering::Relaxed);
+                let name = strip_rust_prefix(ir.name()).to_pascal_case();
+                let style = ir.style.unwrap_or(self.enum_style);
+                // Only enums serialized as plain strings or integers can be written as anything but a union
+                // A catch-all variant accepts any string, which has no place in a TS `enum`
+                let plain = matches!(ir.repr, EnumRepr::External | EnumRepr::Int)
+                    && ir.variants.iter().all(|var| matches!(var, IrEnumVariant::Unit(_)) && !var.is_other());
+
+                match style {
+                    EnumStyle::Enum | EnumStyle::ConstEnum if plain => {
+                        out.push_str(&format!("{}export {}enum {} {{\n",
+                                              self.formatter.get_indentation(),
+                                              if style == EnumStyle::ConstEnum { "const " } else { "" },
+                                              name,
+                        ));
+                        self.formatter.depth.fetch_add(1, Ordering::Relaxed);
+
+                        self.format_enum_members(&mut out, ir.repr, &ir.variants);
+
+                        self.formatter.depth.fetch_sub(1, Ordering::Relaxed);
+                        out.push_str(&format!("{}}}", self.formatter.get_indentation()));
+                    }
+                    _ => {
+                        out.push_str(&format!("{}export type {} =\n", self.formatter.get_indentation(), name));
+                        self.formatter.depth.fetch_add(1, Ordering::Relaxed);
 
-                self.format_enum_variants(&mut out, ir.repr, &ir.variants);
+                        self.format_enum_variants(&mut out, ir.repr, &ir.variants);
 
-                // enum tail
-                self.formatter.depth.fetch_sub(1, Ordering::Relaxed);
-                out.push_str(&format!(";"));
+                        // enum tail
+                        self.formatter.depth.fetch_sub(1, Ordering::Relaxed);
+                        out.push(';');
+
+                        if style == EnumStyle::UnionWithValues && plain {
+                            let values: Vec<String> = ir.variants.iter().filter_map(|var| unit_literal(ir.repr, var)).collect();
+                            out.push_str(&format!("\n\n{}export const {} = [{}] as const;",
+                                                  self.formatter.get_indentation(),
+                                                  name.to_screaming_snake_case(),
+                                                  values.join(", "),
+                            ));
+                        }
+                    }
+                }
+            }
+            IrItem::Const(ir) => {
+                #[cfg(feature = "comments")]
+                if let Some(doc) = ir.docs {
+                    out.push_str(&format!("{}/** {doc} */\n", self.formatter.get_indentation()));
+                }
+                // JSON is valid TypeScript, and `as const` keeps the literal types rather than widening them
+                out.push_str(&format!("{}export const {} = {} as const;", self.formatter.get_indentation(), export_name(item), ir.value));
+            }
+            IrItem::Alias(ir) => {
+                #[cfg(feature = "comments")]
+                let docs = ir.docs;
+                #[cfg(not(feature = "comments"))]
+                let docs = None;
+                out.push_str(&self.format_field_docs(docs, self.precision_unsafe(&ir.ty, None, None), false));
+                out.push_str(&format!("{}export type {} = {}{}{}",
+                                      self.formatter.get_indentation(),
+                                      export_name(item),
+                                      self.get_field_name(&ir.ty, None),
+                                      if ir.ty.array { "[]" } else { "" },
+                                      if ir.ty.optional { " | null" } else { "" },
+                ));
             }
         }
         out
@@ -281,8 +380,11 @@ impl<'t> TsExporter<'t> {
                             self.format_enum_struct_fields(out, compact, &vir.fields);
 
                             self.formatter.depth.fetch_sub(1, Ordering::Relaxed);
-                            out.push_str(&format!("{}}}}}\n",
+                            let flattened = self.format_flattened(&vir.flattened);
+                            out.push_str(&format!("{}}}{}{}}}\n",
                                                   if compact { Cow::from(" ") } else { self.formatter.get_indentation() },
+                                                  flattened,
+                                                  if flattened.is_empty() { "" } else { " " },
                             ));
                             self.formatter.depth.fetch_sub(1, Ordering::Relaxed);
                         }
@@ -296,8 +398,9 @@ impl<'t> TsExporter<'t> {
                             self.format_enum_struct_fields(out, compact, &vir.fields);
 
                             self.formatter.depth.fetch_sub(1, Ordering::Relaxed);
-                            out.push_str(&format!("{}}}\n",
+                            out.push_str(&format!("{}}}{}\n",
                                                   if compact { Cow::from(" ") } else { self.formatter.get_indentation() },
+                                                  self.format_flattened(&vir.flattened),
                             ));
                             self.formatter.depth.fetch_sub(1, Ordering::Relaxed);
                         }
@@ -318,8 +421,9 @@ impl<'t> TsExporter<'t> {
                             self.format_enum_struct_fields(out, compact, &vir.fields);
 
                             self.formatter.depth.fetch_sub(1, Ordering::Relaxed);
-                            out.push_str(&format!("{}}}\n",
+                            out.push_str(&format!("{}}}{}\n",
                                                   if compact { Cow::from(" ") } else { self.formatter.get_indentation() },
+                                                  self.format_flattened(&vir.flattened),
                             ));
                             self.formatter.depth.fetch_sub(1, Ordering::Relaxed);
                         }
@@ -347,8 +451,9 @@ impl<'t> TsExporter<'t> {
                             self.format_enum_struct_fields(out, compact, &vir.fields);
 
                             self.formatter.depth.fetch_sub(1, Ordering::Relaxed);
-                            out.push_str(&format!("{}}}{}",
+                            out.push_str(&format!("{}}}{}{}",
                                                   if compact { Cow::from(" ") } else { self.formatter.get_indentation() },
+                                                  self.format_flattened(&vir.flattened),
                                                   if compact { "" } else { "\n" },
                             ));
 
@@ -359,6 +464,8 @@ impl<'t> TsExporter<'t> {
 
                             self.formatter.depth.fetch_sub(1, Ordering::Relaxed);
                         }
+                        // Possible through user-crafted IR, but only enums without fields are represented as integers
+                        EnumRepr::Int => unreachable!(),
                     }
                 }
                 IrEnumVariant::Tuple(vir) => {
@@ -369,15 +476,14 @@ impl<'t> TsExporter<'t> {
 
                     // "Newtypes" - tuples with exactly one field - are written without the surrounding tuple representation
                     // This unwrapping of the inner type means we need to flatten any docs too
-                    let compact = if vir.fields.len() == 1 {
-                        let field = vir.fields.first().unwrap();
+                    let compact = vir.fields.len() == 1;
+                    if let Some(field) = vir.fields.first().filter(|_| compact) {
                         #[cfg(feature = "comments")]
-                        if let Some(doc) = field.docs {
-                            out.push_str(&format!("/** {doc} */ "));
-                        }
-                        true
+                        let docs = field.docs;
+                        #[cfg(not(feature = "comments"))]
+                        let docs = None;
+                        out.push_str(&self.format_field_docs(docs, self.precision_unsafe(&field.ty, field.literals.any, field.int_style), true));
                     }
-                    else { false };
 
                     match repr {
                         EnumRepr::External => {
@@ -386,7 +492,7 @@ impl<'t> TsExporter<'t> {
                                 out.push_str(&format!("{}| {{ {}: {} }}\n",
                                                       self.formatter.get_indentation(),
                                                       strip_rust_prefix(var.name()),
-                                                      self.get_field_name(field.ty.id),
+                                                      self.get_literal_name(&field.ty, field.literals.any, field.int_style),
                                 ));
                             }
                             else {
@@ -394,7 +500,7 @@ impl<'t> TsExporter<'t> {
 
                                 self.format_enum_tuple_fields(out, &vir.fields);
 
-                                out.push_str(&format!("] }}\n"));
+                                out.push_str("] }\n");
                             }
 
                         }
@@ -443,30 +549,51 @@ impl<'t> TsExporter<'t> {
                             ));
                             self.formatter.depth.fetch_sub(1, Ordering::Relaxed);
                         }
-                        // Possible through user-crafted IR, but will never be generated by the derive macro we expect you to use
-                        EnumRepr::Internal(_) => unreachable!(),
+                        // serde writes the tag alongside the fields of the wrapped type, which must be a struct or map
+                        EnumRepr::Internal(tag) if compact => {
+                            let field = vir.fields.first().unwrap();
+                            out.push_str(&format!("{}| {{ {}: \"{}\" }} & {}\n",
+                                                  self.formatter.get_indentation(),
+                                                  tag,
+                                                  strip_rust_prefix(var.name()),
+                                                  self.get_literal_name(&field.ty, field.literals.any, field.int_style),
+                            ));
+                        }
+                        // Possible through user-crafted IR, but serde rejects tuple variants in internally tagged enums
+                        EnumRepr::Internal(_) | EnumRepr::Int => unreachable!(),
                     }
                 }
+                #[allow(unused_variables)]
                 IrEnumVariant::Unit(vir) => {
                     #[cfg(feature = "comments")]
                     if let Some(doc) = vir.docs {
                         out.push_str(&format!("{}/** {doc} */\n", self.formatter.get_indentation()));
                     }
 
+                    // anything the enum does not know is read as this variant, so it stands for any other name
+                    let name = match vir.other {
+                        true => Cow::from("string"),
+                        false => format!("\"{}\"", strip_rust_prefix(var.name())).into(),
+                    };
+
                     match repr {
                         EnumRepr::External => {
-                            out.push_str(&format!("{}| \"{}\"\n", self.formatter.get_indentation(), strip_rust_prefix(var.name())));
+                            out.push_str(&format!("{}| {}\n", self.formatter.get_indentation(), name));
                         }
                         EnumRepr::Untagged => {
-                            // todo: fix this, it is probably incorrect
-                            out.push_str(&format!("{}| \"{}\"\n", self.formatter.get_indentation(), strip_rust_prefix(var.name())));
+                            // serde writes untagged unit variants as `null`, since there is nothing else to tell them apart by
+                            out.push_str(&format!("{}| null\n", self.formatter.get_indentation()));
                         }
                         EnumRepr::Internal(tag) => {
-                            out.push_str(&format!("{}| {{ {}: \"{}\" }}\n", self.formatter.get_indentation(), tag, strip_rust_prefix(var.name())));
+                            out.push_str(&format!("{}| {{ {}: {} }}\n", self.formatter.get_indentation(), tag, name));
                         }
                         EnumRepr::Adjacent { tag, .. } => {
-                            // todo: fix this, it may be incorrect
-                            out.push_str(&format!("{}| {{ {}: \"{}\" }}\n", self.formatter.get_indentation(), tag, strip_rust_prefix(var.name())));
+                            // serde leaves out the content of unit variants entirely
+                            out.push_str(&format!("{}| {{ {}: {} }}\n", self.formatter.get_indentation(), tag, name));
+                        }
+                        EnumRepr::Int => {
+                            let value = vir.discriminant.map_or(Cow::from("number"), |value| value.to_string().into());
+                            out.push_str(&format!("{}| {}\n", self.formatter.get_indentation(), value));
                         }
                     }
                 }
@@ -474,34 +601,50 @@ impl<'t> TsExporter<'t> {
         }
     }
 
+    /// Writes the members of a TS `enum`, set to the value each is serialized as
+    fn format_enum_members(&self, out: &mut String, repr: EnumRepr, variants: &[IrEnumVariant]) {
+        for var in variants {
+            #[cfg(feature = "comments")]
+            if let IrEnumVariant::Unit(vir) = var {
+                if let Some(doc) = vir.docs {
+                    out.push_str(&format!("{}/** {doc} */\n", self.formatter.get_indentation()));
+                }
+            }
+            match unit_literal(repr, var) {
+                Some(value) => out.push_str(&format!("{}{} = {},\n", self.formatter.get_indentation(), member_name(var), value)),
+                None => out.push_str(&format!("{}{},\n", self.formatter.get_indentation(), member_name(var))),
+            }
+        }
+    }
+
     fn format_enum_struct_fields(&self, out: &mut String, compact: bool, fields: &[IrNamedField]) {
         for (n, field) in fields.iter().enumerate() {
             if compact {
                 if n > 0 {
-                    out.push_str(&format!(", "));
+                    out.push_str(", ");
                 }
                 #[cfg(feature = "comments")]
-                if let Some(doc) = field.docs {
-                    out.push_str(&format!("/** {doc} */ "));
-                }
-                out.push_str(&format!("{}{}: {}{}",
+                let docs = field.docs;
+                #[cfg(not(feature = "comments"))]
+                let docs = None;
+                out.push_str(&self.format_field_docs(docs, self.precision_unsafe(&field.ty, field.literals.any, field.int_style), true));
+                out.push_str(&format!("{}{}: {}",
                                       strip_rust_prefix(field.name()),
-                                      if field.ty.optional { "?" } else { "" },
-                                      self.get_field_name(field.ty.id),
-                                      if field.ty.array { "[]" } else { "" },
+                                      if field.is_optional() { "?" } else { "" },
+                                      self.get_literal_name(&field.ty, field.literals.any, field.int_style),
                 ));
             }
             else {
                 #[cfg(feature = "comments")]
-                if let Some(doc) = field.docs {
-                    out.push_str(&format!("{}/** {doc} */\n", self.formatter.get_indentation()));
-                }
-                out.push_str(&format!("{}{}{}: {}{},\n",
+                let docs = field.docs;
+                #[cfg(not(feature = "comments"))]
+                let docs = None;
+                out.push_str(&self.format_field_docs(docs, self.precision_unsafe(&field.ty, field.literals.any, field.int_style), false));
+                out.push_str(&format!("{}{}{}: {},\n",
                                       self.formatter.get_indentation(),
                                       strip_rust_prefix(field.name()),
-                                      if field.ty.optional { "?" } else { "" },
-                                      self.get_field_name(field.ty.id),
-                                      if field.ty.array { "[]" } else { "" },
+                                      if field.is_optional() { "?" } else { "" },
+                                      self.get_literal_name(&field.ty, field.literals.any, field.int_style),
                 ));
             }
         }
@@ -510,34 +653,131 @@ impl<'t> TsExporter<'t> {
     fn format_enum_tuple_fields(&self, out: &mut String, fields: &[IrUnnamedField]) {
         for (n, field) in fields.iter().enumerate() {
             if n > 0 {
-                out.push_str(&format!(", "));
+                out.push_str(", ");
             }
             #[cfg(feature = "comments")]
-            if let Some(doc) = field.docs {
-                out.push_str(&format!("/** {doc} */ "));
+            let docs = field.docs;
+            #[cfg(not(feature = "comments"))]
+            let docs = None;
+            out.push_str(&self.format_field_docs(docs, self.precision_unsafe(&field.ty, field.literals.any, field.int_style), true));
+            out.push_str(&self.get_literal_name(&field.ty, field.literals.any, field.int_style));
+        }
+    }
+
+    /// Writes enums and maps flattened into a struct as the rest of an intersection with it, such as ` & Shape`
+    fn format_flattened(&self, flattened: &[IrFlattened]) -> String {
+        flattened.iter()
+            .map(|flattened| match flattened {
+                // `None` writes nothing at all
+                IrFlattened::Enum(ty) if ty.optional => format!(" & ({} | {{}})", self.get_field_name(ty, None)),
+                IrFlattened::Enum(ty) => format!(" & {}", self.get_field_name(ty, None)),
+                IrFlattened::Map => " & Record<string, unknown>".to_string(),
+            })
+            .collect()
+    }
+
+    /// Gets the name of a field's type, unless it was given as a literal with `#[burr(type = "...")]` or `#[burr(ts = "...")]`
+    fn get_literal_name(&self, ty: &IrType, literal: Option<&str>, int_style: Option<IntStyle>) -> String {
+        literal.map_or_else(|| self.get_type_name(ty, int_style), str::to_string)
+    }
+
+    /// Gets the name of a type along with any array around it
+    fn get_type_name(&self, ty: &IrType, int_style: Option<IntStyle>) -> String {
+        format!("{}{}", self.get_field_name(ty, int_style), if ty.array { "[]" } else { "" })
+    }
+
+    /// Gets the name of a type, with wide integers written in the given style over the target's own
+    fn get_field_name(&self, ty: &IrType, int_style: Option<IntStyle>) -> String {
+        let target_id = self.target_id(ty);
+
+        // `number` is left to the type map, like any other integer
+        let wide = match int_style.unwrap_or(self.int_style) {
+            _ if !target_id.is_wide_int() => None,
+            IntStyle::Number => None,
+            IntStyle::BigInt => Some("bigint"),
+            IntStyle::String => Some("string"),
+        };
+
+        if let Some(name) = wide {
+            name.to_string()
+        }
+        else if let Some(name) = self.type_strings.get(target_id).or_else(|| self.get_path_name(ty, target_id)) {
+            name.to_string()
+        }
+        else if let Some(item) = self.type_registry.get(target_id).or_else(|| self.exporter.type_registry.get(target_id)) {
+            match self.direction.get().filter(|_| self.split.contains(target_id)) {
+                Some(Direction::Deserialize) => format!("{}Input", item.ident()),
+                Some(Direction::Serialize) => format!("{}Output", item.ident()),
+                None => item.ident().to_string(),
+            }
+        }
+        else {
+            // Nothing tells us how to write this type, so the best we can do is the name it was written with
+            let name = ty.path.path.segments.last().map_or_else(String::new, |segment| segment.ident.to_string());
+            let warning = ExportWarning::UnknownType(name.clone());
+            let mut warnings = self.warnings.borrow_mut();
+            if !warnings.contains(&warning) {
+                warnings.push(warning);
             }
-            out.push_str(&format!("{}", self.get_field_name(field.ty.id)));
+            name
         }
     }
 
-    fn get_field_name(&self, id: TypeId) -> String {
-        // get final type to write
-        let mut target_id = &id;
+    /// Gets the type finally written in place of the given one
+    fn target_id<'a>(&'a self, ty: &'a IrType) -> &'a TypeKey {
+        let mut target_id = &ty.id;
         while let Some(id) = self.type_overrides.get(target_id) {
             target_id = id;
         }
+        target_id
+    }
 
-        if let Some(name) = self.type_strings.get(target_id) {
-            name.to_string()
-        }
-        else {
-            self.type_registry
-                .get(target_id)
-                .unwrap_or_else(|| self.exporter.type_registry.get(target_id).expect("type not registered"))
-                .ident()
-                .to_string()
+    /// Whether a wide integer is being written as a `number`, where values past 2^53 lose precision
+    /// Only the `number` style asks for this to be pointed out, and literal types are left as given
+    fn precision_unsafe(&self, ty: &IrType, literal: Option<&str>, int_style: Option<IntStyle>) -> bool {
+        let target_id = self.target_id(ty);
+        literal.is_none()
+            && target_id.is_wide_int()
+            && int_style.unwrap_or(self.int_style) == IntStyle::Number
+            && self.type_strings.get(target_id) == Some(&"number")
+    }
+
+    /// Writes a field's docs as a JSDoc comment, tagged with `@precision-unsafe` when asked
+    /// Inline comments go ahead of the type on the same line, as in tuples
+    fn format_field_docs(&self, docs: Option<&str>, precision_unsafe: bool, inline: bool) -> String {
+        let indent = self.formatter.get_indentation();
+        match (docs, precision_unsafe) {
+            (None, false) => String::new(),
+            (Some(doc), true) if !inline => format!("{indent}/**\n{indent} * {doc}\n{indent} * @precision-unsafe\n{indent} */\n"),
+            (docs, _) => {
+                let text: Vec<&str> = docs.into_iter().chain(precision_unsafe.then_some("@precision-unsafe")).collect();
+                match inline {
+                    true => format!("/** {} */ ", text.join(" ")),
+                    false => format!("{indent}/** {} */\n", text.join(" ")),
+                }
+            }
         }
     }
+
+    /// Finds a type name registered by path that matches either the written path or the path-based key of a type
+    fn get_path_name(&self, ty: &IrType, id: &TypeKey) -> Option<&&'t str> {
+        let written = ty.path_string();
+        self.type_strings.iter()
+            .find(|(key, _)| match key {
+                TypeKey::Path(path) => paths_match(path, &written) || id.path().is_some_and(|id| paths_match(path, id)),
+                TypeKey::Id(_) => false,
+            })
+            .map(|(_, name)| name)
+    }
+}
+
+/// Gets the name an item is exported with
+/// Constants keep their Rust name, since they are conventionally written in screaming snake case in both languages
+fn export_name(item: &IrItem) -> String {
+    match item {
+        IrItem::Const(_) => strip_rust_prefix(item.name()),
+        _ => strip_rust_prefix(item.name()).to_pascal_case(),
+    }
 }
 
 fn strip_rust_prefix<'s, S: Into<Cow<'s, str>>>(name: S) -> String {
@@ -546,4 +786,21 @@ fn strip_rust_prefix<'s, S: Into<Cow<'s, str>>>(name: S) -> String {
         None => name.into(),
         Some(name) => name.into()
     }
-}
\ No newline at end of file
+}
+/// Gets the literal a unit variant is serialized as, for enums written as plain strings or integers
+fn unit_literal(repr: EnumRepr, var: &IrEnumVariant) -> Option<String> {
+    match (repr, var) {
+        (EnumRepr::Int, IrEnumVariant::Unit(vir)) => vir.discriminant.map(|value| value.to_string()),
+        (_, IrEnumVariant::Unit(_)) => Some(format!("\"{}\"", strip_rust_prefix(var.name()))),
+        _ => None,
+    }
+}
+
+/// Gets the name of an `enum` member, which has to be a valid identifier even when the serialized name is not
+fn member_name(var: &IrEnumVariant) -> String {
+    let name = strip_rust_prefix(var.name());
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+    if valid { name } else { name.to_pascal_case() }
+}
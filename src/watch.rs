@@ -0,0 +1,122 @@
+//! Re-exporting whenever sources change
+//! A running program cannot pick up changes to its own types, so sources are scanned again for each export
+//!
+//! ```ignore
+//! use burrtype::prelude::*;
+//! use burrtype::watch::Watcher;
+//!
+//! let mut watcher = Watcher::new(["src"]);
+//! loop {
+//!     let scan = burrtype::build::scan("my_crate", "src/lib.rs")?;
+//!     let mut exporter = scan.into_exporter();
+//!     exporter.resolve_all("common");
+//!     let report = exporter.export("ts", TypeScript::new())?;
+//!     for file in report.changed() {
+//!         println!("{}: {:?}", file.path.display(), file.status);
+//!     }
+//!
+//!     watcher.wait()?;
+//! }
+//! ```
+//!
+//! Exports already leave unchanged files untouched, so each run only reports the files that actually changed
+
+use crate::export::ExportError;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// Watches files for changes by polling them
+/// Directories are watched recursively, skipping hidden directories and `target`
+pub struct Watcher {
+    roots: Vec<PathBuf>,
+    interval: Duration,
+    snapshot: BTreeMap<PathBuf, (SystemTime, u64)>,
+}
+
+impl Watcher {
+    /// Starts watching the given files and directories, from their current state
+    pub fn new<I: IntoIterator<Item = P>, P: Into<PathBuf>>(roots: I) -> Self {
+        let mut watcher = Watcher {
+            roots: roots.into_iter().map(Into::into).collect(),
+            interval: Duration::from_millis(500),
+            snapshot: BTreeMap::new(),
+        };
+        // Anything we cannot read yet will simply show up as a change once it can be
+        watcher.snapshot = watcher.scan().unwrap_or_default();
+        watcher
+    }
+
+    /// Sets how often to check for changes
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Blocks until something changes, returning every path that was added, removed, or modified
+    /// Changes are collected until the files settle, so a save touching several files is reported once
+    pub fn wait(&mut self) -> Result<Vec<PathBuf>, ExportError> {
+        let mut changed = Vec::new();
+        loop {
+            thread::sleep(self.interval);
+            let snapshot = self.scan()?;
+            let diff = diff(&self.snapshot, &snapshot);
+            self.snapshot = snapshot;
+
+            if diff.is_empty() {
+                if !changed.is_empty() {
+                    changed.sort();
+                    changed.dedup();
+                    return Ok(changed);
+                }
+            }
+            else {
+                changed.extend(diff);
+            }
+        }
+    }
+
+    fn scan(&self) -> Result<BTreeMap<PathBuf, (SystemTime, u64)>, ExportError> {
+        let mut snapshot = BTreeMap::new();
+        for root in &self.roots {
+            scan_path(root, &mut snapshot)?;
+        }
+        Ok(snapshot)
+    }
+}
+
+fn scan_path(path: &Path, snapshot: &mut BTreeMap<PathBuf, (SystemTime, u64)>) -> Result<(), ExportError> {
+    let meta = match fs::metadata(path) {
+        Ok(meta) => meta,
+        // Files can disappear between listing a directory and reading them
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+
+    if meta.is_dir() {
+        for entry in fs::read_dir(path)? {
+            let entry = entry?;
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if entry.file_type()?.is_dir() && (name.starts_with('.') || name == "target") {
+                continue;
+            }
+            scan_path(&entry.path(), snapshot)?;
+        }
+    }
+    else {
+        snapshot.insert(path.to_path_buf(), (meta.modified()?, meta.len()));
+    }
+
+    Ok(())
+}
+
+/// Gets paths that differ between two snapshots
+fn diff(old: &BTreeMap<PathBuf, (SystemTime, u64)>, new: &BTreeMap<PathBuf, (SystemTime, u64)>) -> Vec<PathBuf> {
+    let removed = old.keys().filter(|path| !new.contains_key(*path));
+    let changed = new.iter().filter(|(path, meta)| old.get(*path) != Some(meta)).map(|(path, _)| path);
+    removed.chain(changed).cloned().collect()
+}
@@ -0,0 +1,611 @@
+//! Checks that what serde writes for each enum representation and variant shape is accepted by the TypeScript we generate
+
+mod support;
+
+use burrtype::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+use support::ts::{Decls, Ty};
+
+#[derive(Burr, Serialize)]
+pub struct Inner {
+    x: u32,
+    label: String,
+}
+
+#[derive(Burr, Serialize)]
+pub enum External {
+    Unit,
+    Newtype(Inner),
+    List(Vec<u32>),
+    Tuple(u32, String),
+    Struct { a: u32, b: String },
+}
+
+#[derive(Burr, Serialize)]
+#[serde(untagged)]
+pub enum Untagged {
+    Unit,
+    Newtype(Inner),
+    List(Vec<u32>),
+    Tuple(u32, String),
+    Struct { a: u32, b: String },
+}
+
+#[derive(Burr, Serialize)]
+#[serde(tag = "type")]
+pub enum Internal {
+    Unit,
+    Newtype(Inner),
+    Struct { a: u32, b: String },
+}
+
+#[derive(Burr, Serialize)]
+#[serde(tag = "t", content = "c")]
+pub enum Adjacent {
+    Unit,
+    Newtype(Inner),
+    List(Vec<u32>),
+    Tuple(u32, String),
+    Struct { a: u32, b: String },
+}
+
+#[derive(Burr, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[burr(enum_style = "union_with_values")]
+pub enum Plain {
+    FirstThing,
+    SecondThing,
+}
+
+#[derive(Burr, Serialize, Default)]
+#[serde(default)]
+pub struct Defaults {
+    count: u32,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+}
+
+#[derive(Burr, Serialize)]
+pub struct Skipped {
+    id: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+}
+
+#[derive(Burr, serde_repr::Serialize_repr)]
+#[repr(u16)]
+pub enum Code {
+    Ok = 200,
+    NotFound = 404,
+}
+
+#[derive(Burr, Serialize, Deserialize)]
+pub struct Login {
+    #[serde(rename(serialize = "userName", deserialize = "user"))]
+    name: String,
+    // only ever read
+    #[allow(dead_code)]
+    #[serde(skip_serializing)]
+    password: String,
+    #[serde(skip_deserializing)]
+    token: Option<String>,
+}
+
+#[derive(Burr, Serialize)]
+pub struct Session {
+    id: u32,
+    login: Login,
+}
+
+#[derive(Burr, Serialize)]
+#[serde(transparent)]
+pub struct Email(String);
+
+#[derive(Burr, Serialize)]
+#[serde(transparent)]
+pub struct Wrapped {
+    inner: Inner,
+    #[serde(skip)]
+    #[allow(dead_code)]
+    cached: u32,
+}
+
+#[derive(Burr, Serialize, Deserialize, Clone)]
+#[serde(into = "String", try_from = "String")]
+pub struct Version {
+    major: u32,
+    minor: u32,
+}
+
+impl From<Version> for String {
+    fn from(value: Version) -> Self {
+        format!("{}.{}", value.major, value.minor)
+    }
+}
+
+impl TryFrom<String> for Version {
+    type Error = std::num::ParseIntError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let (major, minor) = value.split_once('.').unwrap_or((&value, "0"));
+        Ok(Version { major: major.parse()?, minor: minor.parse()? })
+    }
+}
+
+#[derive(Burr, Serialize, Deserialize, Clone)]
+#[serde(into = "String", from = "u32")]
+pub struct Level(u32);
+
+impl From<Level> for String {
+    fn from(value: Level) -> Self {
+        format!("level {}", value.0)
+    }
+}
+
+impl From<u32> for Level {
+    fn from(value: u32) -> Self {
+        Level(value)
+    }
+}
+
+#[derive(Burr, Serialize)]
+pub struct Profile {
+    email: Email,
+    version: Version,
+}
+
+#[derive(Burr, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "kind", rename_all_fields = "camelCase")]
+pub enum Event {
+    Created {
+        user_id: u32,
+        #[serde(alias = "when")]
+        created_at: u64,
+    },
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    Deleted { user_id: u32 },
+    #[serde(alias = "removed")]
+    Archived,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Burr, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "type", rename = "point")]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Burr, Serialize)]
+#[serde(tag = "shape")]
+pub enum Shape {
+    Circle { radius: u32 },
+    Square { side: u32 },
+}
+
+#[derive(Burr, Serialize)]
+pub struct Drawing {
+    id: u32,
+    #[serde(flatten)]
+    shape: Shape,
+}
+
+#[derive(Burr, Serialize)]
+pub struct Extras {
+    name: String,
+    #[serde(flatten)]
+    inner: Option<Inner>,
+    #[serde(flatten)]
+    rest: HashMap<String, Value>,
+}
+
+#[derive(Burr, Serialize)]
+pub enum Layer {
+    Drawn {
+        name: String,
+        #[serde(flatten)]
+        shape: Option<Shape>,
+    },
+}
+
+/// Writes durations as text, the same as `humantime_serde`
+mod humantime_serde {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{}s", duration.as_secs()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        let secs = text.strip_suffix('s').and_then(|secs| secs.parse().ok()).ok_or_else(|| de::Error::custom("expected seconds"))?;
+        Ok(Duration::from_secs(secs))
+    }
+}
+
+/// Reads values from their text, the same as `serde_with::rust::display_fromstr`
+mod display_fromstr {
+    use serde::{de, Deserialize, Deserializer};
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: FromStr<Err: Display>>(deserializer: D) -> Result<T, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(de::Error::custom)
+    }
+}
+
+#[derive(Burr, Serialize, Deserialize)]
+pub struct Timeout {
+    #[serde(with = "humantime_serde")]
+    after: Duration,
+    #[serde(deserialize_with = "display_fromstr::deserialize")]
+    retries: u32,
+    #[burr(type = "Record<string, number>")]
+    limits: HashMap<String, u32>,
+}
+
+#[derive(Burr, Serialize)]
+pub struct Reading {
+    #[burr(ts = "number | string")]
+    value: Value,
+    #[burr(type = "unknown", target(ts = "string", python = "str"))]
+    unit: String,
+    #[burr(target(python = "dict"))]
+    inner: Inner,
+}
+
+#[derive(Burr, Serialize)]
+pub struct Upload {
+    id: uuid::Uuid,
+    path: PathBuf,
+    from: IpAddr,
+    took: Duration,
+    meta: serde_json::Map<String, Value>,
+    on: time::Date,
+}
+
+/// Writes integers as text, the same as `serde_with::DisplayFromStr`
+fn as_text<S: serde::Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+#[derive(Burr, Serialize)]
+pub struct Snowflake {
+    /// Unique across shards
+    id: u64,
+    shard: u32,
+    history: Vec<i64>,
+    #[burr(int_style = "number")]
+    count: u128,
+    #[burr(int_style = "string")]
+    #[serde(serialize_with = "as_text")]
+    total: u64,
+}
+
+fn inner() -> Inner {
+    Inner { x: 1, label: "one".to_string() }
+}
+
+/// The generated file and every type declared in it
+fn decls() -> &'static (String, Decls) {
+    static DECLS: OnceLock<(String, Decls)> = OnceLock::new();
+    DECLS.get_or_init(|| {
+        let source = support::render_all(TypeScript::new());
+        let decls = Decls::parse(&source);
+        (source, decls)
+    })
+}
+
+/// The generated file when types are split into input and output types
+fn split_decls() -> &'static (String, Decls) {
+    static DECLS: OnceLock<(String, Decls)> = OnceLock::new();
+    DECLS.get_or_init(|| {
+        let source = support::render_all(TypeScript::new().with_input_output(true));
+        let decls = Decls::parse(&source);
+        (source, decls)
+    })
+}
+
+/// Asserts every value is accepted by the named type
+/// With `exclusive` set, each value must also be accepted by exactly one member of the union, as tagged enums should be
+fn assert_conforms<T: Serialize>(name: &str, values: &[T], exclusive: bool) {
+    let (source, decls) = decls();
+    let ty = decls.get(name);
+
+    for value in values {
+        let json = serde_json::to_value(value).unwrap();
+        assert!(decls.accepts(ty, &json), "`{json}` is not accepted by `{name}`:\n{source}");
+
+        if let (true, Ty::Union(options)) = (exclusive, ty) {
+            let accepted = options.iter().filter(|ty| decls.accepts(ty, &json)).count();
+            assert_eq!(accepted, 1, "`{json}` is accepted by {accepted} members of `{name}`:\n{source}");
+        }
+    }
+}
+
+fn assert_rejects(name: &str, json: Value) {
+    let (source, decls) = decls();
+    assert!(!decls.accepts(decls.get(name), &json), "`{json}` should not be accepted by `{name}`:\n{source}");
+}
+
+#[test]
+fn externally_tagged() {
+    assert_conforms("External", &[
+        External::Unit,
+        External::Newtype(inner()),
+        External::List(vec![1, 2, 3]),
+        External::Tuple(1, "one".to_string()),
+        External::Struct { a: 1, b: "one".to_string() },
+    ], true);
+
+    assert_rejects("External", json!({ "Unit": null }));
+    assert_rejects("External", json!({ "Newtype": { "x": 1 } }));
+}
+
+#[test]
+fn untagged() {
+    assert_conforms("Untagged", &[
+        Untagged::Unit,
+        Untagged::Newtype(inner()),
+        Untagged::List(vec![1, 2, 3]),
+        Untagged::Tuple(1, "one".to_string()),
+        Untagged::Struct { a: 1, b: "one".to_string() },
+    ], false);
+
+    assert_rejects("Untagged", json!("Unit"));
+}
+
+#[test]
+fn internally_tagged() {
+    assert_conforms("Internal", &[
+        Internal::Unit,
+        Internal::Newtype(inner()),
+        Internal::Struct { a: 1, b: "one".to_string() },
+    ], true);
+
+    assert_rejects("Internal", json!({ "type": "Newtype" }));
+    assert_rejects("Internal", json!({ "type": "Unit", "x": 1 }));
+}
+
+#[test]
+fn adjacently_tagged() {
+    assert_conforms("Adjacent", &[
+        Adjacent::Unit,
+        Adjacent::Newtype(inner()),
+        Adjacent::List(vec![1, 2, 3]),
+        Adjacent::Tuple(1, "one".to_string()),
+        Adjacent::Struct { a: 1, b: "one".to_string() },
+    ], true);
+
+    assert_rejects("Adjacent", json!({ "t": "Newtype", "c": [1] }));
+}
+
+#[test]
+fn unit_only() {
+    assert_conforms("Plain", &[Plain::FirstThing, Plain::SecondThing], true);
+    assert_conforms("Code", &[Code::Ok, Code::NotFound], true);
+
+    assert_rejects("Plain", json!("FirstThing"));
+    assert_rejects("Code", json!(201));
+}
+
+#[test]
+fn omitted_fields() {
+    assert_conforms("Defaults", &[Defaults::default(), Defaults { count: 1, tags: vec!["tag".to_string()] }], false);
+    assert_conforms("Skipped", &[Skipped { id: 1, note: None }, Skipped { id: 1, note: Some("note".to_string()) }], false);
+
+    // a container default lets anything be left out when sending one back
+    let (_, decls) = decls();
+    assert!(decls.accepts(decls.get("Defaults"), &json!({})));
+    assert_rejects("Skipped", json!({ "note": "note" }));
+}
+
+#[test]
+fn values_array_matches_union() {
+    let (source, _) = decls();
+    assert!(source.contains(r#"export const PLAIN = ["first_thing", "second_thing"] as const;"#), "{source}");
+}
+
+#[test]
+fn one_sided_fields() {
+    let login = Login { name: "me".to_string(), password: "secret".to_string(), token: Some("token".to_string()) };
+    // written as the serialize side, with anything skipped one way left optional
+    assert_conforms("Login", &[&login], false);
+
+    let (source, decls) = split_decls();
+    let written = serde_json::to_value(&login).unwrap();
+    assert!(decls.accepts(decls.get("LoginOutput"), &written), "{source}");
+    assert!(!decls.accepts(decls.get("LoginInput"), &written), "{source}");
+
+    let read = json!({ "user": "me", "password": "secret" });
+    assert!(serde_json::from_value::<Login>(read.clone()).is_ok());
+    assert!(decls.accepts(decls.get("LoginInput"), &read), "{source}");
+    assert!(!decls.accepts(decls.get("LoginOutput"), &read), "{source}");
+}
+
+#[test]
+fn input_output_types() {
+    let (source, decls) = split_decls();
+
+    // types using a split type are split along with it, and refer to the same side
+    assert!(source.contains("login: LoginInput,"), "{source}");
+    assert!(source.contains("login: LoginOutput,"), "{source}");
+    decls.get("SessionInput");
+
+    // a default only makes fields optional when reading
+    assert!(decls.accepts(decls.get("DefaultsInput"), &json!({})), "{source}");
+    assert!(!decls.accepts(decls.get("DefaultsOutput"), &json!({})), "{source}");
+
+    // types read the way they are written stay as one
+    decls.get("Inner");
+    decls.get("External");
+    decls.get("Skipped");
+    assert!(!source.contains("InnerInput"), "{source}");
+}
+
+#[test]
+fn proxied_types() {
+    let (source, _) = decls();
+    assert!(source.contains("export type Email = string"), "{source}");
+    assert!(source.contains("export type Version = string"), "{source}");
+    assert!(source.contains("email: Email,"), "{source}");
+
+    assert_conforms("Wrapped", &[Wrapped { inner: inner(), cached: 0 }], false);
+    assert_conforms("Version", &[Version { major: 1, minor: 2 }], false);
+    assert_conforms("Profile", &[Profile { email: Email("me@example.com".to_string()), version: Version { major: 1, minor: 0 } }], false);
+    assert_conforms("Level", &[Level(3)], false);
+    assert_rejects("Version", json!({ "major": 1, "minor": 2 }));
+
+    // read through another type than it is written as
+    let (source, decls) = split_decls();
+    assert!(decls.accepts(decls.get("LevelInput"), &json!(3)), "{source}");
+    assert!(decls.accepts(decls.get("LevelOutput"), &json!("level 3")), "{source}");
+    assert!(serde_json::from_value::<Level>(json!(3)).is_ok());
+}
+
+#[test]
+fn renamed_and_catch_all_variants() {
+    assert_conforms("Event", &[
+        Event::Created { user_id: 1, created_at: 2 },
+        Event::Deleted { user_id: 1 },
+        Event::Archived,
+        Event::Unknown,
+    ], false);
+
+    // anything unknown is read as the catch-all
+    let unknown = json!({ "kind": "Renamed" });
+    assert_eq!(serde_json::from_value::<Event>(unknown.clone()).unwrap(), Event::Unknown);
+    let (source, decls) = decls();
+    assert!(decls.accepts(decls.get("Event"), &unknown), "{source}");
+    assert_rejects("Event", json!({ "kind": "Created", "user_id": 1, "createdAt": 2 }));
+
+    // aliases are only read
+    let (source, decls) = split_decls();
+    let aliased = json!({ "kind": "Created", "userId": 1, "when": 2 });
+    assert!(serde_json::from_value::<Event>(aliased.clone()).is_ok());
+    assert!(decls.accepts(decls.get("EventInput"), &aliased), "{source}");
+    assert!(!decls.accepts(decls.get("EventOutput"), &aliased), "{source}");
+    assert!(source.contains(r#"| { kind: "removed" }"#), "{source}");
+}
+
+#[test]
+fn tagged_structs() {
+    let (source, _) = decls();
+    assert!(source.contains("export interface Point {\n  type: \"point\",\n  x: number,"), "{source}");
+
+    assert_conforms("Point", &[Point { x: 1, y: 2 }], false);
+    assert_rejects("Point", json!({ "x": 1, "y": 2 }));
+    assert_rejects("Point", json!({ "type": "Point", "x": 1, "y": 2 }));
+
+    // serde reads the struct without looking at its tag
+    assert_eq!(serde_json::from_value::<Point>(json!({ "x": 1, "y": 2 })).unwrap(), Point { x: 1, y: 2 });
+}
+
+#[test]
+fn flattened_enums_and_maps() {
+    let (source, _) = decls();
+    assert!(source.contains("export type Drawing = {\n  id: number,\n} & Shape"), "{source}");
+    assert!(source.contains("} & Record<string, unknown>"), "{source}");
+
+    assert_conforms("Drawing", &[
+        Drawing { id: 1, shape: Shape::Circle { radius: 2 } },
+        Drawing { id: 1, shape: Shape::Square { side: 2 } },
+    ], false);
+    assert_rejects("Drawing", json!({ "id": 1 }));
+    assert_rejects("Drawing", json!({ "id": 1, "shape": "Circle", "side": 2 }));
+
+    let rest = HashMap::from([("color".to_string(), json!("red"))]);
+    assert_conforms("Extras", &[
+        Extras { name: "one".to_string(), inner: Some(inner()), rest: rest.clone() },
+        Extras { name: "one".to_string(), inner: None, rest },
+    ], false);
+
+    assert_conforms("Layer", &[
+        Layer::Drawn { name: "one".to_string(), shape: Some(Shape::Circle { radius: 2 }) },
+        Layer::Drawn { name: "one".to_string(), shape: None },
+    ], false);
+    assert!(source.contains("} & (Shape | {}) }"), "{source}");
+}
+
+#[test]
+fn serde_helpers() {
+    let (source, _) = decls();
+    assert!(source.contains("  after: string,\n  retries: number,\n  limits: Record<string, number>,"), "{source}");
+
+    let timeout = Timeout { after: Duration::from_secs(5), retries: 3, limits: HashMap::from([("daily".to_string(), 10)]) };
+    assert_conforms("Timeout", &[&timeout], false);
+    assert_rejects("Timeout", json!({ "after": { "secs": 5, "nanos": 0 }, "retries": 3, "limits": {} }));
+
+    // read as text, but written as a number
+    let (source, decls) = split_decls();
+    let read = json!({ "after": "5s", "retries": "3", "limits": {} });
+    assert!(serde_json::from_value::<Timeout>(read.clone()).is_ok());
+    assert!(decls.accepts(decls.get("TimeoutInput"), &read), "{source}");
+    assert!(!decls.accepts(decls.get("TimeoutOutput"), &read), "{source}");
+}
+
+#[test]
+fn per_target_literals() {
+    let (source, _) = decls();
+    // each target takes its own literal over one for every target, and its own type over one for another target
+    assert!(source.contains("  value: number | string,\n  unit: string,\n  inner: Inner,"), "{source}");
+
+    assert_conforms("Reading", &[
+        Reading { value: json!(1.5), unit: "kg".to_string(), inner: inner() },
+        Reading { value: json!("n/a"), unit: "kg".to_string(), inner: inner() },
+    ], false);
+    assert_rejects("Reading", json!({ "value": true, "unit": "kg", "inner": { "x": 1, "label": "one" } }));
+}
+
+#[test]
+fn well_known_types() {
+    let (source, _) = decls();
+    assert!(source.contains("  id: string,\n  path: string,\n  from: string,\n  took: { secs: number, nanos: number },\n  meta: Record<string, unknown>,\n  on: [number, number],"), "{source}");
+
+    let upload = Upload {
+        id: uuid::Uuid::nil(),
+        path: PathBuf::from("uploads/one.txt"),
+        from: IpAddr::from([127, 0, 0, 1]),
+        took: Duration::from_millis(1500),
+        meta: json!({ "size": 3 }).as_object().unwrap().clone(),
+        on: time::Date::from_ordinal_date(2024, 60).unwrap(),
+    };
+    assert_conforms("Upload", &[&upload], false);
+    assert_rejects("Upload", json!({ "id": "", "path": "", "from": "", "took": 1.5, "meta": {}, "on": [2024, 60] }));
+    // nothing here enables `time`'s `serde-human-readable` feature, so dates are written as their year and day
+    assert_rejects("Upload", json!({ "id": "", "path": "", "from": "", "took": { "secs": 1, "nanos": 0 }, "meta": {}, "on": "2024-02-29" }));
+
+    // names given by path still win over the defaults
+    let source = support::render_all(TypeScript::new().with_path_name("Duration", "number"));
+    assert!(source.contains("  took: number,"), "{source}");
+}
+
+#[test]
+fn wide_integers() {
+    let value = serde_json::to_value(Snowflake { id: 1 << 60, shard: 1, history: vec![-1], count: 1, total: u64::MAX }).unwrap();
+
+    // by default every wide integer is marked, even without a style of its own
+    let (source, _) = decls();
+    let marked = "  /**\n   * Unique across shards\n   * @precision-unsafe\n   */\n  id: number,\n  shard: number,\n  /** @precision-unsafe */\n  history: number[],\n  /** @precision-unsafe */\n  count: number,\n  total: string,";
+    assert!(source.contains(marked), "{source}");
+    assert_conforms("Snowflake", &[&value], false);
+    assert_rejects("Snowflake", json!({ "id": 1, "shard": 1, "history": [], "count": 1, "total": 1 }));
+
+    let source = support::render_all(TypeScript::new().with_int_style(IntStyle::Number));
+    assert!(source.contains(marked), "{source}");
+
+    // the field's own style wins over the target's
+    let source = support::render_all(TypeScript::new().with_int_style(IntStyle::BigInt));
+    assert!(source.contains("  /** Unique across shards */\n  id: bigint,\n  shard: number,\n  history: bigint[],\n  /** @precision-unsafe */\n  count: number,\n  total: string,"), "{source}");
+    // serde_json still writes numbers, which only a parser that knows to make `bigint`s of them reads as such
+    let decls = Decls::parse(&source);
+    assert!(!decls.accepts(decls.get("Snowflake"), &value), "`JSON.parse` never makes a `bigint`:\n{source}");
+}
@@ -0,0 +1,75 @@
+//! Writing exports to disk, and cleaning up after types that are no longer exported
+
+use burrtype::export::{Burrxporter, FileStatus};
+use burrtype::ir::{IrExt, IrItem};
+use burrtype::prelude::*;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+#[derive(Burr, Serialize)]
+pub struct User {
+    id: u32,
+}
+
+#[derive(Burr, Serialize)]
+#[burr(mod = "extra/deep")]
+pub struct Extra {
+    note: String,
+}
+
+/// A fresh directory to export into
+fn temp_root(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("burrtype-export-{}-{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+fn export(root: &Path, items: Vec<IrItem>) -> Vec<(String, FileStatus)> {
+    let mut exporter = Burrxporter::from_items(items);
+    exporter.resolve_all("types").with_root(root);
+    let report = exporter.export("ts", TypeScript::new()).expect("types should export");
+
+    let mut files: Vec<_> = report.files.into_iter().map(|file| (file.path.to_string_lossy().replace('\\', "/"), file.status)).collect();
+    files.sort_by(|(a, _), (b, _)| a.cmp(b));
+    files
+}
+
+#[test]
+fn unchanged_exports_are_left_alone() {
+    let root = temp_root("unchanged");
+    let items = || vec![User::get_ir(), Extra::get_ir()];
+
+    assert_eq!(export(&root, items()), [
+        ("ts/extra/deep.ts".to_string(), FileStatus::Written),
+        // `extra` only holds a module, so its index has nothing to write
+        ("ts/extra/index.ts".to_string(), FileStatus::Skipped),
+        ("ts/types.ts".to_string(), FileStatus::Written),
+    ]);
+    assert_eq!(export(&root, items()), [
+        ("ts/extra/deep.ts".to_string(), FileStatus::Unchanged),
+        ("ts/extra/index.ts".to_string(), FileStatus::Skipped),
+        ("ts/types.ts".to_string(), FileStatus::Unchanged),
+    ]);
+}
+
+#[test]
+fn stale_files_are_deleted() {
+    let root = temp_root("stale");
+    export(&root, vec![User::get_ir(), Extra::get_ir()]);
+
+    // every file written is listed under its export as `target\tfile`
+    let manifest = std::fs::read_to_string(root.join(".burrtype-manifest")).unwrap();
+    assert_eq!(manifest, "ts\tts/extra/deep.ts\nts\tts/types.ts\n");
+    assert!(root.join("ts/extra/deep.ts").exists());
+
+    let files = export(&root, vec![User::get_ir()]);
+    assert_eq!(files, [
+        ("ts/extra/deep.ts".to_string(), FileStatus::Deleted),
+        ("ts/types.ts".to_string(), FileStatus::Unchanged),
+    ]);
+    assert!(!root.join("ts/extra").exists(), "directories left empty are removed");
+    assert!(root.join("ts/types.ts").exists());
+
+    let manifest = std::fs::read_to_string(root.join(".burrtype-manifest")).unwrap();
+    assert_eq!(manifest, "ts\tts/types.ts\n");
+}
@@ -0,0 +1,123 @@
+//! The build script scanner reads these same sources, so its exports should match the derive's exactly
+
+mod scanned;
+
+use burrtype::build::scan;
+use burrtype::export::{Burrxporter, ExportError, Target};
+use burrtype::prelude::*;
+use std::path::{Path, PathBuf};
+
+fn render(mut exporter: Burrxporter) -> Vec<(PathBuf, String)> {
+    exporter.resolve_all("types");
+    let mut files: Vec<_> = TypeScript::new()
+        .render(Path::new("types"), &exporter)
+        .expect("types should render")
+        .into_iter()
+        .map(|file| (file.path, file.contents))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Writes a crate with the given files into a fresh temporary directory, returning its root file
+fn temp_crate(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("burrtype-scan-{}-{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    for (path, contents) in files {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+    dir.join("lib.rs")
+}
+
+#[test]
+fn scan_matches_derive() {
+    let scanned = scan("scan", "tests/scan.rs").expect("fixtures should scan");
+
+    let mut files: Vec<_> = scanned.files.iter().map(|file| file.to_string_lossy().replace('\\', "/")).collect();
+    files.sort();
+    assert_eq!(files, [
+        "tests/scan.rs",
+        "tests/scanned/mod.rs",
+        "tests/scanned/tagging.rs",
+        "tests/scanned/users.rs",
+    ]);
+
+    let derived = render(Burrxporter::new());
+    let scanned = render(scanned.into_exporter());
+    assert_eq!(scanned, derived);
+
+    let types = &derived.iter().find(|(path, _)| path.ends_with("types.ts")).expect("default mod should be written").1;
+    assert!(types.contains("export type Level =\n  | -1\n  | 0\n  | 16\n  | 17\n;"), "{types}");
+    assert!(types.contains("  views?: string,\n"), "helpers keep the field's `Option`: {types}");
+    assert!(types.contains("export type Mask =\n  | 18446744073709551614\n  | 18446744073709551615\n;"), "{types}");
+    assert!(derived.iter().any(|(path, _)| path.ends_with("people.ts")), "mod overrides are kept");
+}
+
+#[test]
+fn discriminants_must_be_literals() {
+    let entry = temp_crate("discriminant", &[("lib.rs", "
+        #[derive(Burr, Serialize_repr)]
+        #[repr(u8)]
+        pub enum Flags { A = 1 << 2, B }
+    ")]);
+    assert!(matches!(scan("fixture", entry), Err(ExportError::Parse { .. })));
+
+    let entry = temp_crate("discriminant_overflow", &[("lib.rs", "
+        #[derive(Burr, Serialize_repr)]
+        #[repr(u128)]
+        pub enum Huge { A = 0x7FFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF, B }
+    ")]);
+    assert!(matches!(scan("fixture", entry), Err(ExportError::Parse { .. })), "discriminants past `i128::MAX` are rejected");
+}
+
+#[test]
+fn flattened_types_must_be_scanned() {
+    let entry = temp_crate("flatten", &[("lib.rs", "
+        #[derive(Burr, Serialize)]
+        pub struct Outer {
+            #[serde(flatten)]
+            inner: other::Inner,
+        }
+    ")]);
+    assert!(scan("fixture", entry).is_err());
+}
+
+#[test]
+fn one_sided_proxies_are_rejected() {
+    let entry = temp_crate("proxy", &[("lib.rs", "
+        #[derive(Burr, Serialize)]
+        #[serde(into = \"String\")]
+        pub struct Version { major: u32 }
+    ")]);
+    match scan("fixture", entry) {
+        Err(ExportError::Parse { source, .. }) => assert!(source.to_string().contains("give both `into` and `from`"), "{source}"),
+        other => panic!("expected a parse error, got {other:?}"),
+    }
+}
+
+#[test]
+fn helper_types_need_a_helper() {
+    let entry = temp_crate("with_type", &[("lib.rs", "
+        #[derive(Burr, Serialize)]
+        pub struct Visit { #[burr(with_type = String)] at: u64 }
+    ")]);
+    match scan("fixture", entry) {
+        Err(ExportError::Parse { source, .. }) => assert!(source.to_string().contains("uses none"), "{source}"),
+        other => panic!("expected a parse error, got {other:?}"),
+    }
+}
+
+#[test]
+fn missing_mods_are_reported() {
+    let entry = temp_crate("missing", &[
+        ("lib.rs", "mod present; mod absent;"),
+        ("present/mod.rs", "#[path = \"../elsewhere.rs\"] mod moved;"),
+        ("elsewhere.rs", ""),
+    ]);
+    match scan("fixture", entry) {
+        Err(ExportError::ModNotFound { name, .. }) => assert_eq!(name, "absent"),
+        other => panic!("expected a missing mod, got {other:?}"),
+    }
+}
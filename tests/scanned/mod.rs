@@ -0,0 +1,64 @@
+//! Types both derived and scanned by `tests/scan.rs`, spelling paths and modules in as many ways as the scanner understands
+
+#![allow(dead_code)]
+
+use burrtype::prelude::*;
+use serde::Serialize;
+use serde_repr::Serialize_repr;
+
+mod users;
+#[path = "tagging.rs"]
+pub mod tags;
+
+pub mod shapes {
+    use super::*;
+
+    #[derive(Burr, Serialize)]
+    #[serde(tag = "kind")]
+    pub enum Shape {
+        Circle { radius: u32 },
+        Square { side: u32 },
+    }
+}
+
+use self::tags::*;
+use users::User as Author;
+
+/// Written with every kind of path the scanner resolves
+#[derive(Burr, Serialize)]
+pub struct Post {
+    author: Author,
+    tags: Vec<Tag>,
+    #[serde(flatten)]
+    shape: shapes::Shape,
+    meta: crate::scanned::tags::Meta,
+    level: Option<Level>,
+    mask: Mask,
+    #[serde(serialize_with = "as_text")]
+    #[burr(with_type = String)]
+    views: Option<u64>,
+}
+
+fn as_text<S: serde::Serializer>(value: &Option<u64>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&value.unwrap_or_default())
+}
+
+#[derive(Burr, Serialize_repr)]
+#[repr(i16)]
+pub enum Level {
+    Low = -1,
+    Normal,
+    High = 0x10,
+    Urgent,
+}
+
+/// Past the end of an `i64`
+#[derive(Burr, Serialize_repr)]
+#[repr(u64)]
+pub enum Mask {
+    Top = 0xFFFF_FFFF_FFFF_FFFE,
+    All,
+}
+
+#[burr]
+pub type Tags = Vec<Tag>;
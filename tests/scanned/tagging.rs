@@ -0,0 +1,15 @@
+use burrtype::prelude::*;
+use serde::Serialize;
+
+#[derive(Burr, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Tag {
+    News,
+    Opinion,
+}
+
+#[derive(Burr, Serialize)]
+pub struct Meta {
+    created: u32,
+    edited: Option<u32>,
+}
@@ -0,0 +1,12 @@
+use super::tags::Meta;
+use burrtype::prelude::*;
+use serde::Serialize;
+
+#[derive(Burr, Serialize)]
+#[burr(mod = "people")]
+pub struct User {
+    id: u32,
+    name: String,
+    #[serde(flatten)]
+    meta: Meta,
+}
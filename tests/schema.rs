@@ -0,0 +1,379 @@
+//! Schemas written from registered types, and what comparing them finds
+
+#![allow(dead_code)]
+
+use burrtype::export::{Burrxporter, Target};
+use burrtype::ir::{Direction, IrExt, IrItem};
+use burrtype::prelude::*;
+use burrtype::schema::{diff, ChangeKind, Schema, Severity};
+use std::path::Path;
+
+/// Each module holds a type before and after a single change, in `old` and `new`
+mod removed_field {
+    pub mod old {
+        #[derive(burrtype::Burr, serde::Serialize)]
+        pub struct User { id: u32, name: String }
+    }
+    pub mod new {
+        #[derive(burrtype::Burr, serde::Serialize)]
+        pub struct User { id: u32 }
+    }
+}
+
+mod optional_to_required {
+    pub mod old {
+        #[derive(burrtype::Burr, serde::Serialize)]
+        pub struct User { name: Option<String> }
+    }
+    pub mod new {
+        #[derive(burrtype::Burr, serde::Serialize)]
+        pub struct User { name: String }
+    }
+}
+
+mod required_to_optional {
+    pub mod old {
+        #[derive(burrtype::Burr, serde::Serialize)]
+        pub struct User { name: String }
+    }
+    pub mod new {
+        #[derive(burrtype::Burr, serde::Serialize)]
+        pub struct User { name: Option<String> }
+    }
+}
+
+mod type_change {
+    pub mod old {
+        #[derive(burrtype::Burr, serde::Serialize)]
+        pub struct User { id: u32 }
+    }
+    pub mod new {
+        #[derive(burrtype::Burr, serde::Serialize)]
+        pub struct User { id: String }
+    }
+}
+
+mod removed_variant {
+    pub mod old {
+        #[derive(burrtype::Burr, serde::Serialize)]
+        pub enum Status { Active, Paused }
+    }
+    pub mod new {
+        #[derive(burrtype::Burr, serde::Serialize)]
+        pub enum Status { Active }
+    }
+}
+
+mod added_variant {
+    pub mod old {
+        #[derive(burrtype::Burr, serde::Serialize)]
+        pub enum Status { Active }
+    }
+    pub mod new {
+        #[derive(burrtype::Burr, serde::Serialize)]
+        pub enum Status { Active, Paused }
+    }
+}
+
+mod repr_tag_change {
+    pub mod old {
+        #[derive(burrtype::Burr, serde::Serialize)]
+        #[serde(tag = "type")]
+        pub enum Shape { Circle { radius: u32 } }
+    }
+    pub mod new {
+        #[derive(burrtype::Burr, serde::Serialize)]
+        #[serde(tag = "kind")]
+        pub enum Shape { Circle { radius: u32 } }
+    }
+}
+
+mod renamed_type {
+    pub mod old {
+        #[derive(burrtype::Burr, serde::Serialize)]
+        pub struct User { id: u32 }
+    }
+    pub mod new {
+        #[derive(burrtype::Burr, serde::Serialize)]
+        pub struct Account { id: u32 }
+    }
+}
+
+mod new_required_field {
+    pub mod old {
+        #[derive(burrtype::Burr, serde::Serialize)]
+        pub struct User { id: u32 }
+    }
+    pub mod new {
+        #[derive(burrtype::Burr, serde::Serialize)]
+        pub struct User { id: u32, name: String }
+    }
+}
+
+mod new_optional_field {
+    pub mod old {
+        #[derive(burrtype::Burr, serde::Serialize)]
+        pub struct User { id: u32 }
+    }
+    pub mod new {
+        #[derive(burrtype::Burr, serde::Serialize)]
+        pub struct User { id: u32, name: Option<String> }
+    }
+}
+
+/// Everything a schema has to carry for targets to write it again
+mod round_trip {
+    use burrtype::prelude::*;
+    use serde::Serialize;
+    use std::time::Duration;
+
+    /// Largest page a client may ask for
+    #[burr]
+    pub const MAX_PAGE: u32 = 100;
+
+    #[burr]
+    pub const DEFAULT_USER: User = User { id: 0, joined: 0, name: String::new(), nickname: None, since: Duration::ZERO, tags: Vec::new() };
+
+    /// Identifies a user
+    #[burr]
+    pub type UserId = u64;
+
+    /// Someone using the API
+    #[derive(Burr, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct User {
+        pub id: UserId,
+        /// Milliseconds since the epoch
+        #[burr(int_style = "bigint")]
+        pub joined: u64,
+        pub name: String,
+        #[serde(skip_serializing_if = "Option::is_none", alias = "nick")]
+        pub nickname: Option<String>,
+        pub since: Duration,
+        pub tags: Vec<Tag>,
+    }
+
+    #[derive(Burr, Serialize)]
+    #[burr(mod = "tagging")]
+    pub struct Tag(#[burr(ts = "`#${string}`")] String);
+
+    #[derive(Burr, Serialize)]
+    pub struct Pair(u32, /** Second of the two */ String);
+
+    #[derive(Burr, Serialize)]
+    pub struct Empty;
+
+    #[derive(Burr, Serialize)]
+    pub enum External {
+        Unit,
+        Newtype(User),
+        Tuple(u32, u32),
+        Struct { x: u32 },
+    }
+
+    #[derive(Burr, Serialize)]
+    #[serde(tag = "type")]
+    pub enum Internal {
+        Circle { radius: u32 },
+        #[serde(rename = "square")]
+        Square { side: u32 },
+        #[serde(other)]
+        Unknown,
+    }
+
+    #[derive(Burr, Serialize)]
+    #[serde(tag = "t", content = "c")]
+    pub enum Adjacent {
+        Text(String),
+        Point { x: i32, y: i32 },
+    }
+
+    #[derive(Burr, Serialize)]
+    #[serde(untagged)]
+    pub enum Untagged {
+        Number(u32),
+        Text(String),
+    }
+
+    /// Written as its discriminant
+    #[derive(Burr, serde_repr::Serialize_repr)]
+    #[repr(u8)]
+    #[burr(enum_style = "union_with_values")]
+    pub enum Level {
+        Low = 1,
+        High = 10,
+    }
+
+    /// Past the end of an `i64`, and of a `u64`
+    #[derive(Burr, serde_repr::Serialize_repr)]
+    #[repr(i128)]
+    pub enum Mask {
+        Low = -1,
+        Top = u64::MAX as i128,
+        Past,
+    }
+
+    #[derive(Burr, Serialize)]
+    pub struct Event {
+        pub level: Level,
+        #[serde(flatten)]
+        pub shape: Internal,
+        #[serde(flatten)]
+        pub extra: std::collections::HashMap<String, String>,
+    }
+}
+
+/// Renders every file of an already resolved exporter
+fn render(exporter: &Burrxporter) -> Vec<(std::path::PathBuf, String)> {
+    TypeScript::new()
+        .render(Path::new("types"), exporter)
+        .expect("types should render")
+        .into_iter()
+        .map(|file| (file.path, file.contents))
+        .collect()
+}
+
+/// Builds a schema of the given items, with `old` and `new` left out of their paths so both sides line up
+fn schema(items: Vec<IrItem>) -> Schema {
+    let json = Burrxporter::from_items(items).to_schema().to_json().replace("::old::", "::").replace("::new::", "::");
+    Schema::from_json(&json).expect("schema should load")
+}
+
+fn changes(old: Vec<IrItem>, new: Vec<IrItem>) -> Vec<(Severity, ChangeKind)> {
+    diff(&schema(old), &schema(new)).changes.into_iter().map(|change| (change.severity, change.kind)).collect()
+}
+
+#[test]
+fn classifies_changes() {
+    use Severity::*;
+
+    let cases = vec![
+        (
+            "removed field",
+            vec![removed_field::old::User::get_ir()],
+            vec![removed_field::new::User::get_ir()],
+            vec![(Breaking, ChangeKind::FieldRemoved)],
+        ),
+        (
+            "optional to required",
+            vec![optional_to_required::old::User::get_ir()],
+            vec![optional_to_required::new::User::get_ir()],
+            vec![(Breaking, ChangeKind::OptionalityChanged { optional: false })],
+        ),
+        (
+            "required to optional",
+            vec![required_to_optional::old::User::get_ir()],
+            vec![required_to_optional::new::User::get_ir()],
+            vec![(Breaking, ChangeKind::OptionalityChanged { optional: true })],
+        ),
+        (
+            "type change",
+            vec![type_change::old::User::get_ir()],
+            vec![type_change::new::User::get_ir()],
+            vec![(Breaking, ChangeKind::FieldTypeChanged { from: "u32".to_string(), to: "String".to_string() })],
+        ),
+        (
+            "removed variant",
+            vec![removed_variant::old::Status::get_ir()],
+            vec![removed_variant::new::Status::get_ir()],
+            vec![(Breaking, ChangeKind::VariantRemoved)],
+        ),
+        (
+            "added variant",
+            vec![added_variant::old::Status::get_ir()],
+            vec![added_variant::new::Status::get_ir()],
+            vec![(NonBreaking, ChangeKind::VariantAdded)],
+        ),
+        (
+            "repr tag change",
+            vec![repr_tag_change::old::Shape::get_ir()],
+            vec![repr_tag_change::new::Shape::get_ir()],
+            vec![(Breaking, ChangeKind::ReprChanged {
+                from: "internally tagged by `type`".to_string(),
+                to: "internally tagged by `kind`".to_string(),
+            })],
+        ),
+        (
+            "renamed type",
+            vec![renamed_type::old::User::get_ir()],
+            vec![renamed_type::new::Account::get_ir()],
+            vec![(Breaking, ChangeKind::ItemRenamed { from: "User".to_string(), to: "Account".to_string() })],
+        ),
+        (
+            "new required field",
+            vec![new_required_field::old::User::get_ir()],
+            vec![new_required_field::new::User::get_ir()],
+            vec![(Breaking, ChangeKind::FieldAdded { optional: false })],
+        ),
+        (
+            "new optional field",
+            vec![new_optional_field::old::User::get_ir()],
+            vec![new_optional_field::new::User::get_ir()],
+            vec![(NonBreaking, ChangeKind::FieldAdded { optional: true })],
+        ),
+    ];
+
+    for (name, old, new, expected) in cases {
+        assert_eq!(changes(old, new), expected, "{name}");
+    }
+}
+
+#[test]
+fn classifies_skipped_changes() {
+    use Direction::*;
+    use Severity::*;
+
+    let cases = [
+        // no longer written
+        (None, Some(Serialize), Breaking),
+        (Some(Deserialize), Some(Serialize), Breaking),
+        // now read
+        (Some(Deserialize), None, Breaking),
+        // written again, or no longer read
+        (Some(Serialize), None, NonBreaking),
+        (None, Some(Deserialize), NonBreaking),
+        (Some(Serialize), Some(Deserialize), NonBreaking),
+    ];
+
+    for (from, to, severity) in cases {
+        assert_eq!(ChangeKind::SkippedChanged { from, to }.severity(), severity, "{from:?} to {to:?}");
+    }
+}
+
+#[test]
+fn unchanged_schemas_have_no_changes() {
+    let items = || vec![removed_field::old::User::get_ir(), removed_variant::old::Status::get_ir()];
+    assert_eq!(changes(items(), items()), Vec::new());
+}
+
+#[test]
+fn schemas_round_trip() {
+    let items = Burrxporter::new().type_registry.into_values().filter(|item| item.path().starts_with("schema::round_trip::"));
+    let mut exporter = Burrxporter::from_items(items);
+    exporter.resolve_all("types");
+
+    let json = exporter.to_schema().to_json();
+    let schema = Schema::from_json(&json).expect("schema should load");
+    assert_eq!(schema.to_json(), json);
+
+    let loaded = Burrxporter::from_schema(&schema).expect("schema should rebuild an exporter");
+    let (written, read) = (render(&exporter), render(&loaded));
+    assert_eq!(read, written);
+
+    // make sure everything above made it into what was compared
+    let all: String = written.iter().map(|(_, contents)| contents.as_str()).collect();
+    for expected in [
+        "/** Largest page a client may ask for */\nexport const MAX_PAGE = 100 as const;",
+        "/**\n * Identifies a user\n * @precision-unsafe\n */\nexport type UserId = number",
+        "  /** Milliseconds since the epoch */\n  joined: bigint,",
+        "export type Tag = `#${string}`",
+        "export type Pair = [number, /** Second of the two */ string]",
+        "export const LEVEL = [1, 10] as const;",
+        "| { type: \"square\", side: number }",
+        "| { t: \"Text\", c: string }",
+        "& Record<string, unknown>",
+        "  | -1\n  | 18446744073709551615\n  | 18446744073709551616\n",
+    ] {
+        assert!(all.contains(expected), "missing `{expected}` in:\n{all}");
+    }
+}
@@ -0,0 +1,20 @@
+#![allow(dead_code)]
+
+pub mod ts;
+
+use burrtype::export::{Burrxporter, Target};
+use burrtype::prelude::*;
+use std::path::Path;
+
+/// Renders every registered type into a single TypeScript file
+pub fn render_all(target: TypeScript) -> String {
+    let mut exporter = Burrxporter::new();
+    exporter.resolve_all("types");
+
+    let files = target
+        .with_file_map(ModFileMap::Inline)
+        .render(Path::new("types"), &exporter)
+        .expect("types should render");
+    assert_eq!(files.len(), 1, "inline exports write a single file");
+    files.into_iter().next().unwrap().contents
+}
@@ -0,0 +1,333 @@
+//! Just enough of TypeScript to check JSON values against the types we generate
+//! Only covers what the exporter writes: interfaces, type aliases, unions, intersections, object literals, records, tuples, arrays and literals
+
+use serde_json::Value;
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Ty {
+    String,
+    Number,
+    /// Only made by parsers that know to read large numbers as `bigint`, never by `JSON.parse`, so no JSON value is one
+    BigInt,
+    Boolean,
+    Null,
+    Unknown,
+    StringLit(String),
+    NumberLit(f64),
+    Ref(String),
+    Object(Vec<Member>),
+    Tuple(Vec<Ty>),
+    Array(Box<Ty>),
+    /// `Record<string, T>`, an object with any keys
+    Record(Box<Ty>),
+    Union(Vec<Ty>),
+    Intersection(Vec<Ty>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Member {
+    pub name: String,
+    pub optional: bool,
+    pub ty: Ty,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Num(f64),
+    Punct(char),
+}
+
+/// Every type declared in a file, by name
+#[derive(Debug, Default)]
+pub struct Decls {
+    pub types: HashMap<String, Ty>,
+}
+
+impl Decls {
+    pub fn parse(source: &str) -> Self {
+        let tokens = tokenize(source);
+        let mut parser = Parser { tokens, pos: 0 };
+        let mut decls = Decls::default();
+
+        while parser.pos < parser.tokens.len() {
+            if !parser.eat_ident("export") {
+                parser.pos += 1;
+                continue;
+            }
+
+            if parser.eat_ident("interface") {
+                let name = parser.ident();
+                parser.expect('{');
+                let members = parser.members();
+                decls.types.insert(name, Ty::Object(members));
+            }
+            else if parser.eat_ident("type") {
+                let name = parser.ident();
+                parser.expect('=');
+                let ty = parser.ty();
+                parser.eat(';');
+                decls.types.insert(name, ty);
+            }
+        }
+
+        decls
+    }
+
+    /// Gets the declared type with the given name
+    pub fn get(&self, name: &str) -> &Ty {
+        self.types.get(name).unwrap_or_else(|| panic!("`{name}` was not exported"))
+    }
+
+    /// Checks whether a value is assignable to a type, rejecting properties the type does not know about
+    pub fn accepts(&self, ty: &Ty, value: &Value) -> bool {
+        self.matches(ty, value, true)
+    }
+
+    fn matches(&self, ty: &Ty, value: &Value, exact: bool) -> bool {
+        match (ty, value) {
+            (Ty::String, Value::String(_)) => true,
+            (Ty::Number, Value::Number(_)) => true,
+            (Ty::Boolean, Value::Bool(_)) => true,
+            (Ty::Null, Value::Null) => true,
+            (Ty::Unknown, _) => true,
+            (Ty::StringLit(lit), Value::String(s)) => lit == s,
+            (Ty::NumberLit(lit), Value::Number(n)) => n.as_f64() == Some(*lit),
+            (Ty::Ref(name), value) => self.matches(self.get(name), value, exact),
+            (Ty::Object(members), Value::Object(map)) => {
+                let known = members.iter().all(|member| match map.get(&member.name) {
+                    Some(value) => self.matches(&member.ty, value, true),
+                    None => member.optional,
+                });
+                let extra = map.keys().any(|key| !members.iter().any(|member| &member.name == key));
+                known && !(exact && extra)
+            }
+            (Ty::Tuple(items), Value::Array(values)) => {
+                items.len() == values.len() && items.iter().zip(values).all(|(ty, value)| self.matches(ty, value, true))
+            }
+            (Ty::Array(item), Value::Array(values)) => values.iter().all(|value| self.matches(item, value, true)),
+            (Ty::Record(item), Value::Object(map)) => map.values().all(|value| self.matches(item, value, true)),
+            (Ty::Union(options), value) => options.iter().any(|ty| self.matches(ty, value, exact)),
+            (Ty::Intersection(parts), Value::Object(map)) => {
+                // each part only sees some of the properties, so the full set is checked against all of them together
+                let all_known = self.keys(ty);
+                parts.iter().all(|ty| self.matches(ty, value, false))
+                    && !(exact && !parts.iter().any(|ty| self.is_record(ty)) && map.keys().any(|key| !all_known.contains(key)))
+            }
+            _ => false,
+        }
+    }
+
+    fn keys(&self, ty: &Ty) -> Vec<String> {
+        match ty {
+            Ty::Ref(name) => self.keys(self.get(name)),
+            Ty::Object(members) => members.iter().map(|member| member.name.clone()).collect(),
+            Ty::Intersection(parts) | Ty::Union(parts) => parts.iter().flat_map(|ty| self.keys(ty)).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn is_record(&self, ty: &Ty) -> bool {
+        match ty {
+            Ty::Ref(name) => self.is_record(self.get(name)),
+            Ty::Record(_) => true,
+            _ => false,
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '/' if chars.peek() == Some(&'*') => {
+                let mut last = ' ';
+                for c in chars.by_ref() {
+                    if last == '*' && c == '/' {
+                        break;
+                    }
+                    last = c;
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '"' | '\'' => {
+                let mut s = String::new();
+                for next in chars.by_ref() {
+                    if next == c {
+                        break;
+                    }
+                    s.push(next);
+                }
+                tokens.push(Token::Str(s));
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                let mut s = c.to_string();
+                while let Some(&next) = chars.peek() {
+                    if !(next.is_ascii_digit() || next == '.') {
+                        break;
+                    }
+                    s.push(next);
+                    chars.next();
+                }
+                tokens.push(Token::Num(s.parse().expect("number literal")));
+            }
+            c if c.is_alphabetic() || c == '_' || c == '$' => {
+                let mut s = c.to_string();
+                while let Some(&next) = chars.peek() {
+                    if !(next.is_alphanumeric() || next == '_' || next == '$') {
+                        break;
+                    }
+                    s.push(next);
+                    chars.next();
+                }
+                tokens.push(Token::Ident(s));
+            }
+            c => tokens.push(Token::Punct(c)),
+        }
+    }
+
+    tokens
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(&Token::Punct(c)) {
+            self.pos += 1;
+            true
+        }
+        else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) {
+        assert!(self.eat(c), "expected `{c}`, found {:?}", self.peek());
+    }
+
+    fn eat_ident(&mut self, ident: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Ident(s)) if s == ident) {
+            self.pos += 1;
+            true
+        }
+        else {
+            false
+        }
+    }
+
+    fn ident(&mut self) -> String {
+        match self.tokens.get(self.pos).cloned() {
+            Some(Token::Ident(s)) => {
+                self.pos += 1;
+                s
+            }
+            other => panic!("expected an identifier, found {other:?}"),
+        }
+    }
+
+    /// Parses members up to and including the closing brace
+    fn members(&mut self) -> Vec<Member> {
+        let mut members = Vec::new();
+        while !self.eat('}') {
+            let name = match self.tokens.get(self.pos).cloned() {
+                Some(Token::Ident(s) | Token::Str(s)) => s,
+                other => panic!("expected a property name, found {other:?}"),
+            };
+            self.pos += 1;
+            let optional = self.eat('?');
+            self.expect(':');
+            let ty = self.ty();
+            members.push(Member { name, optional, ty });
+            if !self.eat(',') {
+                self.eat(';');
+            }
+        }
+        members
+    }
+
+    fn ty(&mut self) -> Ty {
+        self.eat('|');
+        let mut options = vec![self.intersection()];
+        while self.eat('|') {
+            options.push(self.intersection());
+        }
+        if options.len() == 1 { options.pop().unwrap() } else { Ty::Union(options) }
+    }
+
+    fn intersection(&mut self) -> Ty {
+        let mut parts = vec![self.postfix()];
+        while self.eat('&') {
+            parts.push(self.postfix());
+        }
+        if parts.len() == 1 { parts.pop().unwrap() } else { Ty::Intersection(parts) }
+    }
+
+    fn postfix(&mut self) -> Ty {
+        let mut ty = self.primary();
+        while self.peek() == Some(&Token::Punct('[')) && self.tokens.get(self.pos + 1) == Some(&Token::Punct(']')) {
+            self.pos += 2;
+            ty = Ty::Array(Box::new(ty));
+        }
+        ty
+    }
+
+    fn primary(&mut self) -> Ty {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        match token {
+            Some(Token::Str(s)) => Ty::StringLit(s),
+            Some(Token::Num(n)) => Ty::NumberLit(n),
+            Some(Token::Ident(s)) => match s.as_str() {
+                "string" => Ty::String,
+                "number" => Ty::Number,
+                "bigint" => Ty::BigInt,
+                "boolean" => Ty::Boolean,
+                "null" => Ty::Null,
+                "unknown" => Ty::Unknown,
+                "Record" => {
+                    self.expect('<');
+                    self.ty();
+                    self.expect(',');
+                    let item = self.ty();
+                    self.expect('>');
+                    Ty::Record(Box::new(item))
+                }
+                _ => Ty::Ref(s),
+            },
+            Some(Token::Punct('{')) => Ty::Object(self.members()),
+            Some(Token::Punct('[')) => {
+                let mut items = Vec::new();
+                while !self.eat(']') {
+                    items.push(self.ty());
+                    self.eat(',');
+                }
+                Ty::Tuple(items)
+            }
+            Some(Token::Punct('(')) => {
+                let ty = self.ty();
+                self.expect(')');
+                ty
+            }
+            other => panic!("expected a type, found {other:?}"),
+        }
+    }
+}